@@ -0,0 +1,109 @@
+use crate::{
+    error::Result,
+    sync::{self, AuthorStats, LogStart},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+///
+#[derive(Hash, Clone, PartialEq, Debug)]
+pub struct AuthorStatsParams {
+    /// where the walked history starts
+    pub start: LogStart,
+    /// also count lines added and removed
+    pub with_lines: bool,
+}
+
+type ResultType = Vec<AuthorStats>;
+struct Request<R, A>(R, A);
+
+/// aggregates the log per author in the background
+pub struct AsyncAuthorStats {
+    current:
+        Arc<Mutex<Option<Request<AuthorStatsParams, ResultType>>>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncAuthorStats {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            current: Arc::new(Mutex::new(None)),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    ///
+    pub fn last(
+        &self,
+    ) -> Result<Option<(AuthorStatsParams, ResultType)>> {
+        let c = self.current.lock()?;
+
+        if let Some(c) = c.as_ref() {
+            Ok(Some((c.0.clone(), c.1.clone())))
+        } else {
+            Ok(None)
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    /// ignored while a request is pending
+    pub fn request(
+        &mut self,
+        params: AuthorStatsParams,
+    ) -> Result<()> {
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        log::trace!("request: {:?}", params);
+
+        let arc_current = Arc::clone(&self.current);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            if let Err(e) = Self::fetch_helper(params, &arc_current) {
+                log::error!("author stats error: {}", e);
+            }
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::AuthorStats)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+
+    fn fetch_helper(
+        params: AuthorStatsParams,
+        arc_current: &Arc<
+            Mutex<Option<Request<AuthorStatsParams, ResultType>>>,
+        >,
+    ) -> Result<()> {
+        let res = sync::get_author_stats(
+            CWD,
+            &params.start,
+            params.with_lines,
+        )?;
+
+        let mut current = arc_current.lock()?;
+        *current = Some(Request(params, res));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    error::Result,
+    sync::{self, CommitId, FileBlame},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+///
+#[derive(Hash, Clone, PartialEq, Debug)]
+pub struct BlameParams {
+    /// path of the file to blame
+    pub file_path: String,
+    /// commit to blame the file at, `HEAD` if `None`
+    pub commit_id: Option<CommitId>,
+}
+
+/// `None` if the file could not be blamed (untracked, binary..)
+type ResultType = Option<FileBlame>;
+struct Request<R, A>(R, A);
+
+///
+pub struct AsyncBlame {
+    current: Arc<Mutex<Option<Request<BlameParams, ResultType>>>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncBlame {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            current: Arc::new(Mutex::new(None)),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    ///
+    pub fn last(
+        &mut self,
+    ) -> Result<Option<(BlameParams, ResultType)>> {
+        let c = self.current.lock()?;
+
+        if let Some(c) = c.as_ref() {
+            Ok(Some((c.0.clone(), c.1.clone())))
+        } else {
+            Ok(None)
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    ///
+    pub fn request(&mut self, params: BlameParams) -> Result<()> {
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        log::trace!("request: {:?}", params);
+
+        let arc_current = Arc::clone(&self.current);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            Self::get_blame_helper(params, &arc_current);
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::Blame)
+                .expect("error sending blame");
+        });
+
+        Ok(())
+    }
+
+    fn get_blame_helper(
+        params: BlameParams,
+        arc_current: &Arc<
+            Mutex<Option<Request<BlameParams, ResultType>>>,
+        >,
+    ) {
+        let res = sync::blame_file(
+            CWD,
+            &params.file_path,
+            params.commit_id,
+        );
+
+        let res = match res {
+            Ok(blame) => Some(blame),
+            Err(e) => {
+                log::error!("blame error: {}", e);
+                None
+            }
+        };
+
+        if let Ok(mut current) = arc_current.lock() {
+            *current = Some(Request(params, res));
+        }
+    }
+}
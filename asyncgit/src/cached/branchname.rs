@@ -1,12 +1,12 @@
 use crate::{
-    error::Result,
+    error::{Error, Result},
     sync::{self, branch::get_branch_name},
 };
 use sync::Head;
 
 ///
 pub struct BranchName {
-    last_result: Option<(Head, String)>,
+    last_result: Option<(Head, Option<String>)>,
     repo_path: String,
 }
 
@@ -19,16 +19,15 @@ impl BranchName {
         }
     }
 
-    ///
+    /// name of the checked out branch or a description of the
+    /// detached `HEAD`
     pub fn lookup(&mut self) -> Result<String> {
         let current_head =
             sync::get_head_tuple(self.repo_path.as_str())?;
 
-        if let Some((last_head, branch_name)) =
-            self.last_result.as_ref()
-        {
-            if *last_head == current_head {
-                return Ok(branch_name.clone());
+        if let Some(last) = self.last_result.as_ref() {
+            if last.0 == current_head {
+                return Ok(Self::display(last));
             }
         }
 
@@ -37,12 +36,35 @@ impl BranchName {
 
     ///
     pub fn last(&self) -> Option<String> {
-        self.last_result.as_ref().map(|last| last.1.clone())
+        self.last_result.as_ref().map(Self::display)
+    }
+
+    /// name of the checked out branch, `None` if `HEAD` is detached
+    pub fn last_branch(&self) -> Option<String> {
+        self.last_result.as_ref().and_then(|last| last.1.clone())
+    }
+
+    ///
+    pub fn is_detached(&self) -> bool {
+        matches!(self.last_result, Some((_, None)))
+    }
+
+    fn display((head, name): &(Head, Option<String>)) -> String {
+        name.clone().unwrap_or_else(|| {
+            format!("HEAD detached at {}", head.id.get_short_string())
+        })
     }
 
     fn fetch(&mut self, head: Head) -> Result<String> {
-        let name = get_branch_name(self.repo_path.as_str())?;
-        self.last_result = Some((head, name.clone()));
-        Ok(name)
+        let name = match get_branch_name(self.repo_path.as_str()) {
+            Ok(name) => Some(name),
+            Err(Error::NoHead) => None,
+            Err(e) => return Err(e),
+        };
+
+        let last = (head, name);
+        let result = Self::display(&last);
+        self.last_result = Some(last);
+        Ok(result)
     }
 }
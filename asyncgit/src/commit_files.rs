@@ -1,6 +1,6 @@
 use crate::{
     error::Result,
-    sync::{self, CommitId},
+    sync::{self, CommitId, CommitStats, DiffParent},
     AsyncNotification, StatusItem, CWD,
 };
 use crossbeam_channel::Sender;
@@ -9,12 +9,55 @@ use std::sync::{
     Arc, Mutex,
 };
 
-type ResultType = Vec<StatusItem>;
+type ResultType = (Vec<StatusItem>, CommitStats);
 struct Request<R, A>(R, A);
 
+/// files of a single commit (diffed against `parent`) or, with
+/// `other` set, the files that differ between `id` and `other`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CommitFilesParams {
+    ///
+    pub id: CommitId,
+    ///
+    pub other: Option<CommitId>,
+    ///
+    pub parent: DiffParent,
+}
+
+impl From<CommitId> for CommitFilesParams {
+    fn from(id: CommitId) -> Self {
+        Self {
+            id,
+            other: None,
+            parent: DiffParent::default(),
+        }
+    }
+}
+
+impl From<(CommitId, DiffParent)> for CommitFilesParams {
+    fn from((id, parent): (CommitId, DiffParent)) -> Self {
+        Self {
+            id,
+            other: None,
+            parent,
+        }
+    }
+}
+
+impl From<(CommitId, CommitId)> for CommitFilesParams {
+    fn from((id, other): (CommitId, CommitId)) -> Self {
+        Self {
+            id,
+            other: Some(other),
+            parent: DiffParent::default(),
+        }
+    }
+}
+
 ///
 pub struct AsyncCommitFiles {
-    current: Arc<Mutex<Option<Request<CommitId, ResultType>>>>,
+    current:
+        Arc<Mutex<Option<Request<CommitFilesParams, ResultType>>>>,
     sender: Sender<AsyncNotification>,
     pending: Arc<AtomicUsize>,
 }
@@ -32,7 +75,7 @@ impl AsyncCommitFiles {
     ///
     pub fn current(
         &mut self,
-    ) -> Result<Option<(CommitId, ResultType)>> {
+    ) -> Result<Option<(CommitFilesParams, ResultType)>> {
         let c = self.current.lock()?;
 
         if let Some(c) = c.as_ref() {
@@ -48,17 +91,17 @@ impl AsyncCommitFiles {
     }
 
     ///
-    pub fn fetch(&mut self, id: CommitId) -> Result<()> {
+    pub fn fetch(&mut self, params: CommitFilesParams) -> Result<()> {
         if self.is_pending() {
             return Ok(());
         }
 
-        log::trace!("request: {}", id.to_string());
+        log::trace!("request: {:?}", params);
 
         {
             let current = self.current.lock()?;
             if let Some(c) = &*current {
-                if c.0 == id {
+                if c.0 == params {
                     return Ok(());
                 }
             }
@@ -71,8 +114,7 @@ impl AsyncCommitFiles {
         self.pending.fetch_add(1, Ordering::Relaxed);
 
         rayon_core::spawn(move || {
-            Self::fetch_helper(id, arc_current)
-                .expect("failed to fetch");
+            Self::fetch_helper(params, arc_current);
 
             arc_pending.fetch_sub(1, Ordering::Relaxed);
 
@@ -85,24 +127,41 @@ impl AsyncCommitFiles {
     }
 
     fn fetch_helper(
-        id: CommitId,
+        params: CommitFilesParams,
         arc_current: Arc<
-            Mutex<Option<Request<CommitId, ResultType>>>,
+            Mutex<Option<Request<CommitFilesParams, ResultType>>>,
         >,
-    ) -> Result<()> {
-        let res = sync::get_commit_files(CWD, id)?;
+    ) {
+        // a failed request is kept as empty, otherwise it would be
+        // requested again on every notification
+        let res = Self::get_files(params).unwrap_or_else(|e| {
+            log::error!("commit files error: {}", e);
+            (Vec::new(), CommitStats::default())
+        });
 
         log::trace!(
-            "get_commit_files: {} ({})",
-            id.to_string(),
-            res.len()
+            "get_commit_files: {:?} ({})",
+            params,
+            res.0.len()
         );
 
-        {
-            let mut current = arc_current.lock()?;
-            *current = Some(Request(id, res));
+        if let Ok(mut current) = arc_current.lock() {
+            *current = Some(Request(params, res));
         }
+    }
 
-        Ok(())
+    fn get_files(params: CommitFilesParams) -> Result<ResultType> {
+        if let Some(other) = params.other {
+            sync::get_compare_commits_files_stats(
+                CWD,
+                (params.id, other),
+            )
+        } else {
+            sync::get_commit_files_stats(
+                CWD,
+                params.id,
+                params.parent,
+            )
+        }
     }
 }
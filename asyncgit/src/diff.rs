@@ -1,7 +1,7 @@
 use crate::{
     error::Result,
     hash,
-    sync::{self, CommitId},
+    sync::{self, CommitId, DiffParent},
     AsyncNotification, FileDiff, CWD,
 };
 use crossbeam_channel::Sender;
@@ -18,6 +18,10 @@ use std::{
 pub enum DiffType {
     /// diff in a given commit
     Commit(CommitId),
+    /// diff in a given commit against the chosen parent(s)
+    CommitParent(CommitId, DiffParent),
+    /// diff between the trees of two commits
+    Commits((CommitId, CommitId)),
     /// diff against staged file
     Stage,
     /// diff against file in workdir
@@ -159,6 +163,19 @@ impl AsyncDiff {
                 id,
                 params.path.clone(),
             )?,
+            DiffType::CommitParent(id, parent) => {
+                sync::diff::get_diff_commit_parent(
+                    CWD,
+                    id,
+                    parent,
+                    params.path.clone(),
+                )?
+            }
+            DiffType::Commits(ids) => sync::diff::get_diff_commits(
+                CWD,
+                ids,
+                params.path.clone(),
+            )?,
         };
 
         let mut notify = false;
@@ -0,0 +1,114 @@
+use crate::{
+    error::Result,
+    sync::{self, FileHistoryEntry},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+///
+#[derive(Hash, Clone, PartialEq, Debug)]
+pub struct FileHistoryParams {
+    /// path of the file to walk the history for
+    pub path: String,
+    /// continue with the old name when a rename is found
+    pub follow_renames: bool,
+}
+
+type ResultType = Vec<FileHistoryEntry>;
+struct Request<R, A>(R, A);
+
+///
+pub struct AsyncFileHistory {
+    current:
+        Arc<Mutex<Option<Request<FileHistoryParams, ResultType>>>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncFileHistory {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            current: Arc::new(Mutex::new(None)),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    ///
+    pub fn current(
+        &mut self,
+    ) -> Result<Option<(FileHistoryParams, ResultType)>> {
+        let c = self.current.lock()?;
+
+        if let Some(c) = c.as_ref() {
+            Ok(Some((c.0.clone(), c.1.clone())))
+        } else {
+            Ok(None)
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    /// always walks the log again since `HEAD` might have moved
+    pub fn fetch(&mut self, params: FileHistoryParams) -> Result<()> {
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        log::trace!("request: {:?}", params);
+
+        let arc_current = Arc::clone(&self.current);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            if let Err(e) = Self::fetch_helper(params, arc_current) {
+                log::error!("file history error: {}", e);
+            }
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::FileHistory)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+
+    fn fetch_helper(
+        params: FileHistoryParams,
+        arc_current: Arc<
+            Mutex<Option<Request<FileHistoryParams, ResultType>>>,
+        >,
+    ) -> Result<()> {
+        let res = sync::get_file_history(
+            CWD,
+            &params.path,
+            params.follow_renames,
+        )?;
+
+        log::trace!(
+            "get_file_history: {} ({})",
+            params.path,
+            res.len()
+        );
+
+        {
+            let mut current = arc_current.lock()?;
+            *current = Some(Request(params, res));
+        }
+
+        Ok(())
+    }
+}
@@ -11,14 +11,20 @@
 //TODO: get this in someday since expect still leads us to crashes sometimes
 // #![deny(clippy::expect_used)]
 
+mod author_stats;
+mod blame;
 pub mod cached;
 mod commit_files;
 mod diff;
 mod error;
 mod fetch;
+mod file_history;
+mod log_search;
+mod notes;
 mod progress;
 mod push;
 mod push_tags;
+mod refs;
 pub mod remote_progress;
 mod revlog;
 mod status;
@@ -26,11 +32,17 @@ pub mod sync;
 mod tags;
 
 pub use crate::{
-    commit_files::AsyncCommitFiles,
+    author_stats::{AsyncAuthorStats, AuthorStatsParams},
+    blame::{AsyncBlame, BlameParams},
+    commit_files::{AsyncCommitFiles, CommitFilesParams},
     diff::{AsyncDiff, DiffParams, DiffType},
     fetch::{AsyncFetch, FetchRequest},
+    file_history::{AsyncFileHistory, FileHistoryParams},
+    log_search::{AsyncLogSearch, LogSearchParams},
+    notes::AsyncNotes,
     push::{AsyncPush, PushRequest},
     push_tags::{AsyncPushTags, PushTagsRequest},
+    refs::AsyncRefs,
     remote_progress::{RemoteProgress, RemoteProgressState},
     revlog::{AsyncLog, FetchStatus},
     status::{AsyncStatus, StatusParams},
@@ -66,6 +78,18 @@ pub enum AsyncNotification {
     PushTags,
     ///
     Fetch,
+    ///
+    FileHistory,
+    ///
+    Blame,
+    ///
+    LogSearch,
+    ///
+    AuthorStats,
+    ///
+    Refs,
+    ///
+    Notes,
 }
 
 /// current working director `./`
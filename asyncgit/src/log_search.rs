@@ -0,0 +1,139 @@
+use crate::{
+    error::Result,
+    sync::{self, CommitId},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+///
+#[derive(Hash, Clone, PartialEq, Debug)]
+pub struct LogSearchParams {
+    /// text to look for
+    pub query: String,
+    /// number of log entries that were searched
+    pub searched: usize,
+}
+
+/// indices of matching entries in the searched log
+type ResultType = Vec<usize>;
+struct Request<R, A>(R, A);
+
+/// searches a list of commits (usually the one fetched by `AsyncLog`)
+/// in the background
+pub struct AsyncLogSearch {
+    current: Arc<Mutex<Option<Request<LogSearchParams, ResultType>>>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncLogSearch {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            current: Arc::new(Mutex::new(None)),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    ///
+    pub fn last(
+        &self,
+    ) -> Result<Option<(LogSearchParams, ResultType)>> {
+        let c = self.current.lock()?;
+
+        if let Some(c) = c.as_ref() {
+            Ok(Some((c.0.clone(), c.1.clone())))
+        } else {
+            Ok(None)
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    /// forget the last result
+    pub fn clear(&mut self) -> Result<()> {
+        self.current.lock()?.take();
+        Ok(())
+    }
+
+    /// searches `ids` which start at index `offset` of the log, the
+    /// matches are appended to the last result if that covered the
+    /// log up to `offset`
+    pub fn request(
+        &mut self,
+        query: String,
+        offset: usize,
+        ids: Vec<CommitId>,
+    ) -> Result<()> {
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        let params = LogSearchParams {
+            query,
+            searched: offset + ids.len(),
+        };
+
+        log::trace!("request: {:?}", params);
+
+        let arc_current = Arc::clone(&self.current);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            if let Err(e) = Self::search_helper(
+                params,
+                offset,
+                &ids,
+                &arc_current,
+            ) {
+                log::error!("log search error: {}", e);
+            }
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::LogSearch)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+
+    fn search_helper(
+        params: LogSearchParams,
+        offset: usize,
+        ids: &[CommitId],
+        arc_current: &Arc<
+            Mutex<Option<Request<LogSearchParams, ResultType>>>,
+        >,
+    ) -> Result<()> {
+        let res = sync::search_commits(CWD, ids, &params.query)?;
+        let res = res.into_iter().map(|idx| idx + offset);
+
+        let mut current = arc_current.lock()?;
+
+        if offset == 0 {
+            *current = Some(Request(params, res.collect()));
+        } else if let Some(Request(last, matches)) = current.as_mut()
+        {
+            // the search may have been cleared or changed meanwhile
+            if last.query == params.query && last.searched == offset {
+                matches.extend(res);
+                *last = params;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    error::Result,
+    hash,
+    sync::{self},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use sync::Notes;
+
+///
+#[derive(Default, Clone)]
+struct NotesResult {
+    hash: u64,
+    notes: Notes,
+}
+
+///
+pub struct AsyncNotes {
+    last: Arc<Mutex<Option<(Instant, NotesResult)>>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncNotes {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            last: Arc::new(Mutex::new(None)),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// last fetched result
+    pub fn last(&mut self) -> Result<Option<Notes>> {
+        let last = self.last.lock()?;
+
+        Ok(last.clone().map(|last| last.1.notes))
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    fn is_outdated(&self, dur: Duration) -> Result<bool> {
+        let last = self.last.lock()?;
+
+        Ok(last
+            .as_ref()
+            .map(|(last_time, _)| last_time.elapsed() > dur)
+            .unwrap_or(true))
+    }
+
+    ///
+    pub fn request(
+        &mut self,
+        dur: Duration,
+        force: bool,
+    ) -> Result<()> {
+        log::trace!("request");
+
+        if !force && (self.is_pending() || !self.is_outdated(dur)?) {
+            return Ok(());
+        }
+
+        let arc_last = Arc::clone(&self.last);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            let notify = AsyncNotes::getter(arc_last)
+                .expect("error getting notes");
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(if notify {
+                    AsyncNotification::Notes
+                } else {
+                    AsyncNotification::FinishUnchanged
+                })
+                .expect("error sending notify");
+        });
+
+        Ok(())
+    }
+
+    fn getter(
+        arc_last: Arc<Mutex<Option<(Instant, NotesResult)>>>,
+    ) -> Result<bool> {
+        let notes = sync::get_notes(CWD)?;
+
+        let hash = hash(&notes);
+
+        if Self::last_hash(arc_last.clone())
+            .map(|last| last == hash)
+            .unwrap_or_default()
+        {
+            return Ok(false);
+        }
+
+        {
+            let mut last = arc_last.lock()?;
+            let now = Instant::now();
+            *last = Some((now, NotesResult { hash, notes }));
+        }
+
+        Ok(true)
+    }
+
+    fn last_hash(
+        last: Arc<Mutex<Option<(Instant, NotesResult)>>>,
+    ) -> Option<u64> {
+        last.lock()
+            .ok()
+            .and_then(|last| last.as_ref().map(|(_, last)| last.hash))
+    }
+}
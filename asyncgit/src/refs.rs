@@ -0,0 +1,128 @@
+use crate::{
+    error::Result,
+    hash,
+    sync::{self},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use sync::Refs;
+
+///
+#[derive(Default, Clone)]
+struct RefsResult {
+    hash: u64,
+    refs: Refs,
+}
+
+///
+pub struct AsyncRefs {
+    last: Arc<Mutex<Option<(Instant, RefsResult)>>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncRefs {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            last: Arc::new(Mutex::new(None)),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// last fetched result
+    pub fn last(&mut self) -> Result<Option<Refs>> {
+        let last = self.last.lock()?;
+
+        Ok(last.clone().map(|last| last.1.refs))
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    fn is_outdated(&self, dur: Duration) -> Result<bool> {
+        let last = self.last.lock()?;
+
+        Ok(last
+            .as_ref()
+            .map(|(last_time, _)| last_time.elapsed() > dur)
+            .unwrap_or(true))
+    }
+
+    ///
+    pub fn request(
+        &mut self,
+        dur: Duration,
+        force: bool,
+    ) -> Result<()> {
+        log::trace!("request");
+
+        if !force && (self.is_pending() || !self.is_outdated(dur)?) {
+            return Ok(());
+        }
+
+        let arc_last = Arc::clone(&self.last);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            let notify = AsyncRefs::getter(arc_last)
+                .expect("error getting refs");
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(if notify {
+                    AsyncNotification::Refs
+                } else {
+                    AsyncNotification::FinishUnchanged
+                })
+                .expect("error sending notify");
+        });
+
+        Ok(())
+    }
+
+    fn getter(
+        arc_last: Arc<Mutex<Option<(Instant, RefsResult)>>>,
+    ) -> Result<bool> {
+        let refs = sync::get_commit_refs(CWD)?;
+
+        let hash = hash(&refs);
+
+        if Self::last_hash(arc_last.clone())
+            .map(|last| last == hash)
+            .unwrap_or_default()
+        {
+            return Ok(false);
+        }
+
+        {
+            let mut last = arc_last.lock()?;
+            let now = Instant::now();
+            *last = Some((now, RefsResult { hash, refs }));
+        }
+
+        Ok(true)
+    }
+
+    fn last_hash(
+        last: Arc<Mutex<Option<(Instant, RefsResult)>>>,
+    ) -> Option<u64> {
+        last.lock()
+            .ok()
+            .and_then(|last| last.as_ref().map(|(_, last)| last.hash))
+    }
+}
@@ -1,10 +1,13 @@
 use crate::{
     error::Result,
-    sync::{utils::repo, CommitId, LogWalker},
+    sync::{
+        get_log_start_tips, utils::repo, CommitGraph, CommitId,
+        GraphRow, LogStart, LogWalker,
+    },
     AsyncNotification, CWD,
 };
 use crossbeam_channel::Sender;
-use git2::Oid;
+use git2::Repository;
 use scopetime::scope_time;
 use std::{
     sync::{
@@ -29,9 +32,13 @@ pub enum FetchStatus {
 ///
 pub struct AsyncLog {
     current: Arc<Mutex<Vec<CommitId>>>,
+    graph: Arc<Mutex<CommitGraph>>,
     sender: Sender<AsyncNotification>,
     pending: Arc<AtomicBool>,
     background: Arc<AtomicBool>,
+    start: LogStart,
+    /// start and its resolved tips of the last walk
+    last_start: Option<(LogStart, Vec<CommitId>)>,
 }
 
 static LIMIT_COUNT: usize = 3000;
@@ -43,9 +50,12 @@ impl AsyncLog {
     pub fn new(sender: &Sender<AsyncNotification>) -> Self {
         Self {
             current: Arc::new(Mutex::new(Vec::new())),
+            graph: Arc::new(Mutex::new(CommitGraph::new())),
             sender: sender.clone(),
             pending: Arc::new(AtomicBool::new(false)),
             background: Arc::new(AtomicBool::new(false)),
+            start: LogStart::Head,
+            last_start: None,
         }
     }
 
@@ -68,6 +78,20 @@ impl AsyncLog {
         Ok(list[min..max].to_vec())
     }
 
+    /// index of `id` in the commits fetched so far
+    pub fn position(&self, id: CommitId) -> Result<Option<usize>> {
+        Ok(self.current.lock()?.iter().position(|c| *c == id))
+    }
+
+    /// graph rows matching the commits of `get_slice`
+    pub fn get_graph_slice(
+        &self,
+        start_index: usize,
+        amount: usize,
+    ) -> Result<Vec<GraphRow>> {
+        Ok(self.graph.lock()?.get_slice(start_index, amount))
+    }
+
     ///
     pub fn is_pending(&self) -> bool {
         self.pending.load(Ordering::Relaxed)
@@ -79,25 +103,16 @@ impl AsyncLog {
     }
 
     ///
-    fn current_head(&self) -> Result<CommitId> {
-        Ok(self
-            .current
-            .lock()?
-            .first()
-            .map_or(Oid::zero().into(), |f| *f))
+    pub const fn start(&self) -> &LogStart {
+        &self.start
     }
 
-    ///
-    fn head_changed(&self) -> Result<bool> {
-        if let Ok(head) = repo(CWD)?.head() {
-            if let Some(head) = head.target() {
-                return Ok(head != self.current_head()?.into());
-            }
-        }
-        Ok(false)
+    /// walk from `start` with the next `fetch`
+    pub fn set_start(&mut self, start: LogStart) {
+        self.start = start;
     }
 
-    ///
+    /// fails if the start does not resolve to a commit (anymore)
     pub fn fetch(&mut self) -> Result<FetchStatus> {
         self.background.store(false, Ordering::Relaxed);
 
@@ -105,16 +120,22 @@ impl AsyncLog {
             return Ok(FetchStatus::Pending);
         }
 
-        if !self.head_changed()? {
+        let tips = get_log_start_tips(CWD, &self.start)?;
+        let start = (self.start.clone(), tips);
+
+        if self.last_start.as_ref() == Some(&start) {
             return Ok(FetchStatus::NoChange);
         }
 
         self.clear()?;
+        self.last_start = Some(start);
 
         let arc_current = Arc::clone(&self.current);
+        let arc_graph = Arc::clone(&self.graph);
         let sender = self.sender.clone();
         let arc_pending = Arc::clone(&self.pending);
         let arc_background = Arc::clone(&self.background);
+        let start = self.start.clone();
 
         self.pending.store(true, Ordering::Relaxed);
 
@@ -122,7 +143,9 @@ impl AsyncLog {
             scope_time!("async::revlog");
 
             AsyncLog::fetch_helper(
+                start,
                 arc_current,
+                arc_graph,
                 arc_background,
                 &sender,
             )
@@ -137,19 +160,25 @@ impl AsyncLog {
     }
 
     fn fetch_helper(
+        start: LogStart,
         arc_current: Arc<Mutex<Vec<CommitId>>>,
+        arc_graph: Arc<Mutex<CommitGraph>>,
         arc_background: Arc<AtomicBool>,
         sender: &Sender<AsyncNotification>,
     ) -> Result<()> {
         let mut entries = Vec::with_capacity(LIMIT_COUNT);
         let r = repo(CWD)?;
-        let mut walker = LogWalker::new(&r);
+        // the log is always drawn with its commit graph
+        let mut walker =
+            LogWalker::new(&r).with_start(start).with_topo_order();
         loop {
             entries.clear();
             let res_is_err =
                 walker.read(&mut entries, LIMIT_COUNT).is_err();
 
             if !res_is_err {
+                Self::extend_graph(&r, &arc_graph, &entries)?;
+
                 let mut current = arc_current.lock()?;
                 current.extend(entries.iter());
             }
@@ -172,8 +201,32 @@ impl AsyncLog {
         Ok(())
     }
 
+    fn extend_graph(
+        repo: &Repository,
+        arc_graph: &Arc<Mutex<CommitGraph>>,
+        entries: &[CommitId],
+    ) -> Result<()> {
+        let parents = entries
+            .iter()
+            .map(|id| {
+                repo.find_commit((*id).into()).map(|c| {
+                    c.parent_ids().map(CommitId::from).collect()
+                })
+            })
+            .collect::<std::result::Result<Vec<Vec<CommitId>>, _>>(
+            )?;
+
+        let mut graph = arc_graph.lock()?;
+        for (id, parents) in entries.iter().zip(parents.iter()) {
+            graph.add(*id, parents);
+        }
+
+        Ok(())
+    }
+
     fn clear(&mut self) -> Result<()> {
         self.current.lock()?.clear();
+        self.graph.lock()?.clear();
         Ok(())
     }
 
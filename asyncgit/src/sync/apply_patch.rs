@@ -0,0 +1,1004 @@
+//! sync git api for applying patch and mbox files
+//! (like `git apply` and `git am`)
+
+use super::{
+    commit_details::CommitSignature,
+    status::{get_status, StatusType},
+    utils::repo,
+    CommitId,
+};
+use crate::{
+    error::{Error, Result},
+    StatusItem, StatusItemType,
+};
+use chrono::{DateTime, FixedOffset, Local};
+use git2::{
+    ApplyLocation, ApplyOptions, Delta, Diff, DiffDelta, DiffHunk,
+    Patch, Repository, Signature, Time,
+};
+use scopetime::scope_time;
+use std::{borrow::Cow, cell::Cell, fs, path::Path};
+
+/// where to apply patches to
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ApplyTarget {
+    /// `git apply`
+    WorkDir,
+    /// `git apply --cached`
+    Index,
+    /// one commit per patch on top of `HEAD` (`git am`)
+    Commits,
+}
+
+/// single patch of a patch file, mbox files can contain several
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchEntry {
+    /// from the mail header, `None` for plain diffs
+    pub author: Option<CommitSignature>,
+    /// subject (without `[PATCH ..]`) and body of the mail
+    pub message: Option<String>,
+    /// as found in the file, line endings and encoding untouched
+    pub diff: Vec<u8>,
+}
+
+impl PatchEntry {
+    /// first line of the message
+    pub fn subject(&self) -> Option<&str> {
+        self.message.as_ref().and_then(|msg| msg.lines().next())
+    }
+}
+
+/// a file or one of its hunks that does not apply
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApplyFailure {
+    ///
+    pub path: String,
+    /// hunk header (`@@ -1,2 +1,2 @@`), `None` if the file as a
+    /// whole fails (e.g. it does not exist)
+    pub hunk: Option<String>,
+    /// reason given by git
+    pub error: String,
+}
+
+/// outcome of `apply_patches`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ApplyResult {
+    /// number of patches applied before stopping
+    pub applied: usize,
+    /// why the next patch did not apply
+    pub failures: Vec<ApplyFailure>,
+}
+
+/// reads a plain patch or an mbox with one or more patches
+/// (cover letters and other mails without a diff are skipped)
+pub fn read_patch_file(path: &Path) -> Result<Vec<PatchEntry>> {
+    scope_time!("read_patch_file");
+
+    let content = fs::read(path)?;
+
+    let res: Vec<PatchEntry> = split_mbox(&content)
+        .into_iter()
+        .filter_map(parse_mail)
+        .collect();
+
+    if res.is_empty() {
+        return Err(Error::Generic(format!(
+            "no patch found in '{}'",
+            path.display()
+        )));
+    }
+
+    Ok(res)
+}
+
+/// files a patch touches
+pub fn get_patch_files(
+    patch: &PatchEntry,
+) -> Result<Vec<StatusItem>> {
+    let diff = Diff::from_buffer(&patch.diff)?;
+
+    Ok(diff
+        .deltas()
+        .map(|delta| StatusItem {
+            path: delta_path(&delta),
+            status: StatusItemType::from(delta.status()),
+        })
+        .collect())
+}
+
+/// checks if `patch` applies to the current state of `target`
+/// without changing anything, empty if it does
+pub fn check_patch(
+    repo_path: &str,
+    patch: &PatchEntry,
+    target: ApplyTarget,
+) -> Result<Vec<ApplyFailure>> {
+    scope_time!("check_patch");
+
+    let repo = repo(repo_path)?;
+    let diff = Diff::from_buffer(&patch.diff)?;
+
+    Ok(find_failures(&repo, &diff, location(target)))
+}
+
+/// applies `patches` in order, stops at the first one that does
+/// not apply and reports its failing hunks.
+/// `ApplyTarget::Commits` needs an empty index.
+pub fn apply_patches(
+    repo_path: &str,
+    patches: &[PatchEntry],
+    target: ApplyTarget,
+) -> Result<ApplyResult> {
+    scope_time!("apply_patches");
+
+    let repo = repo(repo_path)?;
+
+    if target == ApplyTarget::Commits
+        && !get_status(repo_path, StatusType::Stage, false)?
+            .is_empty()
+    {
+        return Err(Error::Generic(String::from(
+            "index contains staged changes",
+        )));
+    }
+
+    let mut res = ApplyResult::default();
+
+    for patch in patches {
+        let diff = Diff::from_buffer(&patch.diff)?;
+
+        let failures = find_failures(&repo, &diff, location(target));
+        if !failures.is_empty() {
+            res.failures = failures;
+            break;
+        }
+
+        repo.apply(&diff, location(target), None)?;
+
+        if target == ApplyTarget::Commits {
+            commit_patch(&repo, patch)?;
+        }
+
+        res.applied += 1;
+    }
+
+    Ok(res)
+}
+
+const fn location(target: ApplyTarget) -> ApplyLocation {
+    match target {
+        ApplyTarget::WorkDir => ApplyLocation::WorkDir,
+        ApplyTarget::Index => ApplyLocation::Index,
+        ApplyTarget::Commits => ApplyLocation::Both,
+    }
+}
+
+fn delta_path(delta: &DiffDelta) -> String {
+    delta
+        .new_file()
+        .path()
+        .or_else(|| delta.old_file().path())
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn hunk_header(hunk: &DiffHunk) -> String {
+    String::from_utf8_lossy(hunk.header())
+        .trim_end()
+        .to_string()
+}
+
+/// hunks to include in a dry run of a single delta
+#[derive(Copy, Clone)]
+enum HunkFilter {
+    All,
+    None,
+    Single(usize),
+}
+
+/// dry runs of `diff` restricted to a single delta and then to its
+/// single hunks to tell which parts fail
+fn find_failures(
+    repo: &Repository,
+    diff: &Diff,
+    location: ApplyLocation,
+) -> Vec<ApplyFailure> {
+    let check = |delta_idx: usize, hunks: HunkFilter| {
+        let delta_count = Cell::new(0_usize);
+        let hunk_count = Cell::new(0_usize);
+
+        let mut opts = ApplyOptions::new();
+        opts.check(true);
+        opts.delta_callback(|_| {
+            let idx = delta_count.get();
+            delta_count.set(idx + 1);
+            idx == delta_idx
+        });
+        opts.hunk_callback(|_| {
+            let idx = hunk_count.get();
+            hunk_count.set(idx + 1);
+            match hunks {
+                HunkFilter::All => true,
+                HunkFilter::None => false,
+                HunkFilter::Single(hunk_idx) => idx == hunk_idx,
+            }
+        });
+
+        repo.apply(diff, location, Some(&mut opts))
+    };
+
+    let mut res = Vec::new();
+
+    for (delta_idx, delta) in diff.deltas().enumerate() {
+        if delta.status() == Delta::Added {
+            if let Some(error) =
+                already_exists(repo, location, &delta_path(&delta))
+            {
+                res.push(ApplyFailure {
+                    path: delta_path(&delta),
+                    hunk: None,
+                    error,
+                });
+                continue;
+            }
+        }
+
+        let error = match check(delta_idx, HunkFilter::All) {
+            Ok(_) => continue,
+            Err(e) => e,
+        };
+
+        let path = delta_path(&delta);
+
+        // e.g. missing file
+        if let Err(e) = check(delta_idx, HunkFilter::None) {
+            res.push(ApplyFailure {
+                path,
+                hunk: None,
+                error: e.message().to_string(),
+            });
+            continue;
+        }
+
+        let failures_before = res.len();
+
+        if let Ok(Some(patch)) = Patch::from_diff(diff, delta_idx) {
+            for hunk_idx in 0..patch.num_hunks() {
+                if let Err(e) =
+                    check(delta_idx, HunkFilter::Single(hunk_idx))
+                {
+                    res.push(ApplyFailure {
+                        path: path.clone(),
+                        hunk: patch
+                            .hunk(hunk_idx)
+                            .ok()
+                            .map(|(hunk, _)| hunk_header(&hunk)),
+                        error: e.message().to_string(),
+                    });
+                }
+            }
+        }
+
+        // hunks only conflict with each other
+        if res.len() == failures_before {
+            res.push(ApplyFailure {
+                path,
+                hunk: None,
+                error: error.message().to_string(),
+            });
+        }
+    }
+
+    res
+}
+
+/// libgit2 does not check this itself and would fail on checkout
+/// or overwrite the index entry
+fn already_exists(
+    repo: &Repository,
+    location: ApplyLocation,
+    path: &str,
+) -> Option<String> {
+    let in_workdir = || {
+        repo.workdir().map_or(false, |dir| dir.join(path).exists())
+    };
+    let in_index = || {
+        repo.index().map_or(false, |index| {
+            index.get_path(Path::new(path), 0).is_some()
+        })
+    };
+
+    match location {
+        ApplyLocation::WorkDir | ApplyLocation::Both
+            if in_workdir() =>
+        {
+            Some(String::from("already exists in working directory"))
+        }
+        ApplyLocation::Index | ApplyLocation::Both if in_index() => {
+            Some(String::from("already exists in index"))
+        }
+        _ => None,
+    }
+}
+
+fn commit_patch(repo: &Repository, patch: &PatchEntry) -> Result<()> {
+    let committer = repo.signature()?;
+    let author = match &patch.author {
+        Some(author) => Signature::new(
+            &author.name,
+            &author.email,
+            &Time::new(author.time, author.time_offset),
+        )?,
+        None => committer.clone(),
+    };
+
+    let mut index = repo.index()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let parent = match repo.head() {
+        Ok(head) => Some(head.peel_to_commit()?),
+        Err(_) => None,
+    };
+    let parents = parent.iter().collect::<Vec<_>>();
+
+    let id: CommitId = repo
+        .commit(
+            Some("HEAD"),
+            &author,
+            &committer,
+            patch.message.as_deref().unwrap_or("apply patch"),
+            &tree,
+            parents.as_slice(),
+        )?
+        .into();
+
+    log::trace!("applied patch as {}", id.to_string());
+
+    Ok(())
+}
+
+/// `From <sha> Mon Sep 17 00:00:00 2001` starts a new mail
+fn is_mbox_from_line(line: &str) -> bool {
+    line.strip_prefix("From ")
+        .and_then(|rest| rest.split(' ').next())
+        .map_or(false, |id| {
+            id.len() == 40
+                && id.chars().all(|c| c.is_ascii_hexdigit())
+        })
+}
+
+/// `line` without its line ending, to look at headers and markers
+fn line_text(line: &[u8]) -> Cow<'_, str> {
+    let mut end = line.len();
+    if line[..end].ends_with(b"\n") {
+        end -= 1;
+    }
+    if line[..end].ends_with(b"\r") {
+        end -= 1;
+    }
+    String::from_utf8_lossy(&line[..end])
+}
+
+/// lines keep their line endings
+fn split_mbox(content: &[u8]) -> Vec<Vec<&[u8]>> {
+    let mut res: Vec<Vec<&[u8]>> = Vec::new();
+
+    let mut start = 0;
+    while start < content.len() {
+        let end = content[start..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(content.len(), |idx| start + idx + 1);
+        let line = &content[start..end];
+        start = end;
+
+        if res.is_empty() || is_mbox_from_line(&line_text(line)) {
+            res.push(Vec::new());
+        }
+
+        if let Some(mail) = res.last_mut() {
+            mail.push(line);
+        }
+    }
+
+    res
+}
+
+/// a diff starts at `diff --git` or, without one, at the
+/// `--- `/`+++ ` file header pair of a plain unified diff
+fn find_diff_start(lines: &[&[u8]]) -> Option<usize> {
+    (0..lines.len()).find(|idx| {
+        lines[*idx].starts_with(b"diff --git ")
+            || is_file_header(lines, *idx)
+    })
+}
+
+/// `--- ` line followed by a `+++ ` one
+fn is_file_header(lines: &[&[u8]], idx: usize) -> bool {
+    lines[idx].starts_with(b"--- ")
+        && lines
+            .get(idx + 1)
+            .map_or(false, |next| next.starts_with(b"+++ "))
+}
+
+/// git style header for the `--- ` and `+++ ` lines of a plain diff,
+/// their paths lose the first component like with `git apply -p1`
+fn git_header(old: &[u8], new: &[u8]) -> Vec<u8> {
+    let path = |line: &[u8]| {
+        let text = line_text(&line[4..]);
+        // `diff -u` appends a timestamp after a tab
+        let path = text.split('\t').next().unwrap_or_default().trim();
+        if path == "/dev/null" {
+            None
+        } else {
+            Some(
+                path.find('/')
+                    .map_or(path, |idx| &path[idx + 1..])
+                    .to_string(),
+            )
+        }
+    };
+
+    let (old, new) = (path(old), path(new));
+    let name = |path: &Option<String>, other: &Option<String>| {
+        path.as_ref()
+            .or(other.as_ref())
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let mut res = format!(
+        "diff --git a/{} b/{}\n",
+        name(&old, &new),
+        name(&new, &old)
+    );
+    match (&old, &new) {
+        (None, _) => res.push_str("new file mode 100644\n"),
+        (_, None) => res.push_str("deleted file mode 100644\n"),
+        _ => (),
+    }
+    res.push_str(&format!(
+        "--- {}\n+++ {}\n",
+        old.map_or_else(
+            || "/dev/null".into(),
+            |p| format!("a/{}", p)
+        ),
+        new.map_or_else(
+            || "/dev/null".into(),
+            |p| format!("b/{}", p)
+        )
+    ));
+
+    res.into_bytes()
+}
+
+fn parse_mail(lines: Vec<&[u8]>) -> Option<PatchEntry> {
+    let diff_start = find_diff_start(&lines)?;
+
+    let is_mail = lines
+        .first()
+        .map_or(false, |l| is_mbox_from_line(&line_text(l)));
+    if !is_mail {
+        return Some(PatchEntry {
+            author: None,
+            message: None,
+            diff: join_lines(&lines),
+        });
+    }
+
+    // headers end at the first empty line, long ones are folded
+    let mut headers: Vec<String> = Vec::new();
+    let mut body_start = diff_start;
+    for (idx, line) in lines.iter().enumerate().skip(1) {
+        let line = line_text(line);
+        if line.is_empty() {
+            body_start = idx + 1;
+            break;
+        } else if line.starts_with(char::is_whitespace) {
+            if let Some(last) = headers.last_mut() {
+                last.push_str(&line);
+            }
+        } else {
+            headers.push(line.to_string());
+        }
+    }
+
+    let header = |name: &str| {
+        headers.iter().find_map(|h| {
+            h.strip_prefix(name).map(|value| value.trim().to_string())
+        })
+    };
+
+    let subject = header("Subject:")
+        .map(|s| strip_patch_prefix(&decode_header(&s)));
+
+    // the body ends with `---` followed by the diff stats
+    let body_end = lines[body_start.min(diff_start)..diff_start]
+        .iter()
+        .position(|l| line_text(l) == "---")
+        .map_or(diff_start, |idx| idx + body_start.min(diff_start));
+    let body = lines[body_start.min(body_end)..body_end]
+        .iter()
+        .map(|l| line_text(l))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let message = subject.map(|subject| {
+        if body.trim().is_empty() {
+            subject
+        } else {
+            format!("{}\n\n{}", subject, body.trim())
+        }
+    });
+
+    let author = header("From:").map(|from| {
+        let from = decode_header(&from);
+        let (name, email) = match (from.find('<'), from.rfind('>')) {
+            (Some(start), Some(end)) if start < end => (
+                from[..start].trim().trim_matches('"').to_string(),
+                from[start + 1..end].to_string(),
+            ),
+            _ => (String::new(), from.clone()),
+        };
+
+        // the committer's current time if the date is missing
+        let date: DateTime<FixedOffset> = header("Date:")
+            .and_then(|date| DateTime::parse_from_rfc2822(&date).ok())
+            .unwrap_or_else(|| Local::now().into());
+
+        CommitSignature {
+            name,
+            email,
+            time: date.timestamp(),
+            time_offset: date.offset().local_minus_utc() / 60,
+        }
+    });
+
+    Some(PatchEntry {
+        author,
+        message,
+        diff: join_lines(strip_signature(&lines[diff_start..])),
+    })
+}
+
+/// removes `[PATCH]`, `[PATCH 1/2]`, `[RFC PATCH v2]` ..
+fn strip_patch_prefix(subject: &str) -> String {
+    let mut subject = subject.trim();
+    while subject.starts_with('[') {
+        match subject.find(']') {
+            Some(end) => subject = subject[end + 1..].trim_start(),
+            None => break,
+        }
+    }
+
+    subject.to_string()
+}
+
+/// decodes the RFC 2047 encoded words (`=?UTF-8?q?J=C3=B6rg?=`)
+/// mail programs use for non-ascii names and subjects
+fn decode_header(value: &str) -> String {
+    let mut res = String::with_capacity(value.len());
+    let mut rest = value;
+    let mut after_word = false;
+
+    while let Some(start) = rest.find("=?") {
+        if let Some((decoded, len)) = decode_word(&rest[start..]) {
+            // whitespace between encoded words is not part of the text
+            let between = &rest[..start];
+            if !after_word || !between.trim().is_empty() {
+                res.push_str(between);
+            }
+            res.push_str(&decoded);
+            rest = &rest[start + len..];
+            after_word = true;
+        } else {
+            res.push_str(&rest[..start + 2]);
+            rest = &rest[start + 2..];
+            after_word = false;
+        }
+    }
+
+    res.push_str(rest);
+    res
+}
+
+/// decodes the encoded word at the start of `word`, returns the text
+/// and the length of the encoded word
+fn decode_word(word: &str) -> Option<(String, usize)> {
+    let mut parts = word.strip_prefix("=?")?.splitn(3, '?');
+    let charset = parts.next()?;
+    let encoding = parts.next()?;
+    let rest = parts.next()?;
+    let text = &rest[..rest.find("?=")?];
+
+    if text.contains(char::is_whitespace) {
+        return None;
+    }
+
+    // `=?charset?encoding?text?=`
+    let len = charset.len() + encoding.len() + text.len() + 6;
+
+    let bytes = match encoding {
+        "B" | "b" => base64::decode(text).ok()?,
+        "Q" | "q" => decode_q(text)?,
+        _ => return None,
+    };
+
+    // `charset*language` (RFC 2231)
+    let charset = charset.split('*').next().unwrap_or_default();
+    let decoded = if charset.eq_ignore_ascii_case("iso-8859-1")
+        || charset.eq_ignore_ascii_case("latin1")
+    {
+        bytes.iter().map(|b| char::from(*b)).collect()
+    } else {
+        String::from_utf8_lossy(&bytes).to_string()
+    };
+
+    Some((decoded, len))
+}
+
+/// `_` is a space and `=XX` a hex encoded byte
+fn decode_q(text: &str) -> Option<Vec<u8>> {
+    let mut res = Vec::with_capacity(text.len());
+    let mut bytes = text.bytes();
+
+    while let Some(b) = bytes.next() {
+        match b {
+            b'_' => res.push(b' '),
+            b'=' => {
+                let hex = [bytes.next()?, bytes.next()?];
+                let hex = std::str::from_utf8(&hex).ok()?;
+                res.push(u8::from_str_radix(hex, 16).ok()?);
+            }
+            _ => res.push(b),
+        }
+    }
+
+    Some(res)
+}
+
+/// format-patch ends mails with `-- ` and a version line
+fn strip_signature<'a>(lines: &'a [&'a [u8]]) -> &'a [&'a [u8]] {
+    lines.iter().rposition(|l| line_text(l) == "-- ").map_or(
+        lines,
+        |idx| {
+            let trailing_diff = lines[idx + 1..].iter().any(|l| {
+                l.starts_with(b"+")
+                    || l.starts_with(b"-")
+                    || l.starts_with(b" ")
+                    || l.starts_with(b"@")
+                    || l.starts_with(b"diff ")
+            });
+
+            if trailing_diff {
+                lines
+            } else {
+                &lines[..idx]
+            }
+        },
+    )
+}
+
+/// plain diffs get the git style header libgit2 needs for each file
+fn join_lines(lines: &[&[u8]]) -> Vec<u8> {
+    let mut res = Vec::new();
+    let mut has_git_header = false;
+
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx];
+        if line.starts_with(b"diff --git ") {
+            has_git_header = true;
+        } else if is_file_header(lines, idx) {
+            if !has_git_header {
+                res.extend(git_header(line, lines[idx + 1]));
+                idx += 2;
+                continue;
+            }
+            has_git_header = false;
+        }
+
+        res.extend_from_slice(line);
+        idx += 1;
+    }
+
+    if !res.ends_with(b"\n") {
+        res.push(b'\n');
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        format_patches, get_commits_info, reset_repo,
+        tests::{repo_init_empty, write_commit_file},
+        utils::repo_write_file,
+        FormatPatchOptions, ResetMode,
+    };
+
+    #[test]
+    fn test_subject_prefix() {
+        assert_eq!(strip_patch_prefix("[PATCH] foo"), "foo");
+        assert_eq!(strip_patch_prefix("[PATCH 1/2] foo"), "foo");
+        assert_eq!(
+            strip_patch_prefix("[RFC] [PATCH v2 3/4] foo [bar]"),
+            "foo [bar]"
+        );
+    }
+
+    #[test]
+    fn test_decode_header() {
+        assert_eq!(decode_header("plain =? text"), "plain =? text");
+        assert_eq!(
+            decode_header("=?UTF-8?q?J=C3=B6rg_B?= <j@b>"),
+            "Jörg B <j@b>"
+        );
+        assert_eq!(
+            decode_header(
+                "[PATCH] =?utf-8?B?w6Q=?= =?iso-8859-1?Q?=E4?="
+            ),
+            "[PATCH] ää"
+        );
+        assert_eq!(
+            decode_header("=?utf-8?x?abc?="),
+            "=?utf-8?x?abc?="
+        );
+    }
+
+    #[test]
+    fn test_mail_bytes() {
+        let (td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a\n", "c1");
+
+        // no date, encoded headers, CRLF and latin1 content
+        let mail = [
+            &b"From 0123456789012345678901234567890123456789 Mon Sep 17 00:00:00 2001\n"[..],
+            b"From: =?UTF-8?q?J=C3=B6rg?= <j@b>\n",
+            b"Subject: [PATCH] =?utf-8?B?w6Q=?=\n",
+            b"\n",
+            b"---\n",
+            b"diff --git a/b b/b\n",
+            b"new file mode 100644\n",
+            b"--- /dev/null\n",
+            b"+++ b/b\n",
+            b"@@ -0,0 +1,2 @@\n",
+            b"+\xe4\r\n",
+            b"+x\r\n",
+            b"-- \n",
+            b"2.30.0\n",
+        ]
+        .concat();
+        let path = td.path().join("mail.patch");
+        fs::write(&path, mail).unwrap();
+
+        let patches = read_patch_file(&path).unwrap();
+        let author = patches[0].author.clone().unwrap();
+        assert_eq!(author.name, "Jörg");
+        assert_eq!(patches[0].subject(), Some("ä"));
+        assert!((Local::now().timestamp() - author.time).abs() < 60);
+
+        let res =
+            apply_patches(repo_path, &patches, ApplyTarget::Commits)
+                .unwrap();
+        assert_eq!(res.applied, 1);
+        assert_eq!(
+            fs::read(root.join("b")).unwrap(),
+            b"\xe4\r\nx\r\n"
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_as_commits() {
+        let (td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "1\n2\n3\n", "c1");
+        write_commit_file(&repo, "a", "1\nx\n3\n", "c2");
+        let c3 = write_commit_file(&repo, "b", "b\n", "c3\n\nbody");
+
+        let files = format_patches(
+            repo_path,
+            &[c3],
+            &td.path().join("out"),
+            FormatPatchOptions::default(),
+        )
+        .unwrap();
+
+        let patches = read_patch_file(&files[0]).unwrap();
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].subject(), Some("c3"));
+        assert_eq!(patches[0].message.as_deref(), Some("c3\n\nbody"));
+        assert_eq!(
+            get_patch_files(&patches[0]).unwrap()[0].status,
+            StatusItemType::New
+        );
+
+        reset_repo(repo_path, c1, ResetMode::Hard).unwrap();
+
+        let res =
+            apply_patches(repo_path, &patches, ApplyTarget::Commits)
+                .unwrap();
+        assert_eq!(res.applied, 1);
+        assert!(res.failures.is_empty());
+
+        let head: CommitId =
+            repo.head().unwrap().target().unwrap().into();
+        let info = get_commits_info(repo_path, &[head], 100).unwrap();
+        assert_eq!(info[0].message, "c3\n\nbody");
+        assert_eq!(
+            repo.find_commit(head.into())
+                .unwrap()
+                .parent_id(0)
+                .unwrap(),
+            c1.into()
+        );
+    }
+
+    #[test]
+    fn test_hunk_failures() {
+        let (td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let lines = (1..=20)
+            .map(|i| format!("line {}\n", i))
+            .collect::<String>();
+        write_commit_file(&repo, "a", &lines, "c1");
+        let c2 = write_commit_file(
+            &repo,
+            "a",
+            &lines
+                .replace("line 2\n", "x\n")
+                .replace("line 19\n", "y\n"),
+            "c2",
+        );
+
+        let files = format_patches(
+            repo_path,
+            &[c2],
+            &td.path().join("out"),
+            FormatPatchOptions::default(),
+        )
+        .unwrap();
+        let patches = read_patch_file(&files[0]).unwrap();
+
+        // only the second hunk still fits
+        repo_write_file(
+            &repo,
+            "a",
+            &lines.replace("line 2\n", "z\n"),
+        )
+        .unwrap();
+
+        let failures =
+            check_patch(repo_path, &patches[0], ApplyTarget::WorkDir)
+                .unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, "a");
+        assert!(failures[0]
+            .hunk
+            .as_ref()
+            .unwrap()
+            .starts_with("@@ -1,"));
+
+        let res =
+            apply_patches(repo_path, &patches, ApplyTarget::WorkDir)
+                .unwrap();
+        assert_eq!(res.applied, 0);
+        assert_eq!(res.failures, failures);
+
+        // missing file fails as a whole
+        repo_write_file(&repo, "a", &lines).unwrap();
+        std::fs::remove_file(root.join("a")).unwrap();
+        let failures =
+            check_patch(repo_path, &patches[0], ApplyTarget::WorkDir)
+                .unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].hunk, None);
+    }
+
+    #[test]
+    fn test_added_file_exists() {
+        let (td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a\n", "c1");
+        let c2 = write_commit_file(&repo, "b", "b\n", "c2");
+
+        let files = format_patches(
+            repo_path,
+            &[c2],
+            &td.path().join("out"),
+            FormatPatchOptions::default(),
+        )
+        .unwrap();
+        let patches = read_patch_file(&files[0]).unwrap();
+
+        for target in &[
+            ApplyTarget::WorkDir,
+            ApplyTarget::Index,
+            ApplyTarget::Commits,
+        ] {
+            let failures =
+                check_patch(repo_path, &patches[0], *target).unwrap();
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].path, "b");
+        }
+
+        reset_repo(repo_path, c1, ResetMode::Hard).unwrap();
+        assert!(check_patch(
+            repo_path,
+            &patches[0],
+            ApplyTarget::Commits
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    #[test]
+    fn test_plain_diff_to_index() {
+        let (td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a\n", "c1");
+
+        let diff = "diff --git a/a b/a\nindex 7898192..6178079 100644\n--- a/a\n+++ b/a\n@@ -1 +1 @@\n-a\n+b\n";
+        let path = td.path().join("plain.diff");
+        fs::write(&path, diff).unwrap();
+
+        let patches = read_patch_file(&path).unwrap();
+        assert_eq!(patches[0].author, None);
+        assert_eq!(patches[0].subject(), None);
+
+        let res =
+            apply_patches(repo_path, &patches, ApplyTarget::Index)
+                .unwrap();
+        assert_eq!(res.applied, 1);
+
+        let staged =
+            get_status(repo_path, StatusType::Stage, false).unwrap();
+        assert_eq!(staged.len(), 1);
+        // workdir untouched
+        assert_eq!(
+            fs::read_to_string(root.join("a")).unwrap(),
+            "a\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_without_git_header() {
+        let (td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a\n", "c1");
+
+        let diff = "intro\n--- a/a\t2021-01-01 10:00:00\n+++ b/a\n@@ -1 +1 @@\n-a\n+b\n--- /dev/null\n+++ b/new\n@@ -0,0 +1 @@\n+new\n";
+        let path = td.path().join("plain.diff");
+        fs::write(&path, diff).unwrap();
+
+        let patches = read_patch_file(&path).unwrap();
+        assert_eq!(patches.len(), 1);
+
+        let res =
+            apply_patches(repo_path, &patches, ApplyTarget::WorkDir)
+                .unwrap();
+        assert_eq!(res.applied, 1);
+        assert_eq!(
+            fs::read_to_string(root.join("a")).unwrap(),
+            "b\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("new")).unwrap(),
+            "new\n"
+        );
+    }
+}
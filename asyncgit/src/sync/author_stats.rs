@@ -0,0 +1,194 @@
+//! sync git api for per author statistics (like `git shortlog`)
+
+use super::{
+    commit_files::DiffStat,
+    logwalker::{LogStart, LogWalker},
+    mailmap::Mailmap,
+    utils::repo,
+    CommitId,
+};
+use crate::error::Result;
+use git2::{Commit, Repository};
+use scopetime::scope_time;
+use std::collections::HashMap;
+
+const WALK_CHUNK_SIZE: usize = 1000;
+
+/// aggregated commits of one author (after applying `.mailmap`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorStats {
+    ///
+    pub name: String,
+    ///
+    pub email: String,
+    ///
+    pub commits: usize,
+    /// author time of the oldest commit in secs since Unix epoch
+    pub first_time: i64,
+    /// author time of the latest commit in secs since Unix epoch
+    pub last_time: i64,
+    /// lines added and removed, only if requested.
+    /// merge commits do not count
+    pub lines: Option<DiffStat>,
+}
+
+/// walks the log from `start` and aggregates it per author,
+/// ordered by number of commits (descending).
+/// counting lines needs a diff per commit and is a lot slower
+pub fn get_author_stats(
+    repo_path: &str,
+    start: &LogStart,
+    with_lines: bool,
+) -> Result<Vec<AuthorStats>> {
+    scope_time!("get_author_stats");
+
+    let repo = repo(repo_path)?;
+    let mailmap = Mailmap::from_repo(&repo)?;
+
+    let mut authors: HashMap<(String, String), AuthorStats> =
+        HashMap::new();
+
+    let mut walker = LogWalker::new(&repo).with_start(start.clone());
+    let mut ids: Vec<CommitId> = Vec::with_capacity(WALK_CHUNK_SIZE);
+
+    loop {
+        ids.clear();
+        if walker.read(&mut ids, WALK_CHUNK_SIZE)? == 0 {
+            break;
+        }
+
+        for id in &ids {
+            let commit = repo.find_commit((*id).into())?;
+            let author = commit.author();
+            let (name, email) = mailmap.resolve(
+                &String::from_utf8_lossy(author.name_bytes()),
+                &String::from_utf8_lossy(author.email_bytes()),
+            );
+            let time = author.when().seconds();
+
+            let lines = if with_lines {
+                Some(commit_lines(&repo, &commit)?)
+            } else {
+                None
+            };
+
+            let entry = authors
+                .entry((name.to_lowercase(), email.to_lowercase()))
+                .or_insert_with(|| AuthorStats {
+                    name,
+                    email,
+                    commits: 0,
+                    first_time: time,
+                    last_time: time,
+                    lines: None,
+                });
+
+            entry.commits += 1;
+            entry.first_time = entry.first_time.min(time);
+            entry.last_time = entry.last_time.max(time);
+            if let Some(lines) = lines {
+                let total =
+                    entry.lines.get_or_insert_with(Default::default);
+                total.insertions += lines.insertions;
+                total.deletions += lines.deletions;
+            }
+        }
+    }
+
+    let mut res: Vec<AuthorStats> =
+        authors.into_iter().map(|(_, v)| v).collect();
+    res.sort_by(|a, b| {
+        b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(res)
+}
+
+fn commit_lines(
+    repo: &Repository,
+    commit: &Commit,
+) -> Result<DiffStat> {
+    if commit.parent_count() > 1 {
+        return Ok(DiffStat::default());
+    }
+
+    let parent_tree = if commit.parent_count() == 1 {
+        Some(commit.parent(0)?.tree()?)
+    } else {
+        None
+    };
+
+    let stats = repo
+        .diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit.tree()?),
+            None,
+        )?
+        .stats()?;
+
+    Ok(DiffStat {
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, stage_add_file,
+        tests::{repo_init_empty, write_commit_file},
+        utils::repo_write_file,
+    };
+    use git2::Signature;
+    use std::path::Path;
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a\nb\n", "c1");
+        write_commit_file(&repo, "a", "a\n", "c2");
+
+        // same person with an old address
+        repo_write_file(&repo, "b", "b\n").unwrap();
+        stage_add_file(repo_path, Path::new("b")).unwrap();
+        let sig = Signature::now("jd", "old@example.com").unwrap();
+        let mut index = repo.index().unwrap();
+        let tree =
+            repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "c3", &tree, &[&head])
+            .unwrap();
+
+        repo_write_file(
+            &repo,
+            ".mailmap",
+            "name <email> <old@example.com>\n",
+        )
+        .unwrap();
+        stage_add_file(repo_path, Path::new(".mailmap")).unwrap();
+        commit(repo_path, "c4").unwrap();
+
+        let res = get_author_stats(repo_path, &LogStart::Head, false)
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].name, "name");
+        assert_eq!(res[0].email, "email");
+        assert_eq!(res[0].commits, 4);
+        assert_eq!(res[0].lines, None);
+        assert!(res[0].first_time <= res[0].last_time);
+
+        let res = get_author_stats(repo_path, &LogStart::Head, true)
+            .unwrap();
+        assert_eq!(
+            res[0].lines,
+            Some(DiffStat {
+                insertions: 4,
+                deletions: 1,
+            })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! sync git api for blaming a file
+
+use super::{utils::repo, CommitId};
+use crate::error::{Error, Result};
+use git2::BlameOptions;
+use scopetime::scope_time;
+use std::path::Path;
+
+/// the commit that last changed a range of lines
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlameHunk {
+    ///
+    pub commit_id: CommitId,
+    ///
+    pub author: String,
+    /// time in secs since Unix epoch
+    pub time: i64,
+    /// 0-based index of the first line of the hunk
+    pub start_line: usize,
+    /// 0-based index of the line after the hunk
+    pub end_line: usize,
+}
+
+/// a file with the blame information for each of its lines
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileBlame {
+    /// commit the file was blamed at
+    pub commit_id: CommitId,
+    ///
+    pub path: String,
+    /// each line of the file with the hunk it belongs to
+    pub lines: Vec<(Option<BlameHunk>, String)>,
+}
+
+/// blames the file at `file_path` as of `commit_id` (defaults to `HEAD`)
+pub fn blame_file(
+    repo_path: &str,
+    file_path: &str,
+    commit_id: Option<CommitId>,
+) -> Result<FileBlame> {
+    scope_time!("blame_file");
+
+    let repo = repo(repo_path)?;
+
+    let commit = match commit_id {
+        Some(id) => repo.find_commit(id.into())?,
+        None => repo.head()?.peel_to_commit()?,
+    };
+
+    let mut opts = BlameOptions::new();
+    opts.newest_commit(commit.id());
+
+    let blame =
+        repo.blame_file(Path::new(file_path), Some(&mut opts))?;
+
+    let blob = commit
+        .tree()?
+        .get_path(Path::new(file_path))?
+        .to_object(&repo)?
+        .peel_to_blob()?;
+
+    if blob.is_binary() {
+        return Err(Error::Generic(format!(
+            "cannot blame binary file: {}",
+            file_path
+        )));
+    }
+
+    let content = String::from_utf8_lossy(blob.content());
+
+    let lines = content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let hunk = blame.get_line(idx + 1).map(|hunk| {
+                let start_line =
+                    hunk.final_start_line().saturating_sub(1);
+                BlameHunk {
+                    commit_id: hunk.final_commit_id().into(),
+                    author: hunk
+                        .final_signature()
+                        .name()
+                        .unwrap_or("<unknown>")
+                        .to_string(),
+                    time: hunk.final_signature().when().seconds(),
+                    start_line,
+                    end_line: start_line + hunk.lines_in_hunk(),
+                }
+            });
+
+            (hunk, line.to_string())
+        })
+        .collect();
+
+    Ok(FileBlame {
+        commit_id: commit.id().into(),
+        path: file_path.to_string(),
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::{repo_init_empty, write_commit_file};
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "foo", "a\nb\n", "c1");
+        let c2 =
+            write_commit_file(&repo, "foo", "a\nb\nc\nd\n", "c2");
+
+        let blame = blame_file(repo_path, "foo", None).unwrap();
+
+        assert_eq!(blame.commit_id, c2);
+        assert_eq!(blame.lines.len(), 4);
+
+        let commits: Vec<_> = blame
+            .lines
+            .iter()
+            .map(|(hunk, _)| hunk.as_ref().unwrap().commit_id)
+            .collect();
+        assert_eq!(commits, vec![c1, c1, c2, c2]);
+
+        let (hunk, line) = &blame.lines[2];
+        assert_eq!(line, "c");
+        assert_eq!(hunk.as_ref().unwrap().start_line, 2);
+        assert_eq!(hunk.as_ref().unwrap().end_line, 4);
+    }
+
+    #[test]
+    fn test_at_commit() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "foo", "a\n", "c1");
+        write_commit_file(&repo, "foo", "b\n", "c2");
+
+        let blame = blame_file(repo_path, "foo", Some(c1)).unwrap();
+
+        assert_eq!(blame.lines.len(), 1);
+        assert_eq!(blame.lines[0].1, "a");
+        assert_eq!(blame.lines[0].0.as_ref().unwrap().commit_id, c1);
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "foo", "a\n", "c1");
+
+        assert!(blame_file(repo_path, "bar", None).is_err());
+    }
+}
@@ -44,6 +44,13 @@ pub(crate) fn get_branch_name_repo(
     Err(Error::NoHead)
 }
 
+/// returns whether `HEAD` points to a commit instead of a branch
+pub fn is_head_detached(repo_path: &str) -> Result<bool> {
+    let repo = utils::repo(repo_path)?;
+
+    Ok(repo.head_detached()?)
+}
+
 ///
 #[derive(Debug)]
 pub struct LocalBranch {
@@ -238,30 +245,25 @@ pub fn checkout_branch(
 ) -> Result<()> {
     scope_time!("checkout_branch");
 
-    // This defaults to a safe checkout, so don't delete anything that
-    // hasn't been committed or stashed, in this case it will Err
     let repo = utils::repo(repo_path)?;
-    let cur_ref = repo.head()?;
-    let statuses = repo.statuses(Some(
-        git2::StatusOptions::new().include_ignored(false),
-    ))?;
-
-    if statuses.is_empty() {
-        repo.set_head(branch_ref)?;
-
-        if let Err(e) = repo.checkout_head(Some(
-            git2::build::CheckoutBuilder::new().force(),
-        )) {
-            // This is safe beacuse cur_ref was just found
-            repo.set_head(
-                bytes2string(cur_ref.name_bytes())?.as_str(),
-            )?;
-            return Err(Error::Git(e));
-        }
-        Ok(())
-    } else {
-        Err(Error::UncommittedChanges)
-    }
+
+    checkout_with(&repo, || Ok(repo.set_head(branch_ref)?))
+}
+
+/// detaches HEAD at `commit` and checks it out, does not work if
+/// there are uncommitted changes
+pub fn checkout_commit(
+    repo_path: &str,
+    commit: CommitId,
+) -> Result<()> {
+    scope_time!("checkout_commit");
+
+    let repo = utils::repo(repo_path)?;
+
+    checkout_with(
+        &repo,
+        || Ok(repo.set_head_detached(commit.into())?),
+    )
 }
 
 ///
@@ -272,7 +274,38 @@ pub fn checkout_remote_branch(
     scope_time!("checkout_remote_branch");
 
     let repo = utils::repo(repo_path)?;
+
+    checkout_with(&repo, || {
+        let name = if let Some(pos) = branch.name.rfind('/') {
+            branch.name[pos..].to_string()
+        } else {
+            branch.name.clone()
+        };
+
+        let commit = repo.find_commit(branch.top_commit.into())?;
+        let mut new_branch = repo.branch(&name, &commit, false)?;
+        new_branch.set_upstream(Some(&branch.name))?;
+
+        repo.set_head(
+            bytes2string(new_branch.into_reference().name_bytes())?
+                .as_str(),
+        )?;
+
+        Ok(())
+    })
+}
+
+/// moves HEAD using `set_head` and checks it out. fails without
+/// touching anything if there are uncommitted changes, restores the
+/// previous HEAD if the checkout fails
+fn checkout_with(
+    repo: &Repository,
+    set_head: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    // This defaults to a safe checkout, so don't delete anything that
+    // hasn't been committed or stashed, in this case it will Err
     let cur_ref = repo.head()?;
+    let cur_detached = repo.head_detached()?;
 
     if !repo
         .statuses(Some(
@@ -283,28 +316,21 @@ pub fn checkout_remote_branch(
         return Err(Error::UncommittedChanges);
     }
 
-    let name = if let Some(pos) = branch.name.rfind('/') {
-        branch.name[pos..].to_string()
-    } else {
-        branch.name.clone()
-    };
-
-    let commit = repo.find_commit(branch.top_commit.into())?;
-    let mut new_branch = repo.branch(&name, &commit, false)?;
-    new_branch.set_upstream(Some(&branch.name))?;
-
-    repo.set_head(
-        bytes2string(new_branch.into_reference().name_bytes())?
-            .as_str(),
-    )?;
+    set_head()?;
 
     if let Err(e) = repo.checkout_head(Some(
         git2::build::CheckoutBuilder::new().force(),
     )) {
-        // This is safe beacuse cur_ref was just found
-        repo.set_head(bytes2string(cur_ref.name_bytes())?.as_str())?;
+        // This is safe because cur_ref was just found
+        match cur_ref.target() {
+            Some(id) if cur_detached => repo.set_head_detached(id)?,
+            _ => repo.set_head(
+                bytes2string(cur_ref.name_bytes())?.as_str(),
+            )?,
+        }
         return Err(Error::Git(e));
     }
+
     Ok(())
 }
 
@@ -343,6 +369,22 @@ pub fn create_branch(repo_path: &str, name: &str) -> Result<()> {
     Ok(())
 }
 
+/// creates a new branch pointing to `commit`, does not switch to it
+pub fn create_branch_at(
+    repo_path: &str,
+    name: &str,
+    commit: CommitId,
+) -> Result<()> {
+    scope_time!("create_branch_at");
+
+    let repo = utils::repo(repo_path)?;
+
+    let commit = repo.find_commit(commit.into())?;
+    repo.branch(name, &commit, false)?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests_branch_name {
     use super::*;
@@ -376,7 +418,9 @@ mod tests_branch_name {
 #[cfg(test)]
 mod tests_create_branch {
     use super::*;
-    use crate::sync::tests::repo_init;
+    use crate::sync::tests::{
+        repo_init, repo_init_empty, write_commit_file,
+    };
 
     #[test]
     fn test_smoke() {
@@ -391,6 +435,28 @@ mod tests_create_branch {
             "branch1"
         );
     }
+
+    #[test]
+    fn test_create_at() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        write_commit_file(&repo, "a", "b", "c2");
+
+        create_branch_at(repo_path, "old", c1).unwrap();
+
+        assert_eq!(get_branch_name(repo_path).unwrap(), "master");
+        assert_eq!(
+            repo.find_branch("old", BranchType::Local)
+                .unwrap()
+                .get()
+                .target()
+                .map(CommitId::new),
+            Some(c1)
+        );
+    }
 }
 
 #[cfg(test)]
@@ -574,7 +640,10 @@ mod tests_branches {
 #[cfg(test)]
 mod tests_checkout {
     use super::*;
-    use crate::sync::tests::repo_init;
+    use crate::sync::{
+        tests::{repo_init, repo_init_empty, write_commit_file},
+        utils::repo_write_file,
+    };
 
     #[test]
     fn test_smoke() {
@@ -604,6 +673,73 @@ mod tests_checkout {
         );
         assert!(checkout_branch(repo_path, "refs/heads/test").is_ok());
     }
+
+    #[test]
+    fn test_checkout_commit() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        write_commit_file(&repo, "a", "b", "c2");
+
+        assert!(!is_head_detached(repo_path).unwrap());
+
+        checkout_commit(repo_path, c1).unwrap();
+
+        assert!(is_head_detached(repo_path).unwrap());
+        assert_eq!(get_head_repo(&repo).unwrap(), c1);
+        assert_eq!(
+            std::fs::read_to_string(root.join("a")).unwrap(),
+            "a"
+        );
+        assert!(matches!(
+            get_branch_name(repo_path),
+            Err(Error::NoHead)
+        ));
+
+        repo_write_file(&repo, "a", "x").unwrap();
+        assert!(matches!(
+            checkout_commit(repo_path, c1),
+            Err(Error::UncommittedChanges)
+        ));
+    }
+
+    #[test]
+    fn test_checkout_commit_rollback_detached() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "lost", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        checkout_commit(repo_path, c2).unwrap();
+
+        // make the checkout of `c1` fail
+        let blob = repo
+            .find_commit(c1.into())
+            .unwrap()
+            .tree()
+            .unwrap()
+            .get_name("a")
+            .unwrap()
+            .id()
+            .to_string();
+        std::fs::remove_file(
+            repo.path()
+                .join("objects")
+                .join(&blob[..2])
+                .join(&blob[2..]),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            checkout_commit(repo_path, c1),
+            Err(Error::Git(_))
+        ));
+        assert!(is_head_detached(repo_path).unwrap());
+        assert_eq!(get_head_repo(&repo).unwrap(), c2);
+    }
 }
 
 #[cfg(test)]
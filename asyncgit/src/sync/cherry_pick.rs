@@ -0,0 +1,429 @@
+//! sync git api for cherry-picking commits
+
+use super::{commit::signature_allow_undefined_name, utils::repo};
+use crate::{
+    error::{Error, Result},
+    sync::{abort_pending_state, CommitId},
+};
+use git2::{Commit, Oid, Repository, RepositoryState};
+use scopetime::scope_time;
+use std::{fs, io, path::PathBuf};
+
+/// file in the git dir holding the commits a paused cherry-pick has
+/// left to pick after the one that stopped
+const TODO_FILE: &str = "gitui-cherry-pick-todo";
+
+///
+#[derive(Debug, Default, PartialEq)]
+pub struct CherryPickResult {
+    /// commits created on top of `HEAD`
+    pub picked: Vec<CommitId>,
+    /// commits not picked yet, the first one stopped with conflicts
+    pub remaining: Vec<CommitId>,
+}
+
+/// picks `commits` onto `HEAD` in the given order.
+/// stops at the first commit that conflicts and leaves the repo in
+/// the cherry-pick state so it can be resolved and continued
+pub fn cherry_pick(
+    repo_path: &str,
+    commits: &[CommitId],
+) -> Result<CherryPickResult> {
+    scope_time!("cherry_pick");
+
+    let repo = repo(repo_path)?;
+
+    if has_todo(&repo) {
+        return Err(Error::Generic(
+            "another cherry-pick is in progress".into(),
+        ));
+    }
+
+    pick(&repo, commits, CherryPickResult::default())
+}
+
+/// commits the resolved cherry-pick in progress and picks the
+/// commits left after it
+pub fn cherry_pick_continue(
+    repo_path: &str,
+) -> Result<CherryPickResult> {
+    scope_time!("cherry_pick_continue");
+
+    let repo = repo(repo_path)?;
+    let todo = load_todo(&repo)?;
+
+    let mut res = CherryPickResult::default();
+
+    match repo.state() {
+        RepositoryState::CherryPick => {
+            if repo.index()?.has_conflicts() {
+                return Err(Error::Generic(
+                    "resolve and stage all conflicts first".into(),
+                ));
+            }
+
+            let commit = repo
+                .revparse_single("CHERRY_PICK_HEAD")?
+                .peel_to_commit()?;
+
+            res.picked.extend(commit_picked(&repo, &commit)?);
+        }
+        // the commit that stopped was committed outside of gitui
+        RepositoryState::Clean if has_todo(&repo) => (),
+        _ => {
+            return Err(Error::Generic(
+                "no cherry-pick in progress".into(),
+            ))
+        }
+    }
+
+    pick(&repo, &todo, res)
+}
+
+/// throws away the cherry-pick in progress and the commits left to
+/// pick after it
+pub fn cherry_pick_abort(repo_path: &str) -> Result<()> {
+    scope_time!("cherry_pick_abort");
+
+    abort_pending_state(repo_path)?;
+
+    remove_todo(&repo(repo_path)?)
+}
+
+/// commits left to pick after the one a cherry-pick stopped at
+pub fn cherry_pick_todo(repo_path: &str) -> Result<Vec<CommitId>> {
+    scope_time!("cherry_pick_todo");
+
+    load_todo(&repo(repo_path)?)
+}
+
+/// whether a cherry-pick of several commits is paused, even if the
+/// commit it stopped at was resolved already
+pub(super) fn has_todo(repo: &Repository) -> bool {
+    todo_path(repo).exists()
+}
+
+fn todo_path(repo: &Repository) -> PathBuf {
+    repo.path().join(TODO_FILE)
+}
+
+fn load_todo(repo: &Repository) -> Result<Vec<CommitId>> {
+    let content = match fs::read_to_string(todo_path(repo)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Ok(Vec::new())
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    content
+        .lines()
+        .map(|line| Ok(Oid::from_str(line)?.into()))
+        .collect()
+}
+
+fn save_todo(repo: &Repository, todo: &[CommitId]) -> Result<()> {
+    if todo.is_empty() {
+        return remove_todo(repo);
+    }
+
+    let content: String = todo
+        .iter()
+        .map(|id| format!("{}\n", id.to_string()))
+        .collect();
+
+    fs::write(todo_path(repo), content)?;
+
+    Ok(())
+}
+
+fn remove_todo(repo: &Repository) -> Result<()> {
+    match fs::remove_file(todo_path(repo)) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        res => Ok(res?),
+    }
+}
+
+/// picks `commits` onto `HEAD` in the given order, on conflicts the
+/// commits after the one that stopped are persisted to continue with
+fn pick(
+    repo: &Repository,
+    commits: &[CommitId],
+    mut res: CherryPickResult,
+) -> Result<CherryPickResult> {
+    for (idx, id) in commits.iter().enumerate() {
+        let commit = repo.find_commit((*id).into())?;
+
+        repo.cherrypick(&commit, None)?;
+
+        if repo.index()?.has_conflicts() {
+            res.remaining = commits[idx..].to_vec();
+            save_todo(repo, &commits[idx + 1..])?;
+            return Ok(res);
+        }
+
+        if let Some(id) = commit_picked(repo, &commit)? {
+            res.picked.push(id);
+        }
+    }
+
+    remove_todo(repo)?;
+
+    Ok(res)
+}
+
+/// commits the index as a copy of `picked`, returns `None` if the
+/// pick turned out empty
+fn commit_picked(
+    repo: &Repository,
+    picked: &Commit,
+) -> Result<Option<CommitId>> {
+    let mut index = repo.index()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    if tree.id() == head.tree_id() {
+        repo.cleanup_state()?;
+        return Ok(None);
+    }
+
+    // MERGE_MSG would contain the list of conflicts
+    let message = picked.message().ok_or_else(|| {
+        Error::Generic("commit message is not utf8".into())
+    })?;
+
+    let committer = signature_allow_undefined_name(repo)?;
+
+    let id = repo.commit(
+        Some("HEAD"),
+        &picked.author(),
+        &committer,
+        message,
+        &tree,
+        &[&head],
+    )?;
+
+    repo.cleanup_state()?;
+
+    Ok(Some(id.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        abort_pending_state, checkout_branch, commit, create_branch,
+        get_commits_info, repo_state, stage_add_file,
+        tests::{repo_init_empty, write_commit_file},
+        utils::repo_write_file,
+        RepoState,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature").unwrap();
+        let c2 = write_commit_file(&repo, "b", "b", "c2");
+        let c3 = write_commit_file(&repo, "c", "c", "c3");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        let res = cherry_pick(repo_path, &[c2, c3]).unwrap();
+
+        assert_eq!(res.picked.len(), 2);
+        assert!(res.remaining.is_empty());
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+
+        let infos =
+            get_commits_info(repo_path, &res.picked, 50).unwrap();
+        assert_eq!(infos[0].message, "c2");
+        assert_eq!(infos[1].message, "c3");
+        assert!(root.join("c").exists());
+    }
+
+    #[test]
+    fn test_conflict_continue() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature").unwrap();
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        let c3 = write_commit_file(&repo, "c", "c", "c3");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "a", "x", "c4");
+
+        let res = cherry_pick(repo_path, &[c2, c3]).unwrap();
+
+        assert!(res.picked.is_empty());
+        assert_eq!(res.remaining, vec![c2, c3]);
+        assert_eq!(
+            repo_state(repo_path).unwrap(),
+            RepoState::CherryPick
+        );
+        assert_eq!(cherry_pick_todo(repo_path).unwrap(), vec![c3]);
+        assert!(cherry_pick_continue(repo_path).is_err());
+
+        repo_write_file(&repo, "a", "resolved").unwrap();
+        repo.index().unwrap().add_path(Path::new("a")).unwrap();
+        repo.index().unwrap().write().unwrap();
+
+        let res = cherry_pick_continue(repo_path).unwrap();
+
+        assert_eq!(res.picked.len(), 2);
+        assert!(res.remaining.is_empty());
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert!(cherry_pick_todo(repo_path).unwrap().is_empty());
+        let infos =
+            get_commits_info(repo_path, &res.picked, 50).unwrap();
+        assert_eq!(infos[0].message, "c2");
+        assert_eq!(infos[1].message, "c3");
+    }
+
+    #[test]
+    fn test_continue_resolved_outside() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature").unwrap();
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        let c3 = write_commit_file(&repo, "c", "c", "c3");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "a", "x", "c4");
+
+        cherry_pick(repo_path, &[c2, c3]).unwrap();
+
+        // resolved and committed like `git commit` would
+        repo_write_file(&repo, "a", "resolved").unwrap();
+        stage_add_file(repo_path, Path::new("a")).unwrap();
+        commit(repo_path, "c2 resolved").unwrap();
+        repo.cleanup_state().unwrap();
+
+        assert_eq!(
+            repo_state(repo_path).unwrap(),
+            RepoState::CherryPick
+        );
+
+        let res = cherry_pick_continue(repo_path).unwrap();
+
+        assert_eq!(res.picked.len(), 1);
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert!(root.join("c").exists());
+    }
+
+    #[test]
+    fn test_abort_drops_todo() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature").unwrap();
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        let c3 = write_commit_file(&repo, "c", "c", "c3");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "a", "x", "c4");
+
+        cherry_pick(repo_path, &[c2, c3]).unwrap();
+        assert!(cherry_pick(repo_path, &[c3]).is_err());
+
+        cherry_pick_abort(repo_path).unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert!(cherry_pick_todo(repo_path).unwrap().is_empty());
+        assert!(!root.join("c").exists());
+    }
+
+    #[test]
+    fn test_abort() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature").unwrap();
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        let c3 = write_commit_file(&repo, "a", "x", "c3");
+
+        cherry_pick(repo_path, &[c2]).unwrap();
+        abort_pending_state(repo_path).unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(
+            CommitId::from(repo.head().unwrap().target().unwrap()),
+            c3
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("a")).unwrap(),
+            "x"
+        );
+    }
+
+    #[test]
+    fn test_abort_keeps_unrelated_changes() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        write_commit_file(&repo, "b", "b", "c2");
+        create_branch(repo_path, "feature").unwrap();
+        repo_write_file(&repo, "new", "new").unwrap();
+        stage_add_file(repo_path, Path::new("new")).unwrap();
+        let c3 = write_commit_file(&repo, "a", "b", "c3");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "a", "x", "c4");
+
+        repo_write_file(&repo, "b", "unrelated").unwrap();
+
+        cherry_pick(repo_path, &[c3]).unwrap();
+        assert!(root.join("new").exists());
+
+        abort_pending_state(repo_path).unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(
+            std::fs::read_to_string(root.join("a")).unwrap(),
+            "x"
+        );
+        assert!(!root.join("new").exists());
+        assert_eq!(
+            std::fs::read_to_string(root.join("b")).unwrap(),
+            "unrelated"
+        );
+        assert!(!repo.index().unwrap().has_conflicts());
+    }
+
+    #[test]
+    fn test_abort_refuses_unstaged_on_staged() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature").unwrap();
+        repo_write_file(&repo, "c", "c").unwrap();
+        stage_add_file(repo_path, Path::new("c")).unwrap();
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "a", "x", "c3");
+
+        // the pick stages `c` and conflicts on `a`
+        cherry_pick(repo_path, &[c2]).unwrap();
+        repo_write_file(&repo, "c", "edited").unwrap();
+
+        assert!(abort_pending_state(repo_path).is_err());
+        assert_eq!(
+            std::fs::read_to_string(root.join("c")).unwrap(),
+            "edited"
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! sync git api for the combined diff of a merge commit
+
+use super::{
+    diff::{
+        mark_word_changes, DiffLine, DiffLinePosition, DiffLineType,
+        FileDiff, Hunk,
+    },
+    CommitId,
+};
+use crate::{
+    error::{Error, Result},
+    hash,
+};
+use git2::{Blob, Commit, DiffOptions, ErrorCode, Patch, Repository};
+use std::{convert::TryFrom, ops::Range, path::Path};
+
+/// unchanged lines shown around the changes of a hunk
+const CONTEXT_LINES: usize = 3;
+
+/// line the merge does not have anymore
+struct LostLine {
+    /// bit mask of the parents that had the line
+    parents: u64,
+    old_lineno: u32,
+    content: String,
+}
+
+/// diff of `path` in the merge commit `id` against all of its parents
+/// at once, like `git diff --cc`: only hunks that differ from every
+/// parent are kept and a line counts as added if no parent has it.
+/// `None` if each change was taken from one of the parents as it is.
+/// binary files have no hunks and report their sizes against the
+/// first parent
+pub(crate) fn get_combined_diff(
+    repo: &Repository,
+    id: CommitId,
+    path: &str,
+) -> Result<Option<FileDiff>> {
+    let commit = repo.find_commit(id.into())?;
+    let parents = commit.parents().collect::<Vec<_>>();
+
+    if parents.len() > 64 {
+        return Err(Error::Generic(
+            "too many parents to combine".into(),
+        ));
+    }
+
+    let new = file_blob(repo, &commit, path)?;
+    let olds = parents
+        .iter()
+        .map(|parent| file_blob(repo, parent, path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let new_content = content(new.as_ref());
+    let first_size =
+        content(olds.first().and_then(Option::as_ref)).len() as u64;
+    let mut res = FileDiff {
+        sizes: (first_size, new_content.len() as u64),
+        size_delta: (new_content.len() as i64)
+            .saturating_sub(first_size as i64),
+        ..FileDiff::default()
+    };
+
+    if new
+        .iter()
+        .chain(olds.iter().flatten())
+        .any(|blob| blob.is_binary())
+    {
+        return Ok(Some(res));
+    }
+
+    let lines = split_lines(new_content);
+    // bit mask of the parents each line of the merge is added to
+    let mut added = vec![0_u64; lines.len()];
+    // lines lost before each line of the merge, the last entry
+    // holds the ones lost at the end
+    let mut lost: Vec<Vec<LostLine>> =
+        (0..=lines.len()).map(|_| Vec::new()).collect();
+
+    let mut opts = DiffOptions::new();
+    opts.context_lines(0);
+
+    for (idx, old) in olds.iter().enumerate() {
+        let parent = 1 << idx;
+        let patch = Patch::from_buffers(
+            content(old.as_ref()),
+            None,
+            new_content,
+            None,
+            Some(&mut opts),
+        )?;
+
+        for hunk_idx in 0..patch.num_hunks() {
+            let (hunk, line_count) = patch.hunk(hunk_idx)?;
+            // a pure deletion starts after the line `new_start`
+            let at = if hunk.new_lines() == 0 {
+                hunk.new_start()
+            } else {
+                hunk.new_start() - 1
+            } as usize;
+
+            let mut hunk_lost = Vec::new();
+            for line_idx in 0..line_count {
+                let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+                match line.origin() {
+                    '+' => {
+                        if let Some(mask) =
+                            line.new_lineno().and_then(|n| {
+                                added.get_mut(n as usize - 1)
+                            })
+                        {
+                            *mask |= parent;
+                        }
+                    }
+                    '-' => hunk_lost.push(LostLine {
+                        parents: parent,
+                        old_lineno: line
+                            .old_lineno()
+                            .unwrap_or_default(),
+                        content: String::from_utf8_lossy(
+                            line.content(),
+                        )
+                        .into_owned(),
+                    }),
+                    _ => (),
+                }
+            }
+
+            merge_lost(&mut lost[at], parent, hunk_lost);
+        }
+    }
+
+    let all = (0..parents.len()).fold(0, |mask, idx| mask | 1 << idx);
+    let changed = |at: usize| {
+        lost[at].iter().fold(
+            added.get(at).copied().unwrap_or_default(),
+            |mask, line| mask | line.parents,
+        )
+    };
+
+    // runs of changes that took the side of no parent
+    let mut kept = vec![false; lost.len()];
+    let mut at = 0;
+    while at < kept.len() {
+        let start = at;
+        let mut mask = 0;
+        while at < kept.len() && changed(at) != 0 {
+            mask |= changed(at);
+            at += 1;
+        }
+
+        if mask == all {
+            for kept in &mut kept[start..at] {
+                *kept = true;
+            }
+        }
+
+        at = at.max(start + 1);
+    }
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for at in (0..kept.len()).filter(|at| kept[*at]) {
+        let range = at.saturating_sub(CONTEXT_LINES)
+            ..(at + CONTEXT_LINES + 1).min(kept.len());
+
+        match ranges.last_mut() {
+            Some(last) if last.end >= range.start => {
+                last.end = range.end;
+            }
+            _ => ranges.push(range),
+        }
+    }
+
+    if ranges.is_empty() {
+        return Ok(None);
+    }
+
+    let marker = "@".repeat(parents.len() + 1);
+
+    for range in ranges {
+        let header = format!(
+            "{} +{},{} {}\n",
+            marker,
+            range.start + 1,
+            range.end.min(lines.len()).saturating_sub(range.start),
+            marker
+        );
+        let header_hash = hash(&header);
+
+        let mut hunk_lines = vec![DiffLine {
+            content: header,
+            line_type: DiffLineType::Header,
+            ..DiffLine::default()
+        }];
+
+        for at in range {
+            if kept[at] {
+                hunk_lines.extend(lost[at].iter().map(|line| {
+                    DiffLine {
+                        content: line.content.clone(),
+                        line_type: DiffLineType::Delete,
+                        position: DiffLinePosition {
+                            old_lineno: Some(line.old_lineno),
+                            new_lineno: None,
+                        },
+                        changes: Vec::new(),
+                    }
+                }));
+            }
+
+            if let Some(line) = lines.get(at) {
+                let line_type = if kept[at] && added[at] == all {
+                    DiffLineType::Add
+                } else {
+                    DiffLineType::None
+                };
+
+                hunk_lines.push(DiffLine {
+                    content: String::from_utf8_lossy(line)
+                        .into_owned(),
+                    line_type,
+                    position: DiffLinePosition {
+                        old_lineno: None,
+                        new_lineno: u32::try_from(at + 1).ok(),
+                    },
+                    changes: Vec::new(),
+                });
+            }
+        }
+
+        mark_word_changes(&mut hunk_lines);
+
+        res.lines += hunk_lines.len();
+        res.hunks.push(Hunk {
+            header_hash,
+            lines: hunk_lines,
+        });
+    }
+
+    Ok(Some(res))
+}
+
+/// merges the `lines` `parent` lost at one place into the ones lost
+/// there by other parents keeping the order of both, a line several
+/// parents lost is listed once
+fn merge_lost(
+    lost: &mut Vec<LostLine>,
+    parent: u64,
+    lines: Vec<LostLine>,
+) {
+    let mut from = 0;
+    let mut unmatched = Vec::new();
+
+    for line in lines {
+        if let Some(idx) = lost.iter().skip(from).position(|lost| {
+            lost.parents & parent == 0 && lost.content == line.content
+        }) {
+            let idx = from + idx;
+            lost[idx].parents |= parent;
+            from = idx + unmatched.len() + 1;
+            lost.splice(idx..idx, unmatched.drain(..));
+        } else {
+            unmatched.push(line);
+        }
+    }
+
+    lost.extend(unmatched);
+}
+
+fn file_blob<'a>(
+    repo: &'a Repository,
+    commit: &Commit,
+    path: &str,
+) -> Result<Option<Blob<'a>>> {
+    match commit.tree()?.get_path(Path::new(path)) {
+        Ok(entry) => Ok(entry.to_object(repo)?.into_blob().ok()),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn content<'a>(blob: Option<&'a Blob>) -> &'a [u8] {
+    blob.map_or(&[], Blob::content)
+}
+
+/// lines of `content` including their line endings
+fn split_lines(content: &[u8]) -> Vec<&[u8]> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+
+    for (idx, byte) in content.iter().enumerate() {
+        if *byte == b'\n' {
+            lines.push(&content[start..=idx]);
+            start = idx + 1;
+        }
+    }
+
+    if start < content.len() {
+        lines.push(&content[start..]);
+    }
+
+    lines
+}
@@ -1,6 +1,8 @@
 use super::{get_head, utils::repo, CommitId};
 use crate::error::Result;
-use git2::{ErrorCode, ObjectType, Repository, Signature};
+use git2::{
+    ErrorCode, ObjectType, Repository, RepositoryState, Signature,
+};
 use scopetime::scope_time;
 
 ///
@@ -56,7 +58,8 @@ pub(crate) fn signature_allow_undefined_name(
     signature
 }
 
-/// this does not run any git hooks
+/// this does not run any git hooks.
+/// like `git commit` this concludes a revert or cherry-pick in progress
 pub fn commit(repo_path: &str, msg: &str) -> Result<CommitId> {
     scope_time!("commit");
 
@@ -75,16 +78,23 @@ pub fn commit(repo_path: &str, msg: &str) -> Result<CommitId> {
 
     let parents = parents.iter().collect::<Vec<_>>();
 
-    Ok(repo
-        .commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            msg,
-            &tree,
-            parents.as_slice(),
-        )?
-        .into())
+    let id = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        msg,
+        &tree,
+        parents.as_slice(),
+    )?;
+
+    if matches!(
+        repo.state(),
+        RepositoryState::Revert | RepositoryState::CherryPick
+    ) {
+        repo.cleanup_state()?;
+    }
+
+    Ok(id.into())
 }
 
 /// Tag a commit.
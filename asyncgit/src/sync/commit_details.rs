@@ -4,7 +4,7 @@ use git2::Signature;
 use scopetime::scope_time;
 
 ///
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CommitSignature {
     ///
     pub name: String,
@@ -12,6 +12,8 @@ pub struct CommitSignature {
     pub email: String,
     /// time in secs since Unix epoch
     pub time: i64,
+    /// timezone of `time` as offset from UTC in minutes
+    pub time_offset: i32,
 }
 
 impl CommitSignature {
@@ -22,6 +24,38 @@ impl CommitSignature {
             email: s.email().unwrap_or("").to_string(),
 
             time: s.when().seconds(),
+            time_offset: s.when().offset_minutes(),
+        }
+    }
+}
+
+/// `key: value` line of the last paragraph of a commit message
+/// (e.g. `Signed-off-by: name <email>`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitTrailer {
+    ///
+    pub key: String,
+    ///
+    pub value: String,
+}
+
+impl CommitTrailer {
+    fn parse(line: &str) -> Option<Self> {
+        let (key, value) = line.split_at(line.find(':')?);
+        let value = &value[1..];
+        let valid_key = !key.is_empty()
+            && key.chars().all(|c| c.is_alphanumeric() || c == '-');
+        // rules out urls like `https://..`
+        let separated = value.is_empty()
+            || value.starts_with(char::is_whitespace);
+
+        if valid_key && separated {
+            Some(Self {
+                key: key.to_string(),
+                value: value.trim().to_string(),
+            })
+        } else {
+            None
         }
     }
 }
@@ -30,8 +64,13 @@ impl CommitSignature {
 pub struct CommitMessage {
     /// first line
     pub subject: String,
-    /// remaining lines if more than one
+    /// remaining lines if more than one (without the trailers)
     pub body: Option<String>,
+    /// trailers found in the last paragraph of the body
+    pub trailers: Vec<CommitTrailer>,
+    /// the lines `trailers` were parsed from (and the blank lines
+    /// around them) as written
+    trailer_lines: Vec<String>,
 }
 
 impl CommitMessage {
@@ -44,9 +83,12 @@ impl CommitMessage {
             String::new()
         };
 
-        let body: Vec<String> =
+        let mut body: Vec<String> =
             lines.map(|line| line.to_string()).collect();
 
+        let (trailers, trailer_lines) =
+            Self::split_trailers(&mut body);
+
         Self {
             subject,
             body: if body.is_empty() {
@@ -54,16 +96,70 @@ impl CommitMessage {
             } else {
                 Some(body.join("\n"))
             },
+            trailers,
+            trailer_lines,
         }
     }
 
-    ///
+    /// removes the trailer paragraph from the end of `body` and
+    /// returns the trailers and the removed lines. leaves `body`
+    /// untouched if the last paragraph contains anything but
+    /// trailers and their continuation lines
+    fn split_trailers(
+        body: &mut Vec<String>,
+    ) -> (Vec<CommitTrailer>, Vec<String>) {
+        let is_blank = |line: &String| line.trim().is_empty();
+
+        let end = body
+            .iter()
+            .rposition(|l| !is_blank(l))
+            .map_or(0, |i| i + 1);
+
+        let start = match body[..end].iter().rposition(is_blank) {
+            Some(blank) => blank + 1,
+            // the trailers need to be separated from the subject
+            None => return (Vec::new(), Vec::new()),
+        };
+
+        let mut trailers: Vec<CommitTrailer> = Vec::new();
+        for line in &body[start..end] {
+            if line.starts_with(char::is_whitespace) {
+                match trailers.last_mut() {
+                    Some(last) => {
+                        last.value.push(' ');
+                        last.value.push_str(line.trim());
+                    }
+                    None => return (Vec::new(), Vec::new()),
+                }
+            } else if let Some(trailer) = CommitTrailer::parse(line) {
+                trailers.push(trailer);
+            } else {
+                return (Vec::new(), Vec::new());
+            }
+        }
+
+        let body_end = body[..start]
+            .iter()
+            .rposition(|l| !is_blank(l))
+            .map_or(0, |i| i + 1);
+
+        (trailers, body.split_off(body_end))
+    }
+
+    /// the message as it was parsed, including its trailers
     pub fn combine(self) -> String {
-        if let Some(body) = self.body {
+        let mut res = if let Some(body) = self.body {
             format!("{}\n{}", self.subject, body)
         } else {
             self.subject
+        };
+
+        for line in self.trailer_lines {
+            res.push('\n');
+            res.push_str(&line);
         }
+
+        res
     }
 }
 
@@ -77,6 +173,8 @@ pub struct CommitDetails {
     pub message: Option<CommitMessage>,
     ///
     pub hash: String,
+    /// more than one for merge commits
+    pub parents: Vec<CommitId>,
 }
 
 ///
@@ -106,6 +204,7 @@ pub fn get_commit_details(
         committer,
         message: Some(msg),
         hash: id.to_string(),
+        parents: commit.parent_ids().map(CommitId::from).collect(),
     };
 
     Ok(details)
@@ -114,7 +213,7 @@ pub fn get_commit_details(
 #[cfg(test)]
 mod tests {
 
-    use super::{get_commit_details, CommitMessage};
+    use super::{get_commit_details, CommitMessage, CommitTrailer};
     use crate::error::Result;
     use crate::sync::{
         commit, stage_add_file, tests::repo_init_empty,
@@ -167,4 +266,79 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_msg_trailers() -> Result<()> {
+        let text = "foo\n\nbar\n\nCo-authored-by: a <a@a>\nFixes: #1\n  and #2\nSigned-off-by: b <b@b>";
+        let msg = CommitMessage::from(text);
+
+        assert_eq!(msg.body, Some(String::from("\nbar")));
+        assert_eq!(
+            msg.trailers,
+            vec![
+                CommitTrailer {
+                    key: String::from("Co-authored-by"),
+                    value: String::from("a <a@a>"),
+                },
+                CommitTrailer {
+                    key: String::from("Fixes"),
+                    value: String::from("#1 and #2"),
+                },
+                CommitTrailer {
+                    key: String::from("Signed-off-by"),
+                    value: String::from("b <b@b>"),
+                },
+            ]
+        );
+
+        let msg =
+            CommitMessage::from("foo\n\nSigned-off-by: b <b@b>");
+        assert_eq!(msg.body, None);
+        assert_eq!(msg.trailers.len(), 1);
+        assert_eq!(
+            msg.combine(),
+            String::from("foo\n\nSigned-off-by: b <b@b>")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_msg_no_trailers() -> Result<()> {
+        // not separated from the subject
+        let msg = CommitMessage::from("foo\nFixes: #1");
+        assert!(msg.trailers.is_empty());
+        assert_eq!(msg.body, Some(String::from("Fixes: #1")));
+
+        // last paragraph is not made of trailers only
+        let msg =
+            CommitMessage::from("foo\n\nFixes: #1\nsome more text");
+        assert!(msg.trailers.is_empty());
+
+        // spaces are not allowed in the key
+        let msg = CommitMessage::from("foo\n\nnote that: this");
+        assert!(msg.trailers.is_empty());
+
+        let msg = CommitMessage::from("foo\n\nhttps://example.com");
+        assert!(msg.trailers.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_msg_trailers_combine() -> Result<()> {
+        // messages are trimmed by `get_message`
+        let msgs = [
+            "foo\n\nSigned-off-by: b <b@b>",
+            "foo\n\nbar\n\nCo-authored-by: a <a@a>\nFixes: #1\n  and #2",
+            "foo\nbar\n\n\nFixes:   #1\n\tand #2\n\nnot a trailer: x",
+            "foo\n\nbar\n\n\nKey: value\nOther-key:  spaced  ",
+        ];
+
+        for msg in &msgs {
+            assert_eq!(&CommitMessage::from(msg).combine(), msg);
+        }
+
+        Ok(())
+    }
 }
@@ -1,9 +1,57 @@
-use super::{stash::is_stash_commit, utils::repo, CommitId};
+use super::{
+    combined_diff::get_combined_diff,
+    diff::{DiffLineType, FileDiff},
+    stash::is_stash_commit,
+    utils::repo,
+    CommitId,
+};
 use crate::{
     error::Error, error::Result, StatusItem, StatusItemType,
 };
-use git2::{Diff, DiffDelta, DiffOptions, Repository};
+use git2::{Diff, DiffDelta, DiffOptions, Patch, Repository};
 use scopetime::scope_time;
+use std::collections::HashMap;
+
+/// which parent(s) a commit gets diffed against
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum DiffParent {
+    /// 0-based index into the parents of the commit
+    Nth(usize),
+    /// all parents at once, only lists files that differ from
+    /// every parent (like `git diff --cc`)
+    Combined,
+}
+
+impl Default for DiffParent {
+    fn default() -> Self {
+        Self::Nth(0)
+    }
+}
+
+/// number of added and removed lines
+#[derive(Copy, Clone, Default, Hash, PartialEq, Eq, Debug)]
+pub struct DiffStat {
+    ///
+    pub insertions: usize,
+    ///
+    pub deletions: usize,
+}
+
+impl DiffStat {
+    fn add(&mut self, other: Self) {
+        self.insertions += other.insertions;
+        self.deletions += other.deletions;
+    }
+}
+
+/// per file line stats of a commit (like `git show --stat`)
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct CommitStats {
+    /// keyed by path
+    pub files: HashMap<String, DiffStat>,
+    /// sum over all files
+    pub total: DiffStat,
+}
 
 /// get all files that are part of a commit
 pub fn get_commit_files(
@@ -16,6 +64,153 @@ pub fn get_commit_files(
 
     let diff = get_commit_diff(&repo, id, None)?;
 
+    diff_to_status_items(&diff)
+}
+
+/// files and line stats of a commit diffed against `parent`,
+/// `DiffParent::Combined` lists the files a merge changed beyond
+/// taking one parent's side (like `git diff --cc`), its types are
+/// the ones against the first parent
+pub fn get_commit_files_stats(
+    repo_path: &str,
+    id: CommitId,
+    parent: DiffParent,
+) -> Result<(Vec<StatusItem>, CommitStats)> {
+    scope_time!("get_commit_files_stats");
+
+    let repo = repo(repo_path)?;
+
+    match parent {
+        DiffParent::Nth(0) => {
+            diff_to_files_stats(&get_commit_diff(&repo, id, None)?)
+        }
+        DiffParent::Nth(n) => {
+            let parent = commit_parent(&repo, id, n)?;
+            diff_to_files_stats(&get_compare_commits_diff(
+                &repo,
+                (parent, id),
+                None,
+            )?)
+        }
+        DiffParent::Combined => {
+            let commit = repo.find_commit(id.into())?;
+
+            let mut files: Option<Vec<StatusItem>> = None;
+            for parent in commit.parent_ids() {
+                let diff = get_compare_commits_diff(
+                    &repo,
+                    (parent.into(), id),
+                    None,
+                )?;
+                let changed = diff_to_status_items(&diff)?;
+
+                files = Some(match files {
+                    None => changed,
+                    Some(files) => files
+                        .into_iter()
+                        .filter(|item| {
+                            changed
+                                .iter()
+                                .any(|f| f.path == item.path)
+                        })
+                        .collect(),
+                });
+            }
+
+            let mut res = Vec::new();
+            let mut stats = CommitStats::default();
+            for item in files.unwrap_or_default() {
+                if let Some(diff) =
+                    get_combined_diff(&repo, id, &item.path)?
+                {
+                    let stat = file_diff_stat(&diff);
+                    stats.total.add(stat);
+                    stats.files.insert(item.path.clone(), stat);
+                    res.push(item);
+                }
+            }
+
+            Ok((res, stats))
+        }
+    }
+}
+
+/// id of the `n`th (0-based) parent of commit `id`
+pub(crate) fn commit_parent(
+    repo: &Repository,
+    id: CommitId,
+    n: usize,
+) -> Result<CommitId> {
+    let commit = repo.find_commit(id.into())?;
+
+    commit.parent_id(n).map(CommitId::new).map_err(|_| {
+        Error::Generic(format!("commit has no parent {}", n + 1))
+    })
+}
+
+/// files and line stats of the diff between the trees of two
+/// commits
+pub fn get_compare_commits_files_stats(
+    repo_path: &str,
+    ids: (CommitId, CommitId),
+) -> Result<(Vec<StatusItem>, CommitStats)> {
+    scope_time!("get_compare_commits_files_stats");
+
+    let repo = repo(repo_path)?;
+
+    let diff = get_compare_commits_diff(&repo, ids, None)?;
+
+    diff_to_files_stats(&diff)
+}
+
+fn diff_to_files_stats(
+    diff: &Diff,
+) -> Result<(Vec<StatusItem>, CommitStats)> {
+    let mut files = Vec::with_capacity(diff.deltas().len());
+    let mut stats = CommitStats::default();
+
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = delta
+            .new_file()
+            .path()
+            .map(|p| p.to_str().unwrap_or("").to_string())
+            .unwrap_or_default();
+
+        if let Some(patch) = Patch::from_diff(diff, idx)? {
+            let (_, insertions, deletions) = patch.line_stats()?;
+            let stat = DiffStat {
+                insertions,
+                deletions,
+            };
+
+            stats.total.add(stat);
+            stats.files.entry(path.clone()).or_default().add(stat);
+        }
+
+        files.push(StatusItem {
+            path,
+            status: StatusItemType::from(delta.status()),
+        });
+    }
+
+    Ok((files, stats))
+}
+
+fn file_diff_stat(diff: &FileDiff) -> DiffStat {
+    let mut stat = DiffStat::default();
+
+    for line in diff.hunks.iter().flat_map(|hunk| &hunk.lines) {
+        match line.line_type {
+            DiffLineType::Add => stat.insertions += 1,
+            DiffLineType::Delete => stat.deletions += 1,
+            _ => (),
+        }
+    }
+
+    stat
+}
+
+fn diff_to_status_items(diff: &Diff) -> Result<Vec<StatusItem>> {
     let mut res = Vec::new();
 
     diff.foreach(
@@ -38,6 +233,32 @@ pub fn get_commit_files(
     Ok(res)
 }
 
+/// diff from the tree of `ids.0` to the tree of `ids.1`
+pub(crate) fn get_compare_commits_diff(
+    repo: &Repository,
+    ids: (CommitId, CommitId),
+    pathspec: Option<String>,
+) -> Result<Diff<'_>> {
+    // scope_time!("get_compare_commits_diff");
+
+    let old_tree = repo.find_commit(ids.0.into())?.tree()?;
+    let new_tree = repo.find_commit(ids.1.into())?.tree()?;
+
+    let mut opts = DiffOptions::new();
+    if let Some(p) = &pathspec {
+        opts.pathspec(p.clone());
+    }
+    opts.show_binary(true);
+
+    let diff = repo.diff_tree_to_tree(
+        Some(&old_tree),
+        Some(&new_tree),
+        Some(&mut opts),
+    )?;
+
+    Ok(diff)
+}
+
 ///
 pub(crate) fn get_commit_diff(
     repo: &Repository,
@@ -89,12 +310,19 @@ pub(crate) fn get_commit_diff(
 
 #[cfg(test)]
 mod tests {
-    use super::get_commit_files;
+    use super::{
+        get_commit_files, get_commit_files_stats,
+        get_compare_commits_files_stats, DiffParent, DiffStat,
+    };
     use crate::{
         error::Result,
         sync::{
-            commit, stage_add_file, stash_save,
-            tests::{get_statuses, repo_init},
+            checkout_branch, commit, create_branch, stage_add_file,
+            stash_save,
+            tests::{
+                get_statuses, merge_commit, repo_init,
+                repo_init_empty, write_commit_file,
+            },
         },
         StatusItemType,
     };
@@ -170,4 +398,138 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_compare_commits() -> Result<()> {
+        let (_td, repo) = repo_init_empty()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        write_commit_file(&repo, "b", "b", "c2");
+        let c3 = write_commit_file(&repo, "a", "c", "c3");
+
+        let (files, _) =
+            get_compare_commits_files_stats(repo_path, (c1, c3))?;
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "a");
+        assert_eq!(files[0].status, StatusItemType::Modified);
+        assert_eq!(files[1].path, "b");
+        assert_eq!(files[1].status, StatusItemType::New);
+
+        let (files, _) =
+            get_compare_commits_files_stats(repo_path, (c3, c1))?;
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1].status, StatusItemType::Deleted);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats() -> Result<()> {
+        let (_td, repo) = repo_init_empty()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "1\n2\n3\n", "c1");
+        write_commit_file(&repo, "b", "1\n", "c2");
+        let c3 = write_commit_file(&repo, "a", "1\nx\n", "c3");
+
+        let (_, stats) = get_commit_files_stats(
+            repo_path,
+            c3,
+            DiffParent::default(),
+        )?;
+
+        assert_eq!(stats.files.len(), 1);
+        assert_eq!(
+            stats.files["a"],
+            DiffStat {
+                insertions: 1,
+                deletions: 2,
+            }
+        );
+        assert_eq!(stats.total, stats.files["a"]);
+
+        let (_, stats) =
+            get_compare_commits_files_stats(repo_path, (c1, c3))?;
+
+        assert_eq!(stats.files.len(), 2);
+        assert_eq!(
+            stats.total,
+            DiffStat {
+                insertions: 2,
+                deletions: 2,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_parents() -> Result<()> {
+        let (_td, repo) = repo_init_empty()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature")?;
+        let c2 = write_commit_file(&repo, "b", "b", "c2");
+        checkout_branch(repo_path, "refs/heads/master")?;
+        write_commit_file(&repo, "c", "c", "c3");
+
+        // merge that also changes `a` on top of both sides
+        let merge = merge_commit(&repo, c2, &[("a", "evil")]);
+
+        let (first, _) = get_commit_files_stats(
+            repo_path,
+            merge,
+            DiffParent::Nth(0),
+        )?;
+        assert_eq!(
+            first.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        let (second, _) = get_commit_files_stats(
+            repo_path,
+            merge,
+            DiffParent::Nth(1),
+        )?;
+        assert_eq!(
+            second
+                .iter()
+                .map(|f| f.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "c"]
+        );
+
+        let (combined, stats) = get_commit_files_stats(
+            repo_path,
+            merge,
+            DiffParent::Combined,
+        )?;
+        assert_eq!(combined.len(), 1);
+        assert_eq!(combined[0].path, "a");
+
+        assert!(get_commit_files_stats(
+            repo_path,
+            merge,
+            DiffParent::Nth(2)
+        )
+        .is_err());
+
+        assert_eq!(stats.files.len(), 1);
+        assert_eq!(
+            stats.total,
+            DiffStat {
+                insertions: 1,
+                deletions: 1,
+            }
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,293 @@
+//! lane layout for drawing the commit graph (like `git log --graph`)
+
+use super::CommitId;
+
+/// connections of a single lane in a single row of the graph
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GraphCell {
+    /// the commit of this row sits in this lane
+    pub commit: bool,
+    /// lane connects to the row above
+    pub up: bool,
+    /// lane connects to the row below
+    pub down: bool,
+    /// horizontal connection to the lane on the left
+    pub left: bool,
+    /// horizontal connection to the lane on the right
+    pub right: bool,
+}
+
+/// graph cells of one commit row, one per lane
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GraphRow {
+    ///
+    pub cells: Vec<GraphCell>,
+}
+
+impl GraphRow {
+    /// index of the lane the commit sits in
+    pub fn commit_lane(&self) -> Option<usize> {
+        self.cells.iter().position(|c| c.commit)
+    }
+}
+
+/// incrementally lays out commits into lanes.
+/// commits have to be added in the order they are shown (children first)
+#[derive(Default)]
+pub struct CommitGraph {
+    /// commit each lane is waiting for
+    lanes: Vec<Option<CommitId>>,
+    rows: Vec<GraphRow>,
+}
+
+impl CommitGraph {
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    ///
+    pub fn clear(&mut self) {
+        self.lanes.clear();
+        self.rows.clear();
+    }
+
+    ///
+    pub fn get_slice(
+        &self,
+        start_index: usize,
+        amount: usize,
+    ) -> Vec<GraphRow> {
+        let min = start_index.min(self.rows.len());
+        let max = min.saturating_add(amount).min(self.rows.len());
+        self.rows[min..max].to_vec()
+    }
+
+    /// adds the next commit row using the commits parent links
+    pub fn add(&mut self, id: CommitId, parents: &[CommitId]) {
+        let lanes_before = self.lanes.clone();
+
+        let children: Vec<usize> = lanes_before
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, lane)| {
+                if *lane == Some(id) {
+                    Some(idx)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let commit_lane = children
+            .first()
+            .copied()
+            .unwrap_or_else(|| self.free_lane(&lanes_before));
+
+        if commit_lane >= self.lanes.len() {
+            self.lanes.resize(commit_lane + 1, None);
+        }
+
+        let mut cells =
+            vec![GraphCell::default(); lanes_before.len()];
+
+        for (idx, lane) in lanes_before.iter().enumerate() {
+            if lane.is_some() {
+                cells[idx].up = true;
+                cells[idx].down = true;
+            }
+        }
+
+        // other children of this commit end in it
+        for idx in children.iter().skip(1) {
+            cells[*idx].down = false;
+            self.lanes[*idx] = None;
+            Self::connect(&mut cells, commit_lane, *idx);
+        }
+
+        self.lanes[commit_lane] = parents.first().copied();
+
+        Self::ensure_cells(&mut cells, commit_lane);
+        cells[commit_lane] = GraphCell {
+            commit: true,
+            up: !children.is_empty(),
+            down: !parents.is_empty(),
+            ..cells[commit_lane]
+        };
+
+        // merge parents either join an existing lane or open a new one
+        for parent in parents.iter().skip(1) {
+            let lane = self
+                .lanes
+                .iter()
+                .position(|lane| *lane == Some(*parent))
+                .unwrap_or_else(|| {
+                    let lane = self.free_lane(&lanes_before);
+                    if lane >= self.lanes.len() {
+                        self.lanes.resize(lane + 1, None);
+                    }
+                    self.lanes[lane] = Some(*parent);
+                    lane
+                });
+
+            if lane == commit_lane {
+                continue;
+            }
+
+            Self::ensure_cells(&mut cells, lane);
+            cells[lane].down = true;
+            Self::connect(&mut cells, commit_lane, lane);
+        }
+
+        while self.lanes.last() == Some(&None) {
+            self.lanes.pop();
+        }
+
+        self.rows.push(GraphRow { cells });
+    }
+
+    /// first lane that is free before and after this row
+    fn free_lane(&self, lanes_before: &[Option<CommitId>]) -> usize {
+        self.lanes
+            .iter()
+            .enumerate()
+            .position(|(idx, lane)| {
+                lane.is_none()
+                    && lanes_before
+                        .get(idx)
+                        .map_or(true, Option::is_none)
+            })
+            .unwrap_or_else(|| {
+                self.lanes.len().max(lanes_before.len())
+            })
+    }
+
+    fn ensure_cells(cells: &mut Vec<GraphCell>, idx: usize) {
+        if idx >= cells.len() {
+            cells.resize(idx + 1, GraphCell::default());
+        }
+    }
+
+    fn connect(cells: &mut Vec<GraphCell>, a: usize, b: usize) {
+        let (min, max) = if a < b { (a, b) } else { (b, a) };
+
+        Self::ensure_cells(cells, max);
+
+        cells[min].right = true;
+        cells[max].left = true;
+        for cell in cells.iter_mut().take(max).skip(min + 1) {
+            cell.left = true;
+            cell.right = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Oid;
+
+    fn id(n: u8) -> CommitId {
+        CommitId::new(Oid::from_bytes(&[n; 20]).unwrap())
+    }
+
+    fn row_str(row: &GraphRow) -> String {
+        row.cells
+            .iter()
+            .map(|c| {
+                if c.commit {
+                    '*'
+                } else {
+                    match (c.up, c.down, c.left || c.right) {
+                        (true, true, false) => '|',
+                        (false, true, true) => '\\',
+                        (true, false, true) => '/',
+                        (true, true, true) => '+',
+                        (false, false, true) => '-',
+                        _ => ' ',
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn rows(graph: &CommitGraph) -> Vec<String> {
+        graph
+            .get_slice(0, graph.len())
+            .iter()
+            .map(row_str)
+            .collect()
+    }
+
+    #[test]
+    fn test_linear() {
+        let mut graph = CommitGraph::new();
+        graph.add(id(3), &[id(2)]);
+        graph.add(id(2), &[id(1)]);
+        graph.add(id(1), &[]);
+
+        assert_eq!(rows(&graph), vec!["*", "*", "*"]);
+        assert!(graph.lanes.is_empty());
+    }
+
+    #[test]
+    fn test_merge_and_fork() {
+        // 4 merges 3 into 2, both forked from 1
+        let mut graph = CommitGraph::new();
+        graph.add(id(4), &[id(2), id(3)]);
+        graph.add(id(3), &[id(1)]);
+        graph.add(id(2), &[id(1)]);
+        graph.add(id(1), &[]);
+
+        assert_eq!(rows(&graph), vec!["*\\", "|*", "*|", "*/"]);
+
+        let merge_row = &graph.get_slice(0, 1)[0];
+        assert!(merge_row.cells[0].right);
+        assert!(merge_row.cells[1].left);
+        assert_eq!(merge_row.commit_lane(), Some(0));
+    }
+
+    #[test]
+    fn test_unrelated_heads() {
+        let mut graph = CommitGraph::new();
+        graph.add(id(2), &[id(1)]);
+        graph.add(id(5), &[id(4)]);
+        graph.add(id(4), &[]);
+        graph.add(id(1), &[]);
+
+        assert_eq!(rows(&graph), vec!["*", "|*", "|*", "*"]);
+    }
+
+    #[test]
+    fn test_lane_not_reused_in_same_row() {
+        // 3 has two children and is a merge of 1 and 2
+        let mut graph = CommitGraph::new();
+        graph.add(id(10), &[id(3)]);
+        graph.add(id(11), &[id(3)]);
+        graph.add(id(3), &[id(1), id(2)]);
+        graph.add(id(2), &[]);
+        graph.add(id(1), &[]);
+
+        assert_eq!(rows(&graph), vec!["*", "|*", "*/\\", "| *", "*"]);
+    }
+
+    #[test]
+    fn test_slice() {
+        let mut graph = CommitGraph::new();
+        graph.add(id(2), &[id(1)]);
+        graph.add(id(1), &[]);
+
+        assert_eq!(graph.get_slice(1, 10).len(), 1);
+        assert_eq!(graph.get_slice(5, 10).len(), 0);
+    }
+}
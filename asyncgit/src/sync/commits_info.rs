@@ -52,8 +52,10 @@ impl From<Oid> for CommitId {
 pub struct CommitInfo {
     ///
     pub message: String,
-    ///
+    /// committer time in secs since Unix epoch
     pub time: i64,
+    /// author time in secs since Unix epoch
+    pub author_time: i64,
     ///
     pub author: String,
     ///
@@ -88,6 +90,7 @@ pub fn get_commits_info(
                 message,
                 author,
                 time: c.time().seconds(),
+                author_time: c.author().when().seconds(),
                 id: CommitId(c.id()),
             }
         })
@@ -1,7 +1,11 @@
 //! sync git api for fetching a diff
 
 use super::{
-    commit_files::get_commit_diff,
+    combined_diff::get_combined_diff,
+    commit_files::{
+        commit_parent, get_commit_diff, get_compare_commits_diff,
+        DiffParent,
+    },
     utils::{self, get_head_repo, work_dir},
     CommitId,
 };
@@ -11,7 +15,7 @@ use git2::{
     Repository,
 };
 use scopetime::scope_time;
-use std::{cell::RefCell, fs, path::Path, rc::Rc};
+use std::{cell::RefCell, fs, ops::Range, path::Path, rc::Rc};
 
 /// type of diff of a single line
 #[derive(Copy, Clone, PartialEq, Hash, Debug)]
@@ -41,6 +45,9 @@ pub struct DiffLine {
     pub line_type: DiffLineType,
     ///
     pub position: DiffLinePosition,
+    /// byte ranges of `content` that differ from the paired line of
+    /// the other side (see `mark_word_changes`)
+    pub changes: Vec<Range<usize>>,
 }
 
 ///
@@ -186,6 +193,56 @@ pub fn get_diff_commit(
     raw_diff_to_file_diff(&diff, work_dir)
 }
 
+/// returns diff of a specific file inside a commit against
+/// `parent`. the combined diff is empty if the merge took every
+/// change of the file from one of its parents
+pub fn get_diff_commit_parent(
+    repo_path: &str,
+    id: CommitId,
+    parent: DiffParent,
+    p: String,
+) -> Result<FileDiff> {
+    scope_time!("get_diff_commit_parent");
+
+    let repo = utils::repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+
+    match parent {
+        DiffParent::Nth(0) => {
+            let diff = get_commit_diff(&repo, id, Some(p))?;
+            raw_diff_to_file_diff(&diff, work_dir)
+        }
+        DiffParent::Nth(n) => {
+            let parent = commit_parent(&repo, id, n)?;
+            let diff = get_compare_commits_diff(
+                &repo,
+                (parent, id),
+                Some(p),
+            )?;
+            raw_diff_to_file_diff(&diff, work_dir)
+        }
+        DiffParent::Combined => {
+            Ok(get_combined_diff(&repo, id, &p)?.unwrap_or_default())
+        }
+    }
+}
+
+/// diff of file `p` between the trees of two commits, see
+/// `get_compare_commits_diff`
+pub fn get_diff_commits(
+    repo_path: &str,
+    ids: (CommitId, CommitId),
+    p: String,
+) -> Result<FileDiff> {
+    scope_time!("get_diff_commits");
+
+    let repo = utils::repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+    let diff = get_compare_commits_diff(&repo, ids, Some(p))?;
+
+    raw_diff_to_file_diff(&diff, work_dir)
+}
+
 ///
 fn raw_diff_to_file_diff<'a>(
     diff: &'a Diff,
@@ -200,11 +257,13 @@ fn raw_diff_to_file_diff<'a>(
         let adder = move |header: &HunkHeader,
                           lines: &Vec<DiffLine>| {
             let mut res = res_cell.borrow_mut();
+            let mut lines = lines.clone();
+            mark_word_changes(&mut lines);
+            res.lines += lines.len();
             res.hunks.push(Hunk {
                 header_hash: hash(header),
-                lines: lines.clone(),
+                lines,
             });
-            res.lines += lines.len();
         };
 
         let res_cell = Rc::clone(&res);
@@ -245,6 +304,7 @@ fn raw_diff_to_file_diff<'a>(
                     content: String::from_utf8_lossy(line.content())
                         .to_string(),
                     line_type,
+                    changes: Vec::new(),
                 };
 
                 current_lines.push(diff_line);
@@ -324,6 +384,181 @@ fn raw_diff_to_file_diff<'a>(
     Ok(res.into_inner())
 }
 
+/// lines longer than this (in words) are not compared
+const WORD_DIFF_MAX_TOKENS: usize = 256;
+
+/// pairs each block of deleted lines with the block of added lines
+/// following it (first with first, ...) and sets the `changes` of
+/// both lines of a pair, like `git diff --word-diff` does
+pub(super) fn mark_word_changes(lines: &mut [DiffLine]) {
+    let mut idx = 0;
+    while idx < lines.len() {
+        let deleted = lines[idx..]
+            .iter()
+            .take_while(|l| l.line_type == DiffLineType::Delete)
+            .count();
+        let added = lines[idx + deleted..]
+            .iter()
+            .take_while(|l| l.line_type == DiffLineType::Add)
+            .count();
+
+        if deleted == 0 || added == 0 {
+            idx += deleted.max(1);
+            continue;
+        }
+
+        for pair in 0..deleted.min(added) {
+            let (old, new) = (idx + pair, idx + deleted + pair);
+
+            if let Some((old_changes, new_changes)) =
+                word_diff(&lines[old].content, &lines[new].content)
+            {
+                lines[old].changes = old_changes;
+                lines[new].changes = new_changes;
+            }
+        }
+
+        idx += deleted + added;
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum CharClass {
+    Word,
+    Space,
+    Other,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if c.is_alphanumeric() || c == '_' {
+            Self::Word
+        } else if c.is_whitespace() {
+            Self::Space
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// splits into words, whitespace runs and single other characters
+fn tokenize(s: &str) -> Vec<Range<usize>> {
+    let mut res: Vec<Range<usize>> = Vec::new();
+    let mut last_class = None;
+
+    for (idx, c) in s.char_indices() {
+        let class = CharClass::of(c);
+        let end = idx + c.len_utf8();
+
+        match res.last_mut() {
+            Some(last)
+                if class != CharClass::Other
+                    && last_class == Some(class) =>
+            {
+                last.end = end;
+            }
+            _ => res.push(idx..end),
+        }
+
+        last_class = Some(class);
+    }
+
+    res
+}
+
+/// changed byte ranges of the old and the new line
+type WordChanges = (Vec<Range<usize>>, Vec<Range<usize>>);
+
+/// byte ranges of the words that differ between `old` and `new`
+/// (longest common subsequence of their words), `None` if the lines
+/// are too long or too different for the result to be useful
+fn word_diff(old: &str, new: &str) -> Option<WordChanges> {
+    let old = old.trim_end_matches(|c| c == '\n' || c == '\r');
+    let new = new.trim_end_matches(|c| c == '\n' || c == '\r');
+
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+
+    if old_tokens.len() > WORD_DIFF_MAX_TOKENS
+        || new_tokens.len() > WORD_DIFF_MAX_TOKENS
+    {
+        return None;
+    }
+
+    let old_words: Vec<&str> =
+        old_tokens.iter().map(|r| &old[r.clone()]).collect();
+    let new_words: Vec<&str> =
+        new_tokens.iter().map(|r| &new[r.clone()]).collect();
+
+    // lcs[i][j]: common words of old_words[i..] and new_words[j..]
+    let mut lcs =
+        vec![vec![0_u16; new_words.len() + 1]; old_words.len() + 1];
+    for i in (0..old_words.len()).rev() {
+        for j in (0..new_words.len()).rev() {
+            lcs[i][j] = if old_words[i] == new_words[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_common = vec![false; old_words.len()];
+    let mut new_common = vec![false; new_words.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < old_words.len() && j < new_words.len() {
+        if old_words[i] == new_words[j] {
+            old_common[i] = true;
+            new_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    let common_len: usize = old_tokens
+        .iter()
+        .zip(&old_common)
+        .filter(|(_, common)| **common)
+        .map(|(r, _)| r.len())
+        .sum();
+    let total_len = old.len().max(new.len());
+
+    // mostly rewritten lines are shown as a whole
+    if common_len * 2 < total_len {
+        return None;
+    }
+
+    Some((
+        changed_ranges(&old_tokens, &old_common),
+        changed_ranges(&new_tokens, &new_common),
+    ))
+}
+
+/// merges the adjacent tokens that are not `common`
+fn changed_ranges(
+    tokens: &[Range<usize>],
+    common: &[bool],
+) -> Vec<Range<usize>> {
+    let mut res: Vec<Range<usize>> = Vec::new();
+
+    for (token, _) in
+        tokens.iter().zip(common).filter(|(_, common)| !**common)
+    {
+        match res.last_mut() {
+            Some(last) if last.end == token.start => {
+                last.end = token.end;
+            }
+            _ => res.push(token.clone()),
+        }
+    }
+
+    res
+}
+
 fn new_file_content(path: &Path) -> Option<Vec<u8>> {
     if let Ok(meta) = fs::symlink_metadata(path) {
         if meta.file_type().is_symlink() {
@@ -344,12 +579,19 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_diff, get_diff_commit};
+    use super::{
+        get_diff, get_diff_commit, get_diff_commit_parent, word_diff,
+        DiffLineType,
+    };
     use crate::error::Result;
     use crate::sync::{
-        commit, stage_add_file,
+        checkout_branch, commit, create_branch, stage_add_file,
         status::{get_status, StatusType},
-        tests::{get_statuses, repo_init, repo_init_empty},
+        tests::{
+            get_statuses, merge_commit, repo_init, repo_init_empty,
+            write_commit_file,
+        },
+        DiffParent,
     };
     use std::{
         fs::{self, File},
@@ -357,6 +599,53 @@ mod tests {
         path::Path,
     };
 
+    #[test]
+    fn test_word_diff() {
+        assert_eq!(
+            word_diff(
+                "let x = foo(a, b);\n",
+                "let x = bar_2(a, c);\n"
+            ),
+            Some((vec![8..11, 15..16], vec![8..13, 17..18]))
+        );
+        assert_eq!(
+            word_diff("a + b", "a+b"),
+            Some((vec![1..2, 3..4], vec![]))
+        );
+        assert_eq!(word_diff("same", "same"), Some((vec![], vec![])));
+        assert_eq!(word_diff("fn a() {}", "let b = 3;"), None);
+    }
+
+    #[test]
+    fn test_word_changes_paired() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "x = 1\ny = 2\nz\n", "c1");
+        fs::write(root.join("a"), "x = 10\nw\nz\n").unwrap();
+
+        let diff =
+            get_diff(repo_path, "a".to_string(), false).unwrap();
+        let lines = &diff.hunks[0].lines;
+        let changes = |content: &str| {
+            lines
+                .iter()
+                .find(|l| {
+                    l.content == content
+                        && l.line_type != DiffLineType::None
+                })
+                .map(|l| l.changes.clone())
+                .unwrap()
+        };
+
+        assert_eq!(changes("x = 1\n"), vec![4..5]);
+        assert_eq!(changes("x = 10\n"), vec![4..6]);
+        // too different to be compared
+        assert!(changes("y = 2\n").is_empty());
+        assert!(changes("w\n").is_empty());
+    }
+
     #[test]
     fn test_untracked_subfolder() {
         let (_td, repo) = repo_init().unwrap();
@@ -585,4 +874,116 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_combined_sizes() -> Result<()> {
+        let (_td, repo) = repo_init_empty()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature")?;
+        let c2 = write_commit_file(&repo, "a", "bbbbbb", "c2");
+        checkout_branch(repo_path, "refs/heads/master")?;
+        write_commit_file(&repo, "c", "c", "c3");
+
+        let merge = merge_commit(&repo, c2, &[("a", "evil")]);
+
+        let diff = get_diff_commit_parent(
+            repo_path,
+            merge,
+            DiffParent::Combined,
+            String::from("a"),
+        )?;
+
+        // both old contents are replaced by the merge
+        assert_eq!(diff.hunks.len(), 1);
+        assert_eq!(
+            diff.hunks[0]
+                .lines
+                .iter()
+                .map(|l| (l.line_type, l.content.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (DiffLineType::Header, "@@@ +1,1 @@@\n"),
+                (DiffLineType::Delete, "a"),
+                (DiffLineType::Delete, "bbbbbb"),
+                (DiffLineType::Add, "evil"),
+            ]
+        );
+        assert_eq!(diff.sizes, (1, 4));
+        assert_eq!(diff.size_delta, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_combined_hides_taken_sides() -> Result<()> {
+        let (_td, repo) = repo_init_empty()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let lines = |changes: &[(usize, &str)]| {
+            (1..=10)
+                .map(|n| {
+                    changes
+                        .iter()
+                        .find(|(line, _)| *line == n)
+                        .map_or(n.to_string(), |(_, c)| c.to_string())
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+                + "\n"
+        };
+
+        write_commit_file(&repo, "a", &lines(&[]), "c1");
+        create_branch(repo_path, "feature")?;
+        let c2 = write_commit_file(
+            &repo,
+            "a",
+            &lines(&[(2, "two")]),
+            "c2",
+        );
+        checkout_branch(repo_path, "refs/heads/master")?;
+        write_commit_file(&repo, "a", &lines(&[(9, "nine")]), "c3");
+
+        let merged = lines(&[(2, "two"), (9, "nine")]);
+        let merge = merge_commit(&repo, c2, &[("a", &merged)]);
+
+        let diff = get_diff_commit_parent(
+            repo_path,
+            merge,
+            DiffParent::Combined,
+            String::from("a"),
+        )?;
+        assert!(diff.hunks.is_empty());
+
+        let evil = lines(&[(2, "two"), (6, "six"), (9, "nine")]);
+        let merge = merge_commit(&repo, c2, &[("a", &evil)]);
+
+        let diff = get_diff_commit_parent(
+            repo_path,
+            merge,
+            DiffParent::Combined,
+            String::from("a"),
+        )?;
+
+        assert_eq!(diff.hunks.len(), 1);
+        let hunk = &diff.hunks[0].lines;
+        assert_eq!(hunk[0].content, "@@@ +3,7 @@@\n");
+        assert_eq!(
+            hunk.iter()
+                .filter(|l| l.line_type != DiffLineType::None)
+                .map(|l| (l.line_type, l.content.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (DiffLineType::Header, "@@@ +3,7 @@@\n"),
+                (DiffLineType::Delete, "6\n"),
+                (DiffLineType::Add, "six\n"),
+            ]
+        );
+        assert_eq!(hunk.last().unwrap().content, "nine\n");
+
+        Ok(())
+    }
 }
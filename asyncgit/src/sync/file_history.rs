@@ -0,0 +1,219 @@
+//! sync git api for the history of a single file
+
+use super::{utils::repo, CommitId};
+use crate::{error::Result, StatusItemType};
+use git2::{Commit, DiffFindOptions, Oid, Repository, Sort};
+use scopetime::scope_time;
+use std::path::Path;
+
+/// a commit that changed the file
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct FileHistoryEntry {
+    ///
+    pub commit: CommitId,
+    /// path of the file inside this commit (differs when renamed)
+    pub path: String,
+    /// how the file was changed
+    pub status: StatusItemType,
+}
+
+/// walks all commits reachable from `HEAD` and returns the ones
+/// changing `path`. with `follow_renames` the walk continues with
+/// the old name once the commit that renamed the file is found.
+pub fn get_file_history(
+    repo_path: &str,
+    path: &str,
+    follow_renames: bool,
+) -> Result<Vec<FileHistoryEntry>> {
+    scope_time!("get_file_history");
+
+    let repo = repo(repo_path)?;
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    walk.push_head()?;
+
+    let mut res = Vec::new();
+    let mut path = path.to_string();
+
+    for id in walk {
+        let commit = repo.find_commit(id?)?;
+
+        if let Some(status) = file_changed(&repo, &commit, &path)? {
+            res.push(FileHistoryEntry {
+                commit: commit.id().into(),
+                path: path.clone(),
+                status,
+            });
+
+            if follow_renames && status == StatusItemType::New {
+                if let Some(old_path) =
+                    find_rename_source(&repo, &commit, &path)?
+                {
+                    if let Some(last) = res.last_mut() {
+                        last.status = StatusItemType::Renamed;
+                    }
+                    path = old_path;
+                }
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+fn entry_id(commit: &Commit, path: &str) -> Result<Option<Oid>> {
+    Ok(commit
+        .tree()?
+        .get_path(Path::new(path))
+        .ok()
+        .map(|e| e.id()))
+}
+
+/// a merge only counts as changing the file if it differs from
+/// all of its parents (meaning the merge itself resolved it)
+fn file_changed(
+    repo: &Repository,
+    commit: &Commit,
+    path: &str,
+) -> Result<Option<StatusItemType>> {
+    let current = entry_id(commit, path)?;
+
+    let mut status = None;
+    for parent_id in commit.parent_ids() {
+        let parent = repo.find_commit(parent_id)?;
+        let old = entry_id(&parent, path)?;
+
+        if old == current {
+            return Ok(None);
+        }
+
+        if status.is_none() {
+            status = Some(match (old, current) {
+                (None, _) => StatusItemType::New,
+                (_, None) => StatusItemType::Deleted,
+                _ => StatusItemType::Modified,
+            });
+        }
+    }
+
+    if commit.parent_count() == 0 {
+        return Ok(current.map(|_| StatusItemType::New));
+    }
+
+    Ok(status)
+}
+
+fn find_rename_source(
+    repo: &Repository,
+    commit: &Commit,
+    path: &str,
+) -> Result<Option<String>> {
+    if commit.parent_count() == 0 {
+        return Ok(None);
+    }
+
+    let parent_tree = commit.parent(0)?.tree()?;
+    let mut diff = repo.diff_tree_to_tree(
+        Some(&parent_tree),
+        Some(&commit.tree()?),
+        None,
+    )?;
+
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    let source = diff.deltas().find_map(|delta| {
+        if delta.status() == git2::Delta::Renamed
+            && delta.new_file().path() == Some(Path::new(path))
+        {
+            delta
+                .old_file()
+                .path()
+                .and_then(Path::to_str)
+                .map(String::from)
+        } else {
+            None
+        }
+    });
+
+    Ok(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, stage_add_all, tests::repo_init_empty,
+        tests::write_commit_file, utils::repo_write_file,
+    };
+    use std::fs;
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "foo", "a", "c1");
+        write_commit_file(&repo, "bar", "a", "c2");
+        let c3 = write_commit_file(&repo, "foo", "b", "c3");
+
+        let res = get_file_history(repo_path, "foo", false).unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].commit, c3);
+        assert_eq!(res[0].status, StatusItemType::Modified);
+        assert_eq!(res[1].commit, c1);
+        assert_eq!(res[1].status, StatusItemType::New);
+    }
+
+    #[test]
+    fn test_follow_rename() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let content = "line1\nline2\nline3\nline4\n";
+        let c1 = write_commit_file(&repo, "foo", content, "c1");
+
+        fs::remove_file(root.join("foo")).unwrap();
+        repo_write_file(&repo, "bar", content).unwrap();
+        stage_add_all(repo_path, "*").unwrap();
+        let mut index = repo.index().unwrap();
+        index.remove_path(Path::new("foo")).unwrap();
+        index.write().unwrap();
+        let c2 = commit(repo_path, "rename").unwrap();
+
+        let res = get_file_history(repo_path, "bar", false).unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].status, StatusItemType::New);
+
+        let res = get_file_history(repo_path, "bar", true).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].commit, c2);
+        assert_eq!(res[0].status, StatusItemType::Renamed);
+        assert_eq!(res[1].commit, c1);
+        assert_eq!(res[1].path, "foo");
+    }
+
+    #[test]
+    fn test_deleted() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "foo", "a", "c1");
+        write_commit_file(&repo, "bar", "a", "c2");
+
+        let mut index = repo.index().unwrap();
+        index.remove_path(Path::new("foo")).unwrap();
+        index.write().unwrap();
+        let c3 = commit(repo_path, "delete").unwrap();
+
+        let res = get_file_history(repo_path, "foo", true).unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].commit, c3);
+        assert_eq!(res[0].status, StatusItemType::Deleted);
+    }
+}
@@ -0,0 +1,404 @@
+//! sync git api for exporting commits as mbox patches
+//! (like `git format-patch`)
+
+use super::{commit_files::get_commit_diff, utils::repo, CommitId};
+use crate::error::{Error, Result};
+use chrono::{FixedOffset, TimeZone};
+use git2::{Commit, DiffStatsFormat, Repository};
+use scopetime::scope_time;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// git uses this fixed date in the `From` line of every patch
+const MBOX_FROM_DATE: &str = "Mon Sep 17 00:00:00 2001";
+/// maximum length of the subject part of a patch file name
+const FILE_NAME_SUBJECT_LENGTH: usize = 52;
+
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FormatPatchOptions {
+    /// `[PATCH n/m]` instead of `[PATCH]` in the subjects
+    pub numbered: bool,
+    /// additionally writes a `0000-cover-letter.patch` to
+    /// describe the series
+    pub cover_letter: bool,
+}
+
+/// writes one mbox formatted patch file per commit into `out_dir`
+/// (created if missing), `commits` are expected oldest first.
+/// merge commits get skipped like `git format-patch` does.
+/// returns the paths of the written files.
+pub fn format_patches(
+    repo_path: &str,
+    commits: &[CommitId],
+    out_dir: &Path,
+    options: FormatPatchOptions,
+) -> Result<Vec<PathBuf>> {
+    scope_time!("format_patches");
+
+    let repo = repo(repo_path)?;
+
+    let mut series = Vec::with_capacity(commits.len());
+    for id in commits {
+        let commit = repo.find_commit((*id).into())?;
+        if commit.parent_count() < 2 {
+            series.push(commit);
+        }
+    }
+
+    if series.is_empty() {
+        return Err(Error::Generic(String::from(
+            "no commits to export (merges are skipped)",
+        )));
+    }
+
+    fs::create_dir_all(out_dir)?;
+
+    let total = series.len();
+    let mut res = Vec::with_capacity(total + 1);
+
+    if options.cover_letter {
+        let path = out_dir.join("0000-cover-letter.patch");
+        fs::write(
+            &path,
+            format_cover_letter(&repo, &series, options.numbered)?,
+        )?;
+        res.push(path);
+    }
+
+    for (idx, commit) in series.iter().enumerate() {
+        let (patch_no, total_patches) = if options.numbered {
+            (idx + 1, total)
+        } else {
+            (1, 1)
+        };
+
+        let email =
+            format_email(&repo, commit, patch_no, total_patches)?;
+
+        let path = out_dir.join(patch_file_name(
+            idx + 1,
+            &String::from_utf8_lossy(
+                commit.summary_bytes().unwrap_or_default(),
+            ),
+        ));
+        fs::write(&path, email)?;
+        res.push(path);
+    }
+
+    Ok(res)
+}
+
+fn format_email(
+    repo: &Repository,
+    commit: &Commit,
+    patch_no: usize,
+    total_patches: usize,
+) -> Result<Vec<u8>> {
+    // `Diff::format_email` panics on these
+    let summary = commit.summary_bytes().ok_or_else(|| {
+        Error::Generic(format!(
+            "commit {} has no message",
+            commit.id()
+        ))
+    })?;
+    if !commit.message_bytes().starts_with(summary) {
+        return Err(Error::Generic(format!(
+            "commit {} message starts with blank lines",
+            commit.id()
+        )));
+    }
+
+    let mut diff = get_commit_diff(repo, commit.id().into(), None)?;
+
+    let email =
+        diff.format_email(patch_no, total_patches, commit, None)?;
+
+    Ok(email.to_vec())
+}
+
+/// `0000-cover-letter.patch` with the placeholders git uses, a
+/// shortlog and the stats of the whole series (only their sum if
+/// the commits are not one after another)
+fn format_cover_letter(
+    repo: &Repository,
+    series: &[Commit],
+    numbered: bool,
+) -> Result<String> {
+    let sig = repo.signature()?;
+    let when = sig.when();
+    let date = FixedOffset::east(when.offset_minutes() * 60)
+        .timestamp(when.seconds(), 0)
+        .to_rfc2822();
+
+    let subject_prefix = if numbered {
+        format!("[PATCH 0/{}]", series.len())
+    } else {
+        String::from("[PATCH]")
+    };
+
+    let mut res = format!(
+        "From {} {}\nFrom: {} <{}>\nDate: {}\nSubject: {} *** SUBJECT HERE ***\n\n*** BLURB HERE ***\n\n",
+        series.last().map(Commit::id).unwrap_or_else(git2::Oid::zero),
+        MBOX_FROM_DATE,
+        String::from_utf8_lossy(sig.name_bytes()),
+        String::from_utf8_lossy(sig.email_bytes()),
+        date,
+        subject_prefix,
+    );
+
+    // shortlog: subjects grouped by author in order of appearance
+    let mut authors: Vec<(String, Vec<String>)> = Vec::new();
+    for commit in series {
+        let author =
+            String::from_utf8_lossy(commit.author().name_bytes())
+                .to_string();
+        let summary = String::from_utf8_lossy(
+            commit.summary_bytes().unwrap_or_default(),
+        )
+        .to_string();
+
+        match authors.iter_mut().find(|(name, _)| name == &author) {
+            Some((_, subjects)) => subjects.push(summary),
+            None => authors.push((author, vec![summary])),
+        }
+    }
+
+    for (author, subjects) in authors {
+        res.push_str(&format!("{} ({}):\n", author, subjects.len()));
+        for subject in subjects {
+            res.push_str(&format!("  {}\n", subject));
+        }
+        res.push('\n');
+    }
+
+    // the range only covers exactly the series if each commit is
+    // on top of the one before
+    let contiguous = series.windows(2).all(|pair| {
+        pair[1].parent_ids().next() == Some(pair[0].id())
+    });
+
+    if contiguous {
+        let first = &series[0];
+        let old_tree = if first.parent_count() > 0 {
+            Some(first.parent(0)?.tree()?)
+        } else {
+            None
+        };
+        let new_tree = series[series.len() - 1].tree()?;
+        let diff = repo.diff_tree_to_tree(
+            old_tree.as_ref(),
+            Some(&new_tree),
+            None,
+        )?;
+        let stats =
+            diff.stats()?.to_buf(DiffStatsFormat::FULL, 72)?;
+
+        res.push_str(&String::from_utf8_lossy(&stats));
+    } else {
+        res.push_str(&summed_stats(repo, series)?);
+    }
+    res.push_str("\n-- \ngitui\n");
+
+    Ok(res)
+}
+
+/// summary line of the stats of each commit against its own parent
+/// added up, like git prints it below a diffstat
+fn summed_stats(
+    repo: &Repository,
+    series: &[Commit],
+) -> Result<String> {
+    let mut files = HashSet::new();
+    let mut insertions = 0;
+    let mut deletions = 0;
+
+    for commit in series {
+        let diff = get_commit_diff(repo, commit.id().into(), None)?;
+        let stats = diff.stats()?;
+        insertions += stats.insertions();
+        deletions += stats.deletions();
+
+        files.extend(
+            diff.deltas()
+                .filter_map(|delta| delta.new_file().path())
+                .map(Path::to_path_buf),
+        );
+    }
+
+    let plural = |count: usize| if count == 1 { "" } else { "s" };
+
+    let mut res = format!(
+        " {} file{} changed",
+        files.len(),
+        plural(files.len())
+    );
+    if insertions > 0 || deletions == 0 {
+        res.push_str(&format!(
+            ", {} insertion{}(+)",
+            insertions,
+            plural(insertions)
+        ));
+    }
+    if deletions > 0 || insertions == 0 {
+        res.push_str(&format!(
+            ", {} deletion{}(-)",
+            deletions,
+            plural(deletions)
+        ));
+    }
+    res.push('\n');
+
+    Ok(res)
+}
+
+/// `0001-fix-the-thing.patch` like git names patch files
+fn patch_file_name(number: usize, subject: &str) -> String {
+    let mut name = String::with_capacity(FILE_NAME_SUBJECT_LENGTH);
+    for c in subject.chars() {
+        if name.len() >= FILE_NAME_SUBJECT_LENGTH {
+            break;
+        }
+
+        if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+
+    let name = name.trim_end_matches(|c| c == '-' || c == '.');
+
+    format!("{:04}-{}.patch", number, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::{
+        merge_commit, repo_init_empty, write_commit_file,
+    };
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            patch_file_name(1, "fix: the (big) thing."),
+            "0001-fix-the-big-thing.patch"
+        );
+        assert_eq!(
+            patch_file_name(12, &"a".repeat(80)),
+            format!("0012-{}.patch", "a".repeat(52))
+        );
+    }
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a\n", "first one");
+        let c2 = write_commit_file(&repo, "a", "b\n", "second one");
+
+        let out = root.join("out");
+        let files = format_patches(
+            repo_path,
+            &[c1, c2],
+            &out,
+            FormatPatchOptions {
+                numbered: true,
+                cover_letter: true,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            files,
+            vec![
+                out.join("0000-cover-letter.patch"),
+                out.join("0001-first-one.patch"),
+                out.join("0002-second-one.patch"),
+            ]
+        );
+
+        let cover = fs::read_to_string(&files[0]).unwrap();
+        assert!(
+            cover.starts_with(&format!("From {} ", c2.to_string()))
+        );
+        assert!(cover.contains("Subject: [PATCH 0/2]"));
+        assert!(cover.contains("  first one\n  second one\n"));
+        assert!(cover.contains("1 file changed"));
+
+        let patch = fs::read_to_string(&files[2]).unwrap();
+        assert!(
+            patch.starts_with(&format!("From {} ", c2.to_string()))
+        );
+        assert!(patch.contains("Subject: [PATCH 2/2] second one"));
+        assert!(patch.contains("-a\n+b\n"));
+    }
+
+    #[test]
+    fn test_cover_letter_gaps() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a\n", "c1");
+        write_commit_file(&repo, "b", "b\n", "c2");
+        let c3 = write_commit_file(&repo, "a", "b\n", "c3");
+
+        let out = root.join("out");
+        let files = format_patches(
+            repo_path,
+            &[c1, c3],
+            &out,
+            FormatPatchOptions {
+                numbered: true,
+                cover_letter: true,
+            },
+        )
+        .unwrap();
+
+        // `b` of the skipped commit is not part of the stats
+        let cover = fs::read_to_string(&files[0]).unwrap();
+        assert!(cover.contains(
+            "\n 1 file changed, 2 insertions(+), 1 deletion(-)\n"
+        ));
+    }
+
+    #[test]
+    fn test_unnumbered_skips_merges() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "b", "b", "c2");
+        let merge = merge_commit(&repo, c1, &[]);
+
+        let out = root.join("out");
+        let files = format_patches(
+            repo_path,
+            &[c2, merge],
+            &out,
+            FormatPatchOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(files, vec![out.join("0001-c2.patch")]);
+
+        let patch = fs::read_to_string(&files[0]).unwrap();
+        assert!(patch.contains("Subject: [PATCH] c2"));
+
+        assert!(format_patches(
+            repo_path,
+            &[merge],
+            &out,
+            FormatPatchOptions::default(),
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,110 @@
+//! sync git api for searching the commit log
+
+use super::{utils::repo, CommitId};
+use crate::error::Result;
+use git2::Commit;
+use scopetime::scope_time;
+
+/// returns the indices of the commits in `ids` matching `query`.
+/// a commit matches if its message (subject or body), author name or
+/// email contains `query` (case insensitive) or its hash starts with it
+pub fn search_commits(
+    repo_path: &str,
+    ids: &[CommitId],
+    query: &str,
+) -> Result<Vec<usize>> {
+    scope_time!("search_commits");
+
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let repo = repo(repo_path)?;
+
+    let mut res = Vec::new();
+    for (idx, id) in ids.iter().enumerate() {
+        let commit = repo.find_commit((*id).into())?;
+
+        if commit_matches(&commit, &query) {
+            res.push(idx);
+        }
+    }
+
+    Ok(res)
+}
+
+/// `query` is expected to be lowercase already
+fn commit_matches(commit: &Commit, query: &str) -> bool {
+    let contains = |s: Option<&str>| {
+        s.map_or(false, |s| s.to_lowercase().contains(query))
+    };
+
+    commit.id().to_string().starts_with(query)
+        || contains(commit.message())
+        || contains(commit.author().name())
+        || contains(commit.author().email())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, stage_add_file,
+        tests::{get_commit_ids, repo_init_empty, write_commit_file},
+        utils::repo_write_file,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn test_search() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "Fix Parser");
+        let c2 = write_commit_file(&repo, "b", "b", "add feature");
+
+        repo_write_file(&repo, "c", "c").unwrap();
+        stage_add_file(repo_path, Path::new("c")).unwrap();
+        let c3 = commit(repo_path, "subject\n\nbody PARSER").unwrap();
+
+        let ids = get_commit_ids(&repo, 10);
+        assert_eq!(ids, vec![c3, c2, c1]);
+
+        let res = search_commits(repo_path, &ids, "parser").unwrap();
+        assert_eq!(res, vec![0, 2]);
+
+        let res = search_commits(repo_path, &ids, "feat").unwrap();
+        assert_eq!(res, vec![1]);
+
+        let hash = c2.to_string();
+        let res =
+            search_commits(repo_path, &ids, &hash[0..7]).unwrap();
+        assert_eq!(res, vec![1]);
+
+        let res = search_commits(repo_path, &ids, "  ").unwrap();
+        assert!(res.is_empty());
+    }
+
+    #[test]
+    fn test_search_author() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+
+        let ids = get_commit_ids(&repo, 10);
+        let author = repo.signature().unwrap();
+
+        let res =
+            search_commits(repo_path, &ids, author.email().unwrap())
+                .unwrap();
+        assert_eq!(res, vec![0]);
+
+        let res = search_commits(repo_path, &ids, "nobody@nowhere")
+            .unwrap();
+        assert!(res.is_empty());
+    }
+}
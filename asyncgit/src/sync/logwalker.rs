@@ -1,11 +1,85 @@
-use super::CommitId;
+use super::{utils::repo, CommitId};
 use crate::error::Result;
-use git2::{Repository, Revwalk};
+use git2::{Oid, Repository, Revwalk, Sort};
+use scopetime::scope_time;
+
+/// where to start walking the log from
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LogStart {
+    /// current `HEAD`
+    Head,
+    /// any branch, tag or revspec (like `origin/master~2`)
+    Rev(String),
+    /// all local and remote branches (and `HEAD`)
+    AllBranches,
+}
+
+impl Default for LogStart {
+    fn default() -> Self {
+        Self::Head
+    }
+}
+
+impl LogStart {
+    /// commits the walk starts at
+    pub fn tips(&self, repo: &Repository) -> Result<Vec<CommitId>> {
+        let mut tips: Vec<CommitId> = Vec::new();
+
+        match self {
+            Self::Head => {
+                // an unborn `HEAD` simply has no commits
+                if let Ok(head) = repo.head() {
+                    tips.push(head.peel_to_commit()?.id().into());
+                }
+            }
+            Self::Rev(spec) => {
+                tips.push(
+                    repo.revparse_single(spec)?
+                        .peel_to_commit()?
+                        .id()
+                        .into(),
+                );
+            }
+            Self::AllBranches => {
+                if let Ok(head) = repo.head() {
+                    tips.push(head.peel_to_commit()?.id().into());
+                }
+
+                for branch in repo.branches(None)? {
+                    let (branch, _) = branch?;
+                    if let Ok(commit) = branch.get().peel_to_commit()
+                    {
+                        let id = commit.id().into();
+                        if !tips.contains(&id) {
+                            tips.push(id);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(tips)
+    }
+}
+
+/// resolves the commits a walk from `start` begins at.
+/// fails if a revspec does not point to a commit
+pub fn get_log_start_tips(
+    repo_path: &str,
+    start: &LogStart,
+) -> Result<Vec<CommitId>> {
+    scope_time!("get_log_start_tips");
+
+    let repo = repo(repo_path)?;
+    start.tips(&repo)
+}
 
 ///
 pub struct LogWalker<'a> {
     repo: &'a Repository,
     revwalk: Option<Revwalk<'a>>,
+    start: LogStart,
+    sorting: Sort,
 }
 
 impl<'a> LogWalker<'a> {
@@ -14,6 +88,24 @@ impl<'a> LogWalker<'a> {
         Self {
             repo,
             revwalk: None,
+            start: LogStart::Head,
+            sorting: Sort::NONE,
+        }
+    }
+
+    /// walk from `start` instead of `HEAD`
+    pub fn with_start(self, start: LogStart) -> Self {
+        Self { start, ..self }
+    }
+
+    /// walk children before their parents as needed to draw the
+    /// commit graph. this makes libgit2 walk the whole history before
+    /// the first commit is returned: the first 3000 of 200k commits
+    /// take ~0.5s (~2s uncached) instead of ~25ms unsorted
+    pub fn with_topo_order(self) -> Self {
+        Self {
+            sorting: Sort::TOPOLOGICAL | Sort::TIME,
+            ..self
         }
     }
 
@@ -27,7 +119,14 @@ impl<'a> LogWalker<'a> {
 
         if self.revwalk.is_none() {
             let mut walk = self.repo.revwalk()?;
-            walk.push_head()?;
+            walk.set_sorting(self.sorting)?;
+            if self.start == LogStart::Head {
+                walk.push_head()?;
+            } else {
+                for tip in self.start.tips(self.repo)? {
+                    walk.push(Oid::from(tip))?;
+                }
+            }
             self.revwalk = Some(walk);
         }
 
@@ -50,8 +149,9 @@ impl<'a> LogWalker<'a> {
 mod tests {
     use super::*;
     use crate::sync::{
-        commit, get_commits_info, stage_add_file,
-        tests::repo_init_empty,
+        checkout_branch, commit, create_branch, get_commits_info,
+        stage_add_file, tag,
+        tests::{repo_init_empty, write_commit_file},
     };
     use std::{fs::File, io::Write, path::Path};
 
@@ -110,4 +210,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_start() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "foo", "a", "c1");
+        let c2 = write_commit_file(&repo, "foo", "b", "c2");
+        tag(repo_path, &c1, "v1").unwrap();
+
+        create_branch(repo_path, "other").unwrap();
+        let c3 = write_commit_file(&repo, "bar", "a", "c3");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        let read = |start: LogStart| {
+            let mut items = Vec::new();
+            LogWalker::new(&repo)
+                .with_start(start)
+                .with_topo_order()
+                .read(&mut items, 100)
+                .unwrap();
+            items
+        };
+
+        assert_eq!(read(LogStart::Head), vec![c2, c1]);
+        assert_eq!(read(LogStart::Rev("v1".into())), vec![c1]);
+        assert_eq!(
+            read(LogStart::Rev("other".into())),
+            vec![c3, c2, c1]
+        );
+        assert_eq!(read(LogStart::Rev("other~2".into())), vec![c1]);
+        assert_eq!(read(LogStart::AllBranches), vec![c3, c2, c1]);
+
+        assert!(get_log_start_tips(
+            repo_path,
+            &LogStart::Rev("nope".into())
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,188 @@
+//! `.mailmap` support, git2 does not expose libgit2's
+
+use crate::error::Result;
+use git2::Repository;
+use std::{fs, path::PathBuf};
+
+/// single line of a mailmap
+#[derive(Debug, Clone, PartialEq)]
+struct MailmapEntry {
+    proper_name: Option<String>,
+    proper_email: Option<String>,
+    commit_name: Option<String>,
+    commit_email: String,
+}
+
+/// maps the names and emails used in commits to canonical ones
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mailmap {
+    entries: Vec<MailmapEntry>,
+}
+
+impl Mailmap {
+    /// reads `.mailmap` in the working tree and the file configured
+    /// in `mailmap.file`, missing files are ignored
+    pub fn from_repo(repo: &Repository) -> Result<Self> {
+        let mut files: Vec<PathBuf> = Vec::new();
+
+        if let Some(workdir) = repo.workdir() {
+            files.push(workdir.join(".mailmap"));
+        }
+        if let Ok(file) = repo.config()?.get_path("mailmap.file") {
+            files.push(file);
+        }
+
+        let mut res = Self::default();
+        for file in files {
+            if let Ok(content) = fs::read_to_string(file) {
+                res.entries
+                    .extend(content.lines().filter_map(parse_line));
+            }
+        }
+
+        Ok(res)
+    }
+
+    ///
+    #[cfg(test)]
+    pub fn parse(content: &str) -> Self {
+        Self {
+            entries: content.lines().filter_map(parse_line).collect(),
+        }
+    }
+
+    /// canonical name and email for the ones used in a commit
+    pub fn resolve(
+        &self,
+        name: &str,
+        email: &str,
+    ) -> (String, String) {
+        // like git: later lines override single fields of earlier
+        // ones for the same commit identity
+        let lookup = |by_name: bool| {
+            self.entries
+                .iter()
+                .filter(|entry| {
+                    entry.commit_email.eq_ignore_ascii_case(email)
+                        && match &entry.commit_name {
+                            Some(commit_name) => {
+                                by_name
+                                    && commit_name
+                                        .eq_ignore_ascii_case(name)
+                            }
+                            None => !by_name,
+                        }
+                })
+                .fold(
+                    None,
+                    |res: Option<(Option<&str>, Option<&str>)>,
+                     entry| {
+                        let (proper_name, proper_email) =
+                            res.unwrap_or_default();
+                        Some((
+                            entry
+                                .proper_name
+                                .as_deref()
+                                .or(proper_name),
+                            entry
+                                .proper_email
+                                .as_deref()
+                                .or(proper_email),
+                        ))
+                    },
+                )
+        };
+
+        // an entry for name and email wins over one for the email
+        let (proper_name, proper_email) = lookup(true)
+            .or_else(|| lookup(false))
+            .unwrap_or_default();
+
+        (
+            proper_name.unwrap_or(name).to_string(),
+            proper_email.unwrap_or(email).to_string(),
+        )
+    }
+}
+
+/// splits `Name <email> rest` into name (if any), email and rest
+fn parse_name_and_email(
+    s: &str,
+) -> Option<(Option<String>, String, &str)> {
+    let start = s.find('<')?;
+    let end = start + s[start..].find('>')?;
+
+    let name = s[..start].trim();
+    let name = if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    };
+
+    Some((name, s[start + 1..end].trim().to_string(), &s[end + 1..]))
+}
+
+/// supported forms:
+/// `Proper Name <commit@email>`
+/// `<proper@email> <commit@email>`
+/// `Proper Name <proper@email> <commit@email>`
+/// `Proper Name <proper@email> Commit Name <commit@email>`
+fn parse_line(line: &str) -> Option<MailmapEntry> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return None;
+    }
+
+    let (name1, email1, rest) = parse_name_and_email(line)?;
+
+    Some(match parse_name_and_email(rest) {
+        Some((name2, email2, _)) => MailmapEntry {
+            proper_name: name1,
+            proper_email: Some(email1),
+            commit_name: name2,
+            commit_email: email2,
+        },
+        None => MailmapEntry {
+            proper_name: name1,
+            proper_email: None,
+            commit_name: None,
+            commit_email: email1,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let mailmap = Mailmap::parse(
+            "# comment\n\
+             Jane Doe <jane@old.org>\n\
+             <jane@new.org> <JANE@old.org>\n\
+             Joe <joe@new.org> joe <joe@old.org>\n\
+             Other Joe <other@new.org> <joe@old.org>\n",
+        );
+
+        assert_eq!(
+            mailmap.resolve("jd", "jane@old.org"),
+            (String::from("Jane Doe"), String::from("jane@new.org"))
+        );
+        assert_eq!(
+            mailmap.resolve("JOE", "joe@old.org"),
+            (String::from("Joe"), String::from("joe@new.org"))
+        );
+        assert_eq!(
+            mailmap.resolve("someone", "joe@old.org"),
+            (
+                String::from("Other Joe"),
+                String::from("other@new.org")
+            )
+        );
+        assert_eq!(
+            mailmap.resolve("x", "x@y.org"),
+            (String::from("x"), String::from("x@y.org"))
+        );
+    }
+}
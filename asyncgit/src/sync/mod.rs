@@ -3,20 +3,35 @@
 //TODO: remove once we have this activated on the toplevel
 #![deny(clippy::expect_used)]
 
+mod apply_patch;
+mod author_stats;
+mod blame;
 pub mod branch;
+mod cherry_pick;
+mod combined_diff;
 mod commit;
 mod commit_details;
 mod commit_files;
+mod commit_graph;
 mod commits_info;
 pub mod cred;
 pub mod diff;
+mod file_history;
+mod format_patch;
 mod hooks;
 mod hunks;
 mod ignore;
+mod log_search;
 mod logwalker;
+mod mailmap;
+mod notes;
 mod patches;
+mod rebase;
+mod reflog;
+mod refs;
 pub mod remotes;
 mod reset;
+mod revert;
 mod staging;
 mod stash;
 mod state;
@@ -24,37 +39,75 @@ pub mod status;
 mod tags;
 pub mod utils;
 
+pub use apply_patch::{
+    apply_patches, check_patch, get_patch_files, read_patch_file,
+    ApplyFailure, ApplyResult, ApplyTarget, PatchEntry,
+};
+pub use author_stats::{get_author_stats, AuthorStats};
+pub use blame::{blame_file, BlameHunk, FileBlame};
 pub use branch::{
-    branch_compare_upstream, checkout_branch, config_is_pull_rebase,
-    create_branch, delete_branch, get_branch_remote,
-    get_branches_info, merge_commit::merge_upstream_commit,
+    branch_compare_upstream, checkout_branch, checkout_commit,
+    config_is_pull_rebase, create_branch, create_branch_at,
+    delete_branch, get_branch_remote, get_branches_info,
+    is_head_detached, merge_commit::merge_upstream_commit,
     merge_ff::branch_merge_upstream_fastforward,
     merge_rebase::merge_upstream_rebase, rename::rename_branch,
     BranchCompare, BranchInfo,
 };
+pub use cherry_pick::{
+    cherry_pick, cherry_pick_abort, cherry_pick_continue,
+    cherry_pick_todo, CherryPickResult,
+};
 pub use commit::{amend, commit, tag};
 pub use commit_details::{
     get_commit_details, CommitDetails, CommitMessage,
+    CommitSignature, CommitTrailer,
+};
+pub use commit_files::{
+    get_commit_files, get_commit_files_stats,
+    get_compare_commits_files_stats, CommitStats, DiffParent,
+    DiffStat,
 };
-pub use commit_files::get_commit_files;
+pub use commit_graph::{CommitGraph, GraphCell, GraphRow};
 pub use commits_info::{get_commits_info, CommitId, CommitInfo};
 pub use diff::get_diff_commit;
+pub use file_history::{get_file_history, FileHistoryEntry};
+pub use format_patch::{format_patches, FormatPatchOptions};
 pub use hooks::{
     hooks_commit_msg, hooks_post_commit, hooks_pre_commit, HookResult,
 };
 pub use hunks::{reset_hunk, stage_hunk, unstage_hunk};
 pub use ignore::add_to_ignore;
-pub use logwalker::LogWalker;
+pub use log_search::search_commits;
+pub use logwalker::{get_log_start_tips, LogStart, LogWalker};
+pub use notes::{
+    get_commit_notes, get_note, get_notes, get_notes_ref, set_note,
+    CommitNote, Notes,
+};
+pub use rebase::{
+    get_rebase_commits, rebase_interactive, rebase_interactive_abort,
+    rebase_interactive_continue, rebase_interactive_skip,
+    rebase_interactive_todo, validate_rebase_todo, RebaseAction,
+    RebaseEntry,
+};
+pub use reflog::{get_reflog, get_reflog_refs, ReflogEntry};
+pub use refs::{get_commit_refs, CommitRef, CommitRefs, Refs};
 pub use remotes::{
     get_default_remote, get_remotes, push::AsyncProgress,
     tags::PushTagsProgress,
 };
-pub use reset::{reset_stage, reset_workdir};
+pub use reset::{
+    reset_loss, reset_repo, reset_stage, reset_workdir, ResetLoss,
+    ResetMode,
+};
+pub use revert::revert;
 pub use staging::{discard_lines, stage_lines};
 pub use stash::{
     get_stashes, stash_apply, stash_drop, stash_pop, stash_save,
 };
-pub use state::{repo_state, RepoState};
+pub use state::{
+    abort_pending_state, merge_msg, repo_state, RepoState,
+};
 pub use tags::{get_tags, CommitTags, Tags};
 pub use utils::{
     get_head, get_head_tuple, is_bare_repo, is_repo, stage_add_all,
@@ -70,7 +123,7 @@ mod tests {
         CommitId, LogWalker,
     };
     use crate::error::Result;
-    use git2::Repository;
+    use git2::{build::CheckoutBuilder, Repository};
     use std::{path::Path, process::Command};
     use tempfile::TempDir;
 
@@ -115,6 +168,47 @@ mod tests {
             .unwrap()
     }
 
+    /// commit merging `other` into `HEAD` and check it out, the
+    /// merge itself changes the content of the (existing) files in
+    /// `overwrite`
+    pub fn merge_commit(
+        repo: &Repository,
+        other: CommitId,
+        overwrite: &[(&str, &str)],
+    ) -> CommitId {
+        let sig = repo.signature().unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let other = repo.find_commit(other.into()).unwrap();
+
+        let mut index =
+            repo.merge_commits(&head, &other, None).unwrap();
+        for (file, content) in overwrite {
+            let mut entry =
+                index.get_path(Path::new(file), 0).unwrap();
+            entry.id = repo.blob(content.as_bytes()).unwrap();
+            entry.file_size = content.len() as u32;
+            index.add(&entry).unwrap();
+        }
+        let tree = repo
+            .find_tree(index.write_tree_to(repo).unwrap())
+            .unwrap();
+
+        let id = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "merge",
+                &tree,
+                &[&head, &other],
+            )
+            .unwrap();
+        repo.checkout_head(Some(CheckoutBuilder::new().force()))
+            .unwrap();
+
+        id.into()
+    }
+
     ///
     pub fn repo_init_empty() -> Result<(TempDir, Repository)> {
         sandbox_config_files();
@@ -0,0 +1,260 @@
+//! sync git api for `git notes`
+
+use super::{
+    commit::signature_allow_undefined_name, utils::repo, CommitId,
+};
+use crate::error::Result;
+use git2::{ErrorCode, Repository};
+use scopetime::scope_time;
+use std::collections::BTreeSet;
+
+/// all commits with a note in one of the notes refs
+pub type Notes = BTreeSet<CommitId>;
+
+/// note attached to a commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitNote {
+    /// like `refs/notes/commits`
+    pub notes_ref: String,
+    ///
+    pub message: String,
+}
+
+/// notes ref new notes are written to:
+/// `core.notesRef` or `refs/notes/commits` if unset
+pub fn get_notes_ref(repo_path: &str) -> Result<String> {
+    let repo = repo(repo_path)?;
+
+    Ok(repo.note_default_ref()?)
+}
+
+/// refs notes are shown from: the default one followed by the
+/// existing ones matching `notes.displayRef` (globs allowed)
+fn notes_refs(repo: &Repository) -> Result<Vec<String>> {
+    let mut res = vec![repo.note_default_ref()?];
+
+    let config = repo.config()?;
+    let display_refs = config.multivar("notes.displayRef", None)?;
+    for entry in &display_refs {
+        let entry = entry?;
+        let pattern = match entry.value() {
+            Some(pattern) => pattern,
+            None => continue,
+        };
+
+        for reference in repo.references_glob(pattern)? {
+            if let Some(name) = reference?.name() {
+                if !res.iter().any(|r| r == name) {
+                    res.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+/// notes of `id` in all displayed notes refs (see `notes_refs`)
+pub fn get_commit_notes(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<Vec<CommitNote>> {
+    scope_time!("get_commit_notes");
+
+    let repo = repo(repo_path)?;
+
+    let mut res = Vec::new();
+    for notes_ref in notes_refs(&repo)? {
+        match repo.find_note(Some(&notes_ref), id.into()) {
+            Ok(note) => res.push(CommitNote {
+                message: String::from_utf8_lossy(
+                    note.message_bytes(),
+                )
+                .trim_end()
+                .to_string(),
+                notes_ref,
+            }),
+            Err(e) if e.code() == ErrorCode::NotFound => (),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(res)
+}
+
+/// all commits with a note in one of the displayed notes refs
+pub fn get_notes(repo_path: &str) -> Result<Notes> {
+    scope_time!("get_notes");
+
+    let repo = repo(repo_path)?;
+
+    let mut res = Notes::new();
+    for notes_ref in notes_refs(&repo)? {
+        let notes = match repo.notes(Some(&notes_ref)) {
+            Ok(notes) => notes,
+            // no note was added yet
+            Err(e) if e.code() == ErrorCode::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        for note in notes {
+            let (_, annotated_id) = note?;
+            res.insert(annotated_id.into());
+        }
+    }
+
+    Ok(res)
+}
+
+/// note of `id` in the default notes ref
+pub fn get_note(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<Option<String>> {
+    let repo = repo(repo_path)?;
+
+    let res = match repo.find_note(None, id.into()) {
+        Ok(note) => Some(
+            String::from_utf8_lossy(note.message_bytes()).to_string(),
+        ),
+        Err(e) if e.code() == ErrorCode::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(res)
+}
+
+/// adds or replaces the note of `id` in the default notes ref,
+/// an empty `message` removes it
+pub fn set_note(
+    repo_path: &str,
+    id: CommitId,
+    message: &str,
+) -> Result<()> {
+    scope_time!("set_note");
+
+    let repo = repo(repo_path)?;
+    let signature = signature_allow_undefined_name(&repo)?;
+
+    let message = message.trim();
+    if message.is_empty() {
+        match repo.note_delete(
+            id.into(),
+            None,
+            &signature,
+            &signature,
+        ) {
+            Err(e) if e.code() != ErrorCode::NotFound => {
+                return Err(e.into())
+            }
+            _ => (),
+        }
+    } else {
+        repo.note(
+            &signature,
+            &signature,
+            None,
+            id.into(),
+            &format!("{}\n", message),
+            true,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::{repo_init_empty, write_commit_file};
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+
+        assert!(get_notes(repo_path).unwrap().is_empty());
+        assert_eq!(get_note(repo_path, c1).unwrap(), None);
+
+        set_note(repo_path, c1, "build passed").unwrap();
+        set_note(repo_path, c1, "build failed\n\ntests: 3").unwrap();
+
+        assert_eq!(
+            get_note(repo_path, c1).unwrap().as_deref(),
+            Some("build failed\n\ntests: 3\n")
+        );
+        assert_eq!(
+            get_commit_notes(repo_path, c1).unwrap(),
+            vec![CommitNote {
+                notes_ref: String::from("refs/notes/commits"),
+                message: String::from("build failed\n\ntests: 3"),
+            }]
+        );
+        assert!(get_commit_notes(repo_path, c2).unwrap().is_empty());
+        assert_eq!(
+            get_notes(repo_path)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![c1]
+        );
+
+        set_note(repo_path, c1, "").unwrap();
+        assert!(get_notes(repo_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_display_ref() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+
+        let sig = repo.signature().unwrap();
+        repo.note(
+            &sig,
+            &sig,
+            Some("refs/notes/ci"),
+            c1.into(),
+            "ok",
+            false,
+        )
+        .unwrap();
+
+        // not shown unless configured
+        assert!(get_notes(repo_path).unwrap().is_empty());
+
+        repo.config()
+            .unwrap()
+            .set_str("notes.displayRef", "refs/notes/*")
+            .unwrap();
+
+        assert_eq!(
+            get_commit_notes(repo_path, c1).unwrap(),
+            vec![CommitNote {
+                notes_ref: String::from("refs/notes/ci"),
+                message: String::from("ok"),
+            }]
+        );
+        assert_eq!(get_notes(repo_path).unwrap().len(), 1);
+
+        repo.config()
+            .unwrap()
+            .set_str("core.notesRef", "refs/notes/ci")
+            .unwrap();
+
+        assert_eq!(
+            get_notes_ref(repo_path).unwrap(),
+            "refs/notes/ci"
+        );
+        assert_eq!(
+            get_note(repo_path, c1).unwrap().as_deref(),
+            Some("ok")
+        );
+    }
+}
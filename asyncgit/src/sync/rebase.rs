@@ -0,0 +1,823 @@
+//! sync git api for interactive rebasing
+
+use super::{commit::signature_allow_undefined_name, utils::repo};
+use crate::{
+    error::{Error, Result},
+    sync::CommitId,
+};
+use git2::{
+    Oid, Repository, RepositoryState, ResetType, StatusOptions,
+};
+use scopetime::scope_time;
+use std::{fs, path::PathBuf};
+
+/// file in the git dir holding a paused interactive rebase
+const TODO_FILE: &str = "gitui-rebase-todo";
+
+/// what to do with a commit when rebasing
+#[derive(Clone, Debug, PartialEq)]
+pub enum RebaseAction {
+    /// use the commit as is
+    Pick,
+    /// use the commit with a new message
+    Reword(String),
+    /// meld into the previous commit, keeping both messages
+    Squash,
+    /// meld into the previous commit, dropping its message
+    Fixup,
+    /// remove the commit
+    Drop,
+}
+
+impl RebaseAction {
+    ///
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword(_) => "reword",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    const fn melds(&self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+/// single line of the rebase todo list
+#[derive(Clone, Debug, PartialEq)]
+pub struct RebaseEntry {
+    ///
+    pub id: CommitId,
+    ///
+    pub action: RebaseAction,
+}
+
+/// todo of a running interactive rebase, persisted while it is
+/// paused for conflicts
+#[derive(Debug)]
+struct RebaseState {
+    /// branch to update once done, `None` if `HEAD` was detached
+    head_name: Option<String>,
+    orig_head: CommitId,
+    /// commit the todo is replayed onto, it must never be melded into
+    onto: CommitId,
+    /// the first entry is the one in progress
+    todo: Vec<RebaseEntry>,
+}
+
+impl RebaseState {
+    fn path(repo: &Repository) -> PathBuf {
+        repo.path().join(TODO_FILE)
+    }
+
+    fn load(repo: &Repository) -> Result<Self> {
+        let content =
+            fs::read_to_string(Self::path(repo)).map_err(|_| {
+                Error::Generic(
+                    "no interactive rebase in progress".into(),
+                )
+            })?;
+
+        let mut lines = content.lines();
+
+        let head_name = lines
+            .next()
+            .and_then(|line| line.strip_prefix("head-name "))
+            .map(String::from)
+            .filter(|name| !name.is_empty());
+
+        let orig_head = lines
+            .next()
+            .and_then(|line| line.strip_prefix("orig-head "))
+            .ok_or_else(|| {
+                Error::Generic("invalid rebase todo file".into())
+            })?;
+        let orig_head = Oid::from_str(orig_head)?.into();
+
+        let onto = lines
+            .next()
+            .and_then(|line| line.strip_prefix("onto "))
+            .ok_or_else(|| {
+                Error::Generic("invalid rebase todo file".into())
+            })?;
+        let onto = Oid::from_str(onto)?.into();
+
+        let todo = lines
+            .map(Self::parse_entry)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            head_name,
+            orig_head,
+            onto,
+            todo,
+        })
+    }
+
+    fn parse_entry(line: &str) -> Result<RebaseEntry> {
+        let mut parts = line.splitn(3, ' ');
+
+        let (action, id) = match (parts.next(), parts.next()) {
+            (Some(action), Some(id)) => (action, id),
+            _ => {
+                return Err(Error::Generic(format!(
+                    "invalid rebase todo line: {}",
+                    line
+                )))
+            }
+        };
+
+        let action = match action {
+            "pick" => RebaseAction::Pick,
+            "reword" => RebaseAction::Reword(unescape(
+                parts.next().unwrap_or_default(),
+            )),
+            "squash" => RebaseAction::Squash,
+            "fixup" => RebaseAction::Fixup,
+            "drop" => RebaseAction::Drop,
+            _ => {
+                return Err(Error::Generic(format!(
+                    "invalid rebase action: {}",
+                    action
+                )))
+            }
+        };
+
+        Ok(RebaseEntry {
+            id: Oid::from_str(id)?.into(),
+            action,
+        })
+    }
+
+    fn save(&self, repo: &Repository) -> Result<()> {
+        let mut content = format!(
+            "head-name {}\norig-head {}\nonto {}\n",
+            self.head_name.as_deref().unwrap_or_default(),
+            self.orig_head.to_string(),
+            self.onto.to_string()
+        );
+
+        for entry in &self.todo {
+            content.push_str(entry.action.name());
+            content.push(' ');
+            content.push_str(&entry.id.to_string());
+            if let RebaseAction::Reword(msg) = &entry.action {
+                content.push(' ');
+                content.push_str(&escape(msg));
+            }
+            content.push('\n');
+        }
+
+        fs::write(Self::path(repo), content)?;
+
+        Ok(())
+    }
+
+    fn remove(repo: &Repository) -> Result<()> {
+        fs::remove_file(Self::path(repo))?;
+        Ok(())
+    }
+}
+
+fn escape(msg: &str) -> String {
+    msg.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(msg: &str) -> String {
+    let mut res = String::with_capacity(msg.len());
+    let mut chars = msg.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => res.push('\n'),
+                Some(c) => res.push(c),
+                None => (),
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
+
+/// commits on top of `base` up to `HEAD` (oldest first) that an
+/// interactive rebase onto `base` would replay
+pub fn get_rebase_commits(
+    repo_path: &str,
+    base: CommitId,
+) -> Result<Vec<CommitId>> {
+    scope_time!("get_rebase_commits");
+
+    let repo = repo(repo_path)?;
+
+    let head = repo.head()?.peel_to_commit()?.id();
+
+    if !repo.graph_descendant_of(head, base.into())? {
+        return Err(Error::Generic(
+            "base commit is not an ancestor of HEAD".into(),
+        ));
+    }
+
+    let mut walk = repo.revwalk()?;
+    walk.push(head)?;
+    walk.hide(base.into())?;
+
+    let mut commits = Vec::new();
+    for id in walk {
+        let commit = repo.find_commit(id?)?;
+        if commit.parent_count() > 1 {
+            return Err(Error::Generic(
+                "cannot rebase merge commits".into(),
+            ));
+        }
+        commits.push(commit.id().into());
+    }
+    commits.reverse();
+
+    Ok(commits)
+}
+
+/// checks that `todo` can be replayed: the first commit that is not
+/// dropped cannot be melded into the base of the rebase
+pub fn validate_rebase_todo(todo: &[RebaseEntry]) -> Result<()> {
+    if let Some(entry) =
+        todo.iter().find(|e| e.action != RebaseAction::Drop)
+    {
+        if entry.action.melds() {
+            return Err(Error::Generic(format!(
+                "cannot {} without a previous commit",
+                entry.action.name()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// replays `todo` onto `base`. stops at the first conflict and
+/// leaves the repo in the rebase state to continue, skip or abort.
+/// returns the commit that stopped with conflicts
+pub fn rebase_interactive(
+    repo_path: &str,
+    base: CommitId,
+    todo: &[RebaseEntry],
+) -> Result<Option<CommitId>> {
+    scope_time!("rebase_interactive");
+
+    let repo = repo(repo_path)?;
+
+    if repo.state() != RepositoryState::Clean {
+        return Err(Error::Generic(
+            "another operation is in progress".into(),
+        ));
+    }
+
+    if !is_worktree_clean(&repo)? {
+        return Err(Error::Generic(
+            "commit or stash your changes first".into(),
+        ));
+    }
+
+    validate_rebase_todo(todo)?;
+
+    let head = repo.head()?;
+    let state = RebaseState {
+        head_name: if head.is_branch() {
+            head.name().map(String::from)
+        } else {
+            None
+        },
+        orig_head: head.peel_to_commit()?.id().into(),
+        onto: base,
+        todo: todo.to_vec(),
+    };
+
+    let base = repo.find_commit(base.into())?;
+    repo.set_head_detached(base.id())?;
+    repo.reset(base.as_object(), ResetType::Hard, None)?;
+
+    run_todo(&repo, state)
+}
+
+/// commits the resolved entry the rebase stopped at and goes on
+pub fn rebase_interactive_continue(
+    repo_path: &str,
+) -> Result<Option<CommitId>> {
+    scope_time!("rebase_interactive_continue");
+
+    let repo = repo(repo_path)?;
+    let mut state = RebaseState::load(&repo)?;
+
+    if repo.index()?.has_conflicts() {
+        return Err(Error::Generic(
+            "resolve and stage all conflicts first".into(),
+        ));
+    }
+
+    if !state.todo.is_empty() {
+        let entry = state.todo.remove(0);
+        commit_entry(&repo, &entry, state.onto)?;
+        repo.open_rebase(None)?.finish(None)?;
+    }
+
+    run_todo(&repo, state)
+}
+
+/// drops the entry the rebase stopped at and goes on
+pub fn rebase_interactive_skip(
+    repo_path: &str,
+) -> Result<Option<CommitId>> {
+    scope_time!("rebase_interactive_skip");
+
+    let repo = repo(repo_path)?;
+    let mut state = RebaseState::load(&repo)?;
+
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), ResetType::Hard, None)?;
+    repo.cleanup_state()?;
+
+    if !state.todo.is_empty() {
+        state.todo.remove(0);
+    }
+
+    run_todo(&repo, state)
+}
+
+/// returns to the branch and commit the rebase started from
+pub fn rebase_interactive_abort(repo_path: &str) -> Result<()> {
+    scope_time!("rebase_interactive_abort");
+
+    let repo = repo(repo_path)?;
+    let state = RebaseState::load(&repo)?;
+
+    let orig_head = repo.find_commit(state.orig_head.into())?;
+
+    match &state.head_name {
+        Some(name) => repo.set_head(name)?,
+        None => repo.set_head_detached(orig_head.id())?,
+    }
+    repo.reset(orig_head.as_object(), ResetType::Hard, None)?;
+    repo.cleanup_state()?;
+
+    RebaseState::remove(&repo)
+}
+
+/// entries left to do in a paused interactive rebase, the first one
+/// is the entry that stopped
+pub fn rebase_interactive_todo(
+    repo_path: &str,
+) -> Result<Vec<RebaseEntry>> {
+    scope_time!("rebase_interactive_todo");
+
+    let repo = repo(repo_path)?;
+
+    if !RebaseState::path(&repo).exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(RebaseState::load(&repo)?.todo)
+}
+
+fn is_worktree_clean(repo: &Repository) -> Result<bool> {
+    let statuses = repo.statuses(Some(
+        StatusOptions::new()
+            .include_untracked(false)
+            .include_ignored(false),
+    ))?;
+
+    Ok(statuses.is_empty())
+}
+
+/// applies each entry onto `HEAD` using a git rebase of that single
+/// commit, so conflicts pause in the regular rebase state
+fn run_todo(
+    repo: &Repository,
+    mut state: RebaseState,
+) -> Result<Option<CommitId>> {
+    while let Some(entry) = state.todo.first().cloned() {
+        if entry.action == RebaseAction::Drop {
+            state.todo.remove(0);
+            continue;
+        }
+
+        state.save(repo)?;
+
+        let commit = repo.find_commit(entry.id.into())?;
+        let parent = commit.parent(0)?;
+        let onto = repo.head()?.peel_to_commit()?;
+
+        let mut rebase = repo.rebase(
+            Some(&repo.find_annotated_commit(commit.id())?),
+            Some(&repo.find_annotated_commit(parent.id())?),
+            Some(&repo.find_annotated_commit(onto.id())?),
+            None,
+        )?;
+
+        if let Some(op) = rebase.next() {
+            op?;
+        }
+
+        if repo.index()?.has_conflicts() {
+            return Ok(Some(entry.id));
+        }
+
+        commit_entry(repo, &entry, state.onto)?;
+        rebase.finish(None)?;
+
+        state.todo.remove(0);
+    }
+
+    let head = repo.head()?.peel_to_commit()?;
+
+    if let Some(name) = &state.head_name {
+        repo.reference(name, head.id(), true, "rebase -i (finish)")?;
+        repo.set_head(name)?;
+    }
+
+    RebaseState::remove(repo)?;
+
+    Ok(None)
+}
+
+/// commits the index according to `entry` on top of `HEAD`, refuses
+/// to meld into `onto` which is not part of the rebase
+fn commit_entry(
+    repo: &Repository,
+    entry: &RebaseEntry,
+    onto: CommitId,
+) -> Result<()> {
+    let commit = repo.find_commit(entry.id.into())?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    // all commits before turned out empty or were dropped
+    if entry.action.melds() && CommitId::from(head.id()) == onto {
+        return Err(Error::Generic(format!(
+            "cannot {} {} without a previous commit, skip or abort",
+            entry.action.name(),
+            entry.id.get_short_string()
+        )));
+    }
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+    let message = commit.message().ok_or_else(|| {
+        Error::Generic("commit message is not utf8".into())
+    })?;
+
+    match &entry.action {
+        RebaseAction::Squash | RebaseAction::Fixup => {
+            let message = if entry.action == RebaseAction::Squash {
+                let head_message = head.message().unwrap_or_default();
+                Some(format!(
+                    "{}\n\n{}",
+                    head_message.trim_end(),
+                    message
+                ))
+            } else {
+                None
+            };
+
+            head.amend(
+                Some("HEAD"),
+                None,
+                None,
+                None,
+                message.as_deref(),
+                Some(&tree),
+            )?;
+        }
+        RebaseAction::Pick | RebaseAction::Reword(_) => {
+            // nothing left to pick
+            if tree.id() == head.tree_id() {
+                return Ok(());
+            }
+
+            let message =
+                if let RebaseAction::Reword(msg) = &entry.action {
+                    msg.as_str()
+                } else {
+                    message
+                };
+
+            let committer = signature_allow_undefined_name(repo)?;
+
+            repo.commit(
+                Some("HEAD"),
+                &commit.author(),
+                &committer,
+                message,
+                &tree,
+                &[&head],
+            )?;
+        }
+        RebaseAction::Drop => (),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, get_commit_details, get_commits_info, repo_state,
+        stage_add_file,
+        tests::{repo_init_empty, write_commit_file},
+        utils::{get_head_refname, repo_write_file},
+        RepoState,
+    };
+    use std::path::Path;
+
+    fn messages(repo_path: &str, base: CommitId) -> Vec<String> {
+        let ids = get_rebase_commits(repo_path, base).unwrap();
+        get_commits_info(repo_path, &ids, 100)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.message)
+            .collect()
+    }
+
+    fn entry(id: CommitId, action: RebaseAction) -> RebaseEntry {
+        RebaseEntry { id, action }
+    }
+
+    #[test]
+    fn test_reorder_reword_drop() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "b", "b", "c2");
+        let c3 = write_commit_file(&repo, "c", "c", "c3");
+        let c4 = write_commit_file(&repo, "d", "d", "c4");
+
+        assert_eq!(
+            get_rebase_commits(repo_path, c1).unwrap(),
+            vec![c2, c3, c4]
+        );
+
+        let res = rebase_interactive(
+            repo_path,
+            c1,
+            &[
+                entry(c4, RebaseAction::Pick),
+                entry(c2, RebaseAction::Reword("new".into())),
+                entry(c3, RebaseAction::Drop),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(res, None);
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(
+            get_head_refname(&repo).unwrap(),
+            "refs/heads/master"
+        );
+        assert_eq!(messages(repo_path, c1), vec!["c4", "new"]);
+        assert!(!root.join("c").exists());
+    }
+
+    #[test]
+    fn test_squash_fixup() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "b", "b", "c2");
+        let c3 = write_commit_file(&repo, "c", "c", "c3");
+        let c4 = write_commit_file(&repo, "d", "d", "c4");
+
+        assert!(rebase_interactive(
+            repo_path,
+            c1,
+            &[entry(c2, RebaseAction::Fixup)]
+        )
+        .is_err());
+
+        rebase_interactive(
+            repo_path,
+            c1,
+            &[
+                entry(c2, RebaseAction::Pick),
+                entry(c3, RebaseAction::Squash),
+                entry(c4, RebaseAction::Fixup),
+            ],
+        )
+        .unwrap();
+
+        let ids = get_rebase_commits(repo_path, c1).unwrap();
+        assert_eq!(ids.len(), 1);
+        let msg = repo
+            .find_commit(ids[0].into())
+            .unwrap()
+            .message()
+            .unwrap()
+            .to_string();
+        assert_eq!(msg, "c2\n\nc3");
+        assert!(root.join("d").exists());
+    }
+
+    #[test]
+    fn test_leading_meld() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "b", "b", "c2");
+        let c3 = write_commit_file(&repo, "c", "c", "c3");
+
+        let todo = [
+            entry(c2, RebaseAction::Drop),
+            entry(c3, RebaseAction::Squash),
+        ];
+        assert!(validate_rebase_todo(&todo).is_err());
+        assert!(rebase_interactive(repo_path, c1, &todo).is_err());
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(messages(repo_path, c1), vec!["c2", "c3"]);
+
+        assert!(validate_rebase_todo(&[
+            entry(c2, RebaseAction::Pick),
+            entry(c3, RebaseAction::Squash),
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn test_meld_after_empty_pick() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        let c3 = write_commit_file(&repo, "a", "a", "c3");
+
+        // picking the revert `c3` first changes nothing on `c1`
+        let res = rebase_interactive(
+            repo_path,
+            c1,
+            &[
+                entry(c3, RebaseAction::Pick),
+                entry(c2, RebaseAction::Squash),
+            ],
+        );
+
+        assert!(res.is_err());
+        assert_eq!(
+            CommitId::from(repo.head().unwrap().target().unwrap()),
+            c1
+        );
+        assert_eq!(
+            repo.find_commit(c1.into()).unwrap().message(),
+            Some("c1")
+        );
+        assert_eq!(
+            rebase_interactive_todo(repo_path).unwrap(),
+            vec![entry(c2, RebaseAction::Squash)]
+        );
+
+        rebase_interactive_abort(repo_path).unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(messages(repo_path, c1), vec!["c2", "c3"]);
+    }
+
+    #[test]
+    fn test_reword_long_message() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        repo_write_file(&repo, "a", "b").unwrap();
+        stage_add_file(repo_path, Path::new("a")).unwrap();
+        let body = "a paragraph that is a lot longer than the \
+                    truncated message shown in the list of commits";
+        let msg = format!("c2\n\n{}\n\n{}\n  indented", body, body);
+        let c2 = commit(repo_path, &msg).unwrap();
+
+        // what the reword input is pre-filled with
+        let prefill = get_commit_details(repo_path, c2)
+            .unwrap()
+            .message
+            .unwrap()
+            .combine();
+        assert_eq!(prefill, msg);
+
+        let reworded = format!("{}\n\nreworded", prefill);
+        rebase_interactive(
+            repo_path,
+            c1,
+            &[entry(c2, RebaseAction::Reword(reworded.clone()))],
+        )
+        .unwrap();
+
+        let ids = get_rebase_commits(repo_path, c1).unwrap();
+        let details = get_commit_details(repo_path, ids[0]).unwrap();
+        assert_eq!(details.message.unwrap().combine(), reworded);
+    }
+
+    #[test]
+    fn test_conflict_continue() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        let c3 = write_commit_file(&repo, "a", "c", "c3");
+
+        let res = rebase_interactive(
+            repo_path,
+            c1,
+            &[
+                entry(c3, RebaseAction::Pick),
+                entry(c2, RebaseAction::Reword("c2\nbody".into())),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(res, Some(c3));
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+        assert_eq!(
+            rebase_interactive_todo(repo_path).unwrap().len(),
+            2
+        );
+        assert!(rebase_interactive_continue(repo_path).is_err());
+
+        repo_write_file(&repo, "a", "c").unwrap();
+        stage_add_file(repo_path, Path::new("a")).unwrap();
+
+        let res = rebase_interactive_continue(repo_path).unwrap();
+        assert_eq!(res, Some(c2));
+        assert_eq!(
+            rebase_interactive_todo(repo_path).unwrap(),
+            vec![entry(c2, RebaseAction::Reword("c2\nbody".into()))]
+        );
+
+        repo_write_file(&repo, "a", "b").unwrap();
+        stage_add_file(repo_path, Path::new("a")).unwrap();
+
+        assert_eq!(
+            rebase_interactive_continue(repo_path).unwrap(),
+            None
+        );
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert!(rebase_interactive_todo(repo_path)
+            .unwrap()
+            .is_empty());
+        assert_eq!(messages(repo_path, c1), vec!["c3", "c2\nbody"]);
+    }
+
+    #[test]
+    fn test_skip_abort() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        let c3 = write_commit_file(&repo, "a", "c", "c3");
+        let c4 = write_commit_file(&repo, "d", "d", "c4");
+
+        let todo = [
+            entry(c3, RebaseAction::Pick),
+            entry(c2, RebaseAction::Pick),
+            entry(c4, RebaseAction::Pick),
+        ];
+
+        assert_eq!(
+            rebase_interactive(repo_path, c1, &todo).unwrap(),
+            Some(c3)
+        );
+        rebase_interactive_abort(repo_path).unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(
+            get_head_refname(&repo).unwrap(),
+            "refs/heads/master"
+        );
+        assert_eq!(messages(repo_path, c1), vec!["c2", "c3", "c4"]);
+
+        assert_eq!(
+            rebase_interactive(repo_path, c1, &todo).unwrap(),
+            Some(c3)
+        );
+        assert_eq!(rebase_interactive_skip(repo_path).unwrap(), None);
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(messages(repo_path, c1), vec!["c2", "c4"]);
+    }
+
+    #[test]
+    fn test_escape() {
+        let msg = "a\\nb\nc\\";
+        assert_eq!(unescape(&escape(msg)), msg);
+        assert!(!escape(msg).contains('\n'));
+    }
+}
@@ -0,0 +1,126 @@
+//! sync git api for reading reflogs
+
+use super::{
+    utils::{bytes2string, repo},
+    CommitId,
+};
+use crate::error::Result;
+use git2::BranchType;
+use scopetime::scope_time;
+
+/// single entry of a reflog, `new_id` is what the ref pointed to
+/// after the change
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReflogEntry {
+    /// position in the reflog, `0` is the latest (`HEAD@{0}`)
+    pub index: usize,
+    ///
+    pub old_id: CommitId,
+    ///
+    pub new_id: CommitId,
+    /// what caused the change (e.g. `commit: msg`)
+    pub message: String,
+    /// seconds since epoch
+    pub time: i64,
+}
+
+/// names of the refs to show reflogs for: `HEAD` followed by all
+/// local branches that have a reflog
+pub fn get_reflog_refs(repo_path: &str) -> Result<Vec<String>> {
+    scope_time!("get_reflog_refs");
+
+    let repo = repo(repo_path)?;
+
+    let mut res = vec![String::from("HEAD")];
+
+    for b in repo.branches(Some(BranchType::Local))? {
+        let reference = b?.0.into_reference();
+        let name = bytes2string(reference.name_bytes())?;
+
+        if !repo.reflog(&name)?.is_empty() {
+            res.push(name);
+        }
+    }
+
+    Ok(res)
+}
+
+/// reflog of `refname`, latest entry first
+pub fn get_reflog(
+    repo_path: &str,
+    refname: &str,
+) -> Result<Vec<ReflogEntry>> {
+    scope_time!("get_reflog");
+
+    let repo = repo(repo_path)?;
+    let reflog = repo.reflog(refname)?;
+
+    let res = reflog
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| ReflogEntry {
+            index,
+            old_id: entry.id_old().into(),
+            new_id: entry.id_new().into(),
+            message: entry
+                .message()
+                .map(String::from)
+                .unwrap_or_default(),
+            time: entry.committer().when().seconds(),
+        })
+        .collect();
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch, reset_repo,
+        tests::{repo_init_empty, write_commit_file},
+        ResetMode,
+    };
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+
+        reset_repo(repo_path, c1, ResetMode::Hard).unwrap();
+
+        let reflog = get_reflog(repo_path, "HEAD").unwrap();
+
+        assert_eq!(reflog[0].index, 0);
+        assert_eq!(reflog[0].old_id, c2);
+        assert_eq!(reflog[0].new_id, c1);
+        assert!(reflog[0].message.starts_with("reset"));
+
+        // the lost commit is still reachable through the reflog
+        assert_eq!(reflog[1].new_id, c2);
+    }
+
+    #[test]
+    fn test_refs() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature").unwrap();
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        assert_eq!(
+            get_reflog_refs(repo_path).unwrap(),
+            vec![
+                String::from("HEAD"),
+                String::from("refs/heads/feature"),
+                String::from("refs/heads/master"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! sync git api for the refs decorating commits
+
+use super::{utils::repo, CommitId};
+use crate::error::Result;
+use git2::BranchType;
+use scopetime::scope_time;
+use std::collections::BTreeMap;
+
+/// a reference pointing to a commit
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CommitRef {
+    /// detached `HEAD`
+    Head,
+    /// local branch `HEAD` points to
+    HeadBranch(String),
+    ///
+    LocalBranch(String),
+    /// remote tracking branch like `origin/master`
+    RemoteBranch(String),
+}
+
+/// all refs pointing to a single commit (`HEAD` first)
+pub type CommitRefs = Vec<CommitRef>;
+/// map of commit to the refs pointing to it
+pub type Refs = BTreeMap<CommitId, CommitRefs>;
+
+/// returns `HEAD`, local and remote branches grouped by commit
+pub fn get_commit_refs(repo_path: &str) -> Result<Refs> {
+    scope_time!("get_commit_refs");
+
+    let repo = repo(repo_path)?;
+
+    let mut res = Refs::new();
+
+    let head = repo.head().ok();
+    let head_branch = head
+        .as_ref()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(String::from));
+
+    if head_branch.is_none() {
+        if let Some(id) = head.and_then(|head| head.target()) {
+            res.entry(id.into()).or_default().push(CommitRef::Head);
+        }
+    }
+
+    for b in repo.branches(None)? {
+        let (branch, branch_type) = b?;
+        let reference = branch.get();
+
+        // skip symbolic refs like `origin/HEAD`
+        let id = match reference.target() {
+            Some(id) => id,
+            None => continue,
+        };
+
+        let name = match branch.name()? {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        let commit_ref = match branch_type {
+            BranchType::Local
+                if head_branch.as_ref() == Some(&name) =>
+            {
+                CommitRef::HeadBranch(name)
+            }
+            BranchType::Local => CommitRef::LocalBranch(name),
+            BranchType::Remote => CommitRef::RemoteBranch(name),
+        };
+
+        res.entry(id.into()).or_default().push(commit_ref);
+    }
+
+    for refs in res.values_mut() {
+        refs.sort();
+    }
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch,
+        tests::{repo_init_empty, write_commit_file},
+    };
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert!(get_commit_refs(repo_path).unwrap().is_empty());
+
+        let c1 = write_commit_file(&repo, "foo", "a", "c1");
+        create_branch(repo_path, "feature").unwrap();
+        let c2 = write_commit_file(&repo, "foo", "b", "c2");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        let refs = get_commit_refs(repo_path).unwrap();
+
+        assert_eq!(
+            refs[&c1],
+            vec![CommitRef::HeadBranch(String::from("master"))]
+        );
+        assert_eq!(
+            refs[&c2],
+            vec![CommitRef::LocalBranch(String::from("feature"))]
+        );
+    }
+
+    #[test]
+    fn test_detached() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "foo", "a", "c1");
+        repo.set_head_detached(c1.into()).unwrap();
+
+        let refs = get_commit_refs(repo_path).unwrap();
+
+        assert_eq!(
+            refs[&c1],
+            vec![
+                CommitRef::Head,
+                CommitRef::LocalBranch(String::from("master"))
+            ]
+        );
+    }
+}
@@ -1,8 +1,41 @@
-use super::utils::{get_head_repo, repo};
+use super::{
+    utils::{get_head_repo, repo},
+    CommitId,
+};
 use crate::error::Result;
-use git2::{build::CheckoutBuilder, ObjectType};
+use git2::{build::CheckoutBuilder, ObjectType, StatusOptions};
 use scopetime::scope_time;
 
+/// how far resetting `HEAD` to another commit goes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ResetMode {
+    /// move `HEAD` only, changes stay staged
+    Soft,
+    /// move `HEAD` and reset the index, changes stay in the workdir
+    Mixed,
+    /// move `HEAD`, reset index and workdir
+    Hard,
+}
+
+impl From<ResetMode> for git2::ResetType {
+    fn from(mode: ResetMode) -> Self {
+        match mode {
+            ResetMode::Soft => Self::Soft,
+            ResetMode::Mixed => Self::Mixed,
+            ResetMode::Hard => Self::Hard,
+        }
+    }
+}
+
+/// what resetting `HEAD` to a commit leaves behind
+#[derive(Debug, Default, PartialEq)]
+pub struct ResetLoss {
+    /// commits no other branch or tag refers to anymore
+    pub commits: usize,
+    /// uncommitted changes to tracked files (lost by hard resets)
+    pub changes: usize,
+}
+
 ///
 pub fn reset_stage(repo_path: &str, path: &str) -> Result<()> {
     scope_time!("reset_stage");
@@ -38,17 +71,79 @@ pub fn reset_workdir(repo_path: &str, path: &str) -> Result<()> {
     Ok(())
 }
 
+/// moves `HEAD` (and the branch it points to) to `commit`
+pub fn reset_repo(
+    repo_path: &str,
+    commit: CommitId,
+    mode: ResetMode,
+) -> Result<()> {
+    scope_time!("reset_repo");
+
+    let repo = repo(repo_path)?;
+
+    let obj =
+        repo.find_object(commit.into(), Some(ObjectType::Commit))?;
+
+    repo.reset(&obj, mode.into(), None)?;
+
+    Ok(())
+}
+
+/// counts what resetting `HEAD` to `commit` would leave behind
+pub fn reset_loss(
+    repo_path: &str,
+    commit: CommitId,
+) -> Result<ResetLoss> {
+    scope_time!("reset_loss");
+
+    let repo = repo(repo_path)?;
+
+    let head = repo.head()?;
+    let head_name = head.name().map(String::from);
+
+    let mut walk = repo.revwalk()?;
+    walk.push(head.peel_to_commit()?.id())?;
+    walk.hide(commit.into())?;
+
+    // commits still reachable from other refs are not lost
+    for reference in repo.references()? {
+        let reference = reference?;
+        if reference.name().map(String::from) == head_name {
+            continue;
+        }
+        if let Ok(other) = reference.peel_to_commit() {
+            walk.hide(other.id())?;
+        }
+    }
+
+    let commits = walk.count();
+
+    let changes = repo
+        .statuses(Some(
+            StatusOptions::new()
+                .include_untracked(false)
+                .include_ignored(false),
+        ))?
+        .len();
+
+    Ok(ResetLoss { commits, changes })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{reset_stage, reset_workdir};
+    use super::{
+        reset_loss, reset_repo, reset_stage, reset_workdir,
+        ResetLoss, ResetMode,
+    };
     use crate::error::Result;
     use crate::sync::{
-        commit,
+        commit, create_branch, get_head,
         status::{get_status, StatusType},
         tests::{
-            debug_cmd_print, get_statuses, repo_init, repo_init_empty,
+            debug_cmd_print, get_statuses, repo_init,
+            repo_init_empty, write_commit_file,
         },
-        utils::{stage_add_all, stage_add_file},
+        utils::{repo_write_file, stage_add_all, stage_add_file},
     };
     use std::{
         fs::{self, File},
@@ -311,4 +406,50 @@ mod tests {
 
         assert_eq!(get_statuses(repo_path), (0, 0));
     }
+
+    #[test]
+    fn test_reset_repo_modes() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        write_commit_file(&repo, "a", "b", "c2");
+
+        reset_repo(repo_path, c1, ResetMode::Soft).unwrap();
+        assert_eq!(get_head(repo_path).unwrap(), c1);
+        assert_eq!(get_statuses(repo_path), (0, 1));
+
+        reset_repo(repo_path, c1, ResetMode::Mixed).unwrap();
+        assert_eq!(get_statuses(repo_path), (1, 0));
+
+        reset_repo(repo_path, c1, ResetMode::Hard).unwrap();
+        assert_eq!(get_statuses(repo_path), (0, 0));
+        assert_eq!(fs::read_to_string(root.join("a")).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_reset_loss() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        write_commit_file(&repo, "a", "b", "c2");
+        create_branch(repo_path, "other").unwrap();
+        write_commit_file(&repo, "a", "c", "c3");
+        write_commit_file(&repo, "a", "d", "c4");
+
+        repo_write_file(&repo, "a", "e").unwrap();
+        repo_write_file(&repo, "untracked", "e").unwrap();
+
+        // c2 is still on `master`
+        assert_eq!(
+            reset_loss(repo_path, c1).unwrap(),
+            ResetLoss {
+                commits: 2,
+                changes: 1
+            }
+        );
+    }
 }
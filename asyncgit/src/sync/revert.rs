@@ -0,0 +1,143 @@
+//! sync git api for reverting commits
+
+use super::{utils::repo, CommitId};
+use crate::error::{Error, Result};
+use git2::RevertOptions;
+use scopetime::scope_time;
+
+/// applies the inverse of `commit` to index and workdir and leaves
+/// the repo in the revert state (see `merge_msg` for the message to
+/// commit with). merge commits need the 1-based `mainline` parent
+/// to revert against.
+/// returns `true` if the revert stopped with conflicts
+pub fn revert(
+    repo_path: &str,
+    commit: CommitId,
+    mainline: Option<u32>,
+) -> Result<bool> {
+    scope_time!("revert");
+
+    let repo = repo(repo_path)?;
+
+    let commit = repo.find_commit(commit.into())?;
+
+    let mut opts = RevertOptions::new();
+    match (commit.parent_count(), mainline) {
+        (0, _) => {
+            return Err(Error::Generic(
+                "cannot revert the root commit".into(),
+            ))
+        }
+        (1, _) => (),
+        (_, None) => {
+            return Err(Error::Generic(
+                "mainline parent needed to revert a merge".into(),
+            ))
+        }
+        (_, Some(mainline)) => {
+            opts.mainline(mainline);
+        }
+    }
+
+    repo.revert(&commit, Some(&mut opts))?;
+
+    Ok(repo.index()?.has_conflicts())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        abort_pending_state, checkout_branch, commit, create_branch,
+        get_commit_details, merge_msg, repo_state, stage_add_file,
+        tests::{merge_commit, repo_init_empty, write_commit_file},
+        utils::repo_write_file,
+        RepoState,
+    };
+    use std::{fs, path::Path};
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+
+        assert!(!revert(repo_path, c2, None).unwrap());
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Revert);
+        assert_eq!(fs::read_to_string(root.join("a")).unwrap(), "a");
+
+        let msg = merge_msg(repo_path).unwrap();
+        assert!(msg.starts_with("Revert \"c2\""));
+        assert!(msg.contains(&c2.to_string()));
+
+        commit(repo_path, &msg).unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+    }
+
+    #[test]
+    fn test_conflict() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        write_commit_file(&repo, "a", "c", "c3");
+
+        assert!(revert(repo_path, c2, None).unwrap());
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Revert);
+        assert!(!merge_msg(repo_path).unwrap().contains("Conflicts"));
+
+        repo_write_file(&repo, "a", "resolved").unwrap();
+        stage_add_file(repo_path, Path::new("a")).unwrap();
+        commit(repo_path, "revert").unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+    }
+
+    #[test]
+    fn test_abort() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+
+        revert(repo_path, c2, None).unwrap();
+        abort_pending_state(repo_path).unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(fs::read_to_string(root.join("a")).unwrap(), "b");
+    }
+
+    #[test]
+    fn test_merge_mainline() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "c1");
+        create_branch(repo_path, "feature").unwrap();
+        let c2 = write_commit_file(&repo, "b", "b", "c2");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "c", "c", "c3");
+        let m = merge_commit(&repo, c2, &[]);
+
+        assert_eq!(
+            get_commit_details(repo_path, m).unwrap().parents.len(),
+            2
+        );
+        assert!(revert(repo_path, m, None).is_err());
+
+        assert!(!revert(repo_path, m, Some(1)).unwrap());
+
+        // reverting against the first parent drops the feature
+        assert!(!root.join("b").exists());
+        assert!(root.join("c").exists());
+    }
+}
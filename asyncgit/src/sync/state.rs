@@ -1,5 +1,8 @@
-use crate::{error::Result, sync::utils};
-use git2::RepositoryState;
+use crate::{
+    error::{Error, Result},
+    sync::{cherry_pick, utils},
+};
+use git2::{build::CheckoutBuilder, RepositoryState, Status};
 use scopetime::scope_time;
 
 ///
@@ -10,6 +13,12 @@ pub enum RepoState {
     ///
     Merge,
     ///
+    CherryPick,
+    ///
+    Revert,
+    ///
+    Rebase,
+    ///
     Other,
 }
 
@@ -18,6 +27,15 @@ impl From<RepositoryState> for RepoState {
         match state {
             RepositoryState::Clean => RepoState::Clean,
             RepositoryState::Merge => RepoState::Merge,
+            RepositoryState::CherryPick
+            | RepositoryState::CherryPickSequence => {
+                RepoState::CherryPick
+            }
+            RepositoryState::Revert
+            | RepositoryState::RevertSequence => RepoState::Revert,
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => RepoState::Rebase,
             _ => RepoState::Other,
         }
     }
@@ -29,5 +47,83 @@ pub fn repo_state(repo_path: &str) -> Result<RepoState> {
 
     let repo = utils::repo(repo_path)?;
 
+    // like git's own sequencer a cherry-pick of several commits stays
+    // in progress until the last one is picked
+    if repo.state() == RepositoryState::Clean
+        && cherry_pick::has_todo(&repo)
+    {
+        return Ok(RepoState::CherryPick);
+    }
+
     Ok(repo.state().into())
 }
+
+/// throws away a cherry-pick or revert in progress like
+/// `git reset --merge`: only paths that are staged or conflicting are
+/// reset to `HEAD`, unstaged changes of other files are kept.
+/// fails if a staged file has unstaged changes on top
+pub fn abort_pending_state(repo_path: &str) -> Result<()> {
+    scope_time!("abort_pending_state");
+
+    let repo = utils::repo(repo_path)?;
+
+    let staged = Status::INDEX_NEW
+        | Status::INDEX_MODIFIED
+        | Status::INDEX_DELETED
+        | Status::INDEX_RENAMED
+        | Status::INDEX_TYPECHANGE;
+    let unstaged = Status::WT_MODIFIED
+        | Status::WT_DELETED
+        | Status::WT_RENAMED
+        | Status::WT_TYPECHANGE;
+
+    let mut paths = Vec::new();
+    for entry in repo.statuses(None)?.iter() {
+        let status = entry.status();
+        let path = entry.path().ok_or_else(|| {
+            Error::Generic("path is not utf8".into())
+        })?;
+
+        if status.is_conflicted() {
+            paths.push(path.to_string());
+        } else if status.intersects(staged) {
+            if status.intersects(unstaged) {
+                return Err(Error::Generic(format!(
+                    "'{}' has unstaged changes, cannot abort",
+                    path
+                )));
+            }
+            paths.push(path.to_string());
+        }
+    }
+
+    if !paths.is_empty() {
+        let head = repo.head()?.peel_to_commit()?;
+        repo.reset_default(Some(head.as_object()), &paths)?;
+
+        // files the operation added are untracked now
+        let mut checkout = CheckoutBuilder::new();
+        checkout.force().remove_untracked(true);
+        for path in &paths {
+            checkout.path(path);
+        }
+        repo.checkout_index(None, Some(&mut checkout))?;
+    }
+
+    repo.cleanup_state()?;
+
+    Ok(())
+}
+
+/// message prepared for the pending merge, revert or cherry-pick
+/// (without the list of conflicts)
+pub fn merge_msg(repo_path: &str) -> Result<String> {
+    scope_time!("merge_msg");
+
+    let repo = utils::repo(repo_path)?;
+
+    let msg = repo.message()?;
+    let msg = msg.split("\nConflicts:\n").next().unwrap_or_default();
+
+    Ok(msg.trim_end().to_string())
+}
@@ -102,6 +102,21 @@ pub fn get_head_repo(repo: &Repository) -> Result<CommitId> {
     }
 }
 
+/// commit a revspec (`abc123`, `v1.2^`, `HEAD~5`, `origin/main`)
+/// points to, tags are peeled
+pub fn resolve_commit(
+    repo_path: &str,
+    spec: &str,
+) -> Result<CommitId> {
+    scope_time!("resolve_commit");
+
+    let repo = repo(repo_path)?;
+    let commit =
+        repo.revparse_single(spec.trim())?.peel_to_commit()?;
+
+    Ok(commit.id().into())
+}
+
 /// add a file diff from workingdir to stage (will not add removed files see `stage_addremoved`)
 pub fn stage_add_file(repo_path: &str, path: &Path) -> Result<()> {
     scope_time!("stage_add_file");
@@ -212,8 +227,10 @@ mod tests {
     use crate::sync::{
         commit,
         status::{get_status, StatusType},
+        tag,
         tests::{
-            debug_cmd_print, get_statuses, repo_init, repo_init_empty,
+            debug_cmd_print, get_statuses, repo_init,
+            repo_init_empty, write_commit_file,
         },
     };
     use std::{
@@ -222,6 +239,27 @@ mod tests {
         path::Path,
     };
 
+    #[test]
+    fn test_resolve_commit() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a", "a", "c1");
+        let c2 = write_commit_file(&repo, "a", "b", "c2");
+        tag(repo_path, &c2, "v1").unwrap();
+
+        assert_eq!(resolve_commit(repo_path, "HEAD~1").unwrap(), c1);
+        assert_eq!(resolve_commit(repo_path, "v1^").unwrap(), c1);
+        assert_eq!(resolve_commit(repo_path, " v1 ").unwrap(), c2);
+        assert_eq!(
+            resolve_commit(repo_path, &c1.get_short_string())
+                .unwrap(),
+            c1
+        );
+        assert!(resolve_commit(repo_path, "nope").is_err());
+    }
+
     #[test]
     fn test_stage_add_smoke() {
         let file_path = Path::new("foo");
@@ -2,23 +2,33 @@ use crate::{
     accessors,
     cmdbar::CommandBar,
     components::{
-        event_pump, BranchListComponent, CommandBlocking,
-        CommandInfo, CommitComponent, Component,
-        CreateBranchComponent, DrawableComponent,
-        ExternalEditorComponent, HelpComponent,
-        InspectCommitComponent, MsgComponent, PullComponent,
-        PushComponent, PushTagsComponent, RenameBranchComponent,
-        ResetComponent, StashMsgComponent, TagCommitComponent,
+        event_pump, ApplyPatchComponent, AuthorStatsComponent,
+        BlameFileComponent, BranchListComponent, CommandBlocking,
+        CommandInfo, CommitComponent, CompareCommitsComponent,
+        Component, CreateBranchComponent, DrawableComponent,
+        ExternalEditorComponent, FileRevlogComponent,
+        FormatPatchComponent, GotoCommitComponent, HelpComponent,
+        InspectCommitComponent, LogRevisionComponent,
+        LogSearchComponent, MsgComponent, NoteCommitComponent,
+        PullComponent, PushComponent, PushTagsComponent,
+        RebaseInteractiveComponent, ReflogComponent,
+        RenameBranchComponent, ResetCommitComponent, ResetComponent,
+        RevertMergeComponent, StashMsgComponent, TagCommitComponent,
     },
     input::{Input, InputEvent, InputState},
     keys::{KeyConfig, SharedKeyConfig},
+    options::Options,
     queue::{Action, InternalEvent, NeedsUpdate, Queue},
     strings::{self, order},
     tabs::{Revlog, StashList, Stashing, Status},
     ui::style::{SharedTheme, Theme},
+    AsyncAppNotification,
 };
 use anyhow::{bail, Result};
-use asyncgit::{sync, AsyncNotification, CWD};
+use asyncgit::{
+    sync::{self, CommitId},
+    AsyncNotification, CWD,
+};
 use crossbeam_channel::Sender;
 use crossterm::event::{Event, KeyEvent};
 use std::{
@@ -43,11 +53,25 @@ pub struct App {
     commit: CommitComponent,
     stashmsg_popup: StashMsgComponent,
     inspect_commit_popup: InspectCommitComponent,
+    compare_commits_popup: CompareCommitsComponent,
+    file_revlog_popup: FileRevlogComponent,
+    blame_file_popup: BlameFileComponent,
     external_editor_popup: ExternalEditorComponent,
     push_popup: PushComponent,
     push_tags_popup: PushTagsComponent,
     pull_popup: PullComponent,
     tag_commit_popup: TagCommitComponent,
+    note_commit_popup: NoteCommitComponent,
+    log_search_popup: LogSearchComponent,
+    log_revision_popup: LogRevisionComponent,
+    goto_commit_popup: GotoCommitComponent,
+    revert_merge_popup: RevertMergeComponent,
+    rebase_interactive_popup: RebaseInteractiveComponent,
+    reset_commit_popup: ResetCommitComponent,
+    format_patch_popup: FormatPatchComponent,
+    apply_patch_popup: ApplyPatchComponent,
+    author_stats_popup: AuthorStatsComponent,
+    reflog_popup: ReflogComponent,
     create_branch_popup: CreateBranchComponent,
     rename_branch_popup: RenameBranchComponent,
     select_branch_popup: BranchListComponent,
@@ -65,6 +89,8 @@ pub struct App {
     // "Flags"
     requires_redraw: Cell<bool>,
     file_to_open: Option<String>,
+    /// the external editor is opened for the note popup
+    edit_note: bool,
 }
 
 // public interface
@@ -73,13 +99,16 @@ impl App {
     #[allow(clippy::too_many_lines)]
     pub fn new(
         sender: &Sender<AsyncNotification>,
+        sender_app: &Sender<AsyncAppNotification>,
         input: Input,
         theme: Theme,
         key_config: KeyConfig,
+        options: Options,
     ) -> Self {
         let queue = Queue::default();
         let theme = Rc::new(theme);
         let key_config = Rc::new(key_config);
+        let options = Rc::new(RefCell::new(options));
 
         Self {
             input,
@@ -99,6 +128,30 @@ impl App {
                 key_config.clone(),
             ),
             inspect_commit_popup: InspectCommitComponent::new(
+                &queue,
+                sender,
+                sender_app,
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+            ),
+            compare_commits_popup: CompareCommitsComponent::new(
+                &queue,
+                sender,
+                sender_app,
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+            ),
+            file_revlog_popup: FileRevlogComponent::new(
+                &queue,
+                sender,
+                sender_app,
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+            ),
+            blame_file_popup: BlameFileComponent::new(
                 &queue,
                 sender,
                 theme.clone(),
@@ -131,6 +184,61 @@ impl App {
                 theme.clone(),
                 key_config.clone(),
             ),
+            note_commit_popup: NoteCommitComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            log_search_popup: LogSearchComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            log_revision_popup: LogRevisionComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            goto_commit_popup: GotoCommitComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            revert_merge_popup: RevertMergeComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            rebase_interactive_popup: RebaseInteractiveComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            reset_commit_popup: ResetCommitComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            format_patch_popup: FormatPatchComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            apply_patch_popup: ApplyPatchComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            author_stats_popup: AuthorStatsComponent::new(
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            reflog_popup: ReflogComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
             create_branch_popup: CreateBranchComponent::new(
                 queue.clone(),
                 theme.clone(),
@@ -162,12 +270,15 @@ impl App {
                 sender,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             status_tab: Status::new(
                 &queue,
                 sender,
+                sender_app,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             stashing_tab: Stashing::new(
                 sender,
@@ -179,12 +290,14 @@ impl App {
                 &queue,
                 theme.clone(),
                 key_config.clone(),
+                options,
             ),
             queue,
             theme,
             key_config,
             requires_redraw: Cell::new(false),
             file_to_open: None,
+            edit_note: false,
         }
     }
 
@@ -271,6 +384,9 @@ impl App {
                             Path::new(&path),
                         )
                     }
+                    None if std::mem::take(&mut self.edit_note) => {
+                        self.note_commit_popup.show_editor()
+                    }
                     None => self.commit.show_editor(),
                 };
 
@@ -299,6 +415,7 @@ impl App {
         self.revlog.update()?;
         self.stashing_tab.update()?;
         self.stashlist_tab.update()?;
+        self.reflog_popup.update()?;
 
         self.update_commands();
 
@@ -316,9 +433,13 @@ impl App {
         self.stashing_tab.update_git(ev)?;
         self.revlog.update_git(ev)?;
         self.inspect_commit_popup.update_git(ev)?;
+        self.compare_commits_popup.update_git(ev)?;
+        self.file_revlog_popup.update_git(ev)?;
+        self.blame_file_popup.update_git(ev)?;
         self.push_popup.update_git(ev)?;
         self.push_tags_popup.update_git(ev)?;
         self.pull_popup.update_git(ev)?;
+        self.author_stats_popup.update_git(ev)?;
 
         //TODO: better system for this
         // can we simply process the queue here and everyone just uses the queue to schedule a cmd update?
@@ -327,6 +448,16 @@ impl App {
         Ok(())
     }
 
+    ///
+    pub fn update_app(&mut self, ev: AsyncAppNotification) {
+        log::trace!("update_app: {:?}", ev);
+
+        self.status_tab.update_app(ev);
+        self.inspect_commit_popup.update_app(ev);
+        self.compare_commits_popup.update_app(ev);
+        self.file_revlog_popup.update_app(ev);
+    }
+
     ///
     pub const fn is_quit(&self) -> bool {
         self.do_quit
@@ -338,10 +469,14 @@ impl App {
             || self.revlog.any_work_pending()
             || self.stashing_tab.anything_pending()
             || self.inspect_commit_popup.any_work_pending()
+            || self.compare_commits_popup.any_work_pending()
+            || self.file_revlog_popup.any_work_pending()
+            || self.blame_file_popup.any_work_pending()
             || self.input.is_state_changing()
             || self.push_popup.any_work_pending()
             || self.push_tags_popup.any_work_pending()
             || self.pull_popup.any_work_pending()
+            || self.author_stats_popup.any_work_pending()
     }
 
     ///
@@ -364,15 +499,29 @@ impl App {
             reset,
             commit,
             stashmsg_popup,
+            blame_file_popup,
+            file_revlog_popup,
             inspect_commit_popup,
+            compare_commits_popup,
             external_editor_popup,
             push_popup,
             push_tags_popup,
             pull_popup,
             tag_commit_popup,
+            note_commit_popup,
+            log_search_popup,
+            log_revision_popup,
+            goto_commit_popup,
+            revert_merge_popup,
+            rebase_interactive_popup,
+            reset_commit_popup,
+            format_patch_popup,
+            apply_patch_popup,
+            author_stats_popup,
             create_branch_popup,
             rename_branch_popup,
             select_branch_popup,
+            reflog_popup,
             help,
             revlog,
             status_tab,
@@ -450,6 +599,9 @@ impl App {
         let new_flags = self.process_internal_events()?;
         flags.insert(new_flags);
 
+        if flags.contains(NeedsUpdate::NOTES) {
+            self.revlog.update_notes()?;
+        }
         if flags.contains(NeedsUpdate::ALL) {
             self.update()?;
         }
@@ -458,6 +610,7 @@ impl App {
         if flags.contains(NeedsUpdate::DIFF) {
             self.status_tab.update_diff()?;
             self.inspect_commit_popup.update_diff()?;
+            self.compare_commits_popup.update_diff()?;
         }
         if flags.contains(NeedsUpdate::COMMANDS) {
             self.update_commands();
@@ -482,6 +635,7 @@ impl App {
         Ok(flags)
     }
 
+    #[allow(clippy::too_many_lines)]
     fn process_internal_event(
         &mut self,
         ev: InternalEvent,
@@ -529,6 +683,37 @@ impl App {
                     self.pull_popup.try_conflict_free_merge(rebase);
                     flags.insert(NeedsUpdate::ALL);
                 }
+                Action::CherryPick(commits) => {
+                    self.revlog.clear_marked();
+                    if self.status_tab.cherry_pick(&commits) {
+                        self.set_tab(0)?;
+                    }
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::AbortOperation => {
+                    if let Err(e) = self.status_tab.abort_operation()
+                    {
+                        self.msg.show_error(&format!(
+                            "abort error:\n{}",
+                            e
+                        ))?;
+                    }
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::ResetCommit { commit, mode, .. } => {
+                    if let Err(e) =
+                        sync::reset_repo(CWD, commit, mode)
+                    {
+                        self.msg.show_error(&format!(
+                            "reset error:\n{}",
+                            e
+                        ))?;
+                    }
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::CreateBranchHere(_) => {
+                    self.create_branch_popup.open()?;
+                }
             },
             InternalEvent::ConfirmAction(action) => {
                 self.reset.open(action)?;
@@ -539,6 +724,10 @@ impl App {
                 flags
                     .insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
             }
+            InternalEvent::ShowInfoMsg(msg) => {
+                self.msg.show_info(msg.as_str())?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
             InternalEvent::Update(u) => flags.insert(u),
             InternalEvent::OpenCommit => self.commit.show()?,
             InternalEvent::PopupStashing(opts) => {
@@ -548,9 +737,16 @@ impl App {
             InternalEvent::TagCommit(id) => {
                 self.tag_commit_popup.open(id)?;
             }
+            InternalEvent::NoteCommit(id) => {
+                self.note_commit_popup.open(id)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
             InternalEvent::CreateBranch => {
                 self.create_branch_popup.open()?;
             }
+            InternalEvent::CreateBranchAt(id) => {
+                self.create_branch_popup.open_at(id)?;
+            }
             InternalEvent::RenameBranch(branch_ref, cur_name) => {
                 self.rename_branch_popup
                     .open(branch_ref, cur_name)?;
@@ -563,12 +759,111 @@ impl App {
                 self.inspect_commit_popup.open(id, tags)?;
                 flags.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS)
             }
+            InternalEvent::OpenFileRevlog(path) => {
+                self.file_revlog_popup.open(path)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::BlameFile(path, commit) => {
+                self.blame_file_popup.open(path, commit)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenLogSearch => {
+                self.log_search_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::SearchLog(query) => {
+                self.revlog.search(query)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenLogRevision => {
+                self.log_revision_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenGotoCommit => {
+                self.goto_commit_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::GotoCommit(id) => {
+                self.revlog.goto_commit(id)?;
+                flags.insert(NeedsUpdate::ALL)
+            }
+            InternalEvent::RevertCommit(id, mainline) => {
+                self.revert_commit(id, mainline)?;
+                flags.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenCommitWithMsg(msg) => {
+                self.commit.show_with_msg(msg)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenReflog => {
+                self.reflog_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenFormatPatch(commits) => {
+                self.format_patch_popup.open(commits)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenAuthorStats(start, title) => {
+                self.author_stats_popup.open(start, title)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenApplyPatch => {
+                self.apply_patch_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::CompareCommits(old, new) => {
+                self.compare_commits_popup.open(old, new)?;
+                flags.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::CheckoutCommit(id, tag) => {
+                self.checkout_commit(id, tag)?;
+                flags.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenResetCommit(id) => {
+                self.reset_commit_popup.open(id)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenRebaseInteractive(base) => {
+                match sync::get_rebase_commits(CWD, base) {
+                    Ok(commits) if commits.is_empty() => {
+                        self.msg.show_error("nothing to rebase")?;
+                    }
+                    Ok(commits) => {
+                        self.rebase_interactive_popup
+                            .open(base, &commits)?;
+                    }
+                    Err(e) => {
+                        self.msg.show_error(&format!(
+                            "rebase error:\n{}",
+                            e
+                        ))?;
+                    }
+                }
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::RebaseInteractive(base, todo) => {
+                if self.status_tab.rebase_interactive(base, &todo) {
+                    self.set_tab(0)?;
+                }
+                flags.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::SetLogStart(start) => {
+                self.revlog.set_start(start)?;
+                self.set_tab(1)?;
+                flags.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS)
+            }
             InternalEvent::OpenExternalEditor(path) => {
                 self.input.set_polling(false);
                 self.external_editor_popup.show()?;
                 self.file_to_open = path;
                 flags.insert(NeedsUpdate::COMMANDS)
             }
+            InternalEvent::OpenNoteEditor => {
+                self.input.set_polling(false);
+                self.external_editor_popup.show()?;
+                self.edit_note = true;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
             InternalEvent::Push(branch, force) => {
                 self.push_popup.push(branch, force)?;
                 flags.insert(NeedsUpdate::ALL)
@@ -586,6 +881,72 @@ impl App {
         Ok(flags)
     }
 
+    /// detaches `HEAD` at `id` and offers to create a branch there,
+    /// the prompt refers to the commit by `tag` if given
+    fn checkout_commit(
+        &mut self,
+        id: CommitId,
+        tag: Option<String>,
+    ) -> Result<()> {
+        if let Err(e) = sync::checkout_commit(CWD, id) {
+            self.msg
+                .show_error(&format!("checkout error:\n{}", e))?;
+        } else {
+            let target = tag.unwrap_or_else(|| id.get_short_string());
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(
+                    Action::CreateBranchHere(target),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// reverts `id` and opens the commit popup with the revert
+    /// message, merges ask for the mainline parent first
+    fn revert_commit(
+        &mut self,
+        id: CommitId,
+        mainline: Option<u32>,
+    ) -> Result<()> {
+        if mainline.is_none() {
+            let parents = sync::get_commit_details(CWD, id)?.parents;
+            if parents.len() > 1 {
+                self.revert_merge_popup.open(id, &parents)?;
+                return Ok(());
+            }
+        }
+
+        match sync::revert(CWD, id, mainline) {
+            Ok(conflicts) => {
+                self.set_tab(0)?;
+                if conflicts {
+                    self.msg.show_error(
+                        &strings::operation_conflict_msg(
+                            &self.key_config,
+                            "revert",
+                            &id.get_short_string(),
+                        ),
+                    )?;
+                } else {
+                    self.commit
+                        .show_with_msg(sync::merge_msg(CWD)?)?;
+                }
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "revert error:\n{}",
+                        e
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     fn commands(&self, force_all: bool) -> Vec<CommandInfo> {
         let mut res = Vec::new();
 
@@ -637,8 +998,22 @@ impl App {
             || self.msg.is_visible()
             || self.stashmsg_popup.is_visible()
             || self.inspect_commit_popup.is_visible()
+            || self.compare_commits_popup.is_visible()
+            || self.file_revlog_popup.is_visible()
+            || self.blame_file_popup.is_visible()
             || self.external_editor_popup.is_visible()
             || self.tag_commit_popup.is_visible()
+            || self.note_commit_popup.is_visible()
+            || self.log_search_popup.is_visible()
+            || self.log_revision_popup.is_visible()
+            || self.goto_commit_popup.is_visible()
+            || self.revert_merge_popup.is_visible()
+            || self.rebase_interactive_popup.is_visible()
+            || self.reset_commit_popup.is_visible()
+            || self.format_patch_popup.is_visible()
+            || self.apply_patch_popup.is_visible()
+            || self.author_stats_popup.is_visible()
+            || self.reflog_popup.is_visible()
             || self.create_branch_popup.is_visible()
             || self.push_popup.is_visible()
             || self.push_tags_popup.is_visible()
@@ -665,9 +1040,23 @@ impl App {
         self.commit.draw(f, size)?;
         self.stashmsg_popup.draw(f, size)?;
         self.help.draw(f, size)?;
+        self.reflog_popup.draw(f, size)?;
         self.inspect_commit_popup.draw(f, size)?;
+        self.compare_commits_popup.draw(f, size)?;
+        self.file_revlog_popup.draw(f, size)?;
+        self.blame_file_popup.draw(f, size)?;
         self.external_editor_popup.draw(f, size)?;
         self.tag_commit_popup.draw(f, size)?;
+        self.note_commit_popup.draw(f, size)?;
+        self.log_search_popup.draw(f, size)?;
+        self.log_revision_popup.draw(f, size)?;
+        self.goto_commit_popup.draw(f, size)?;
+        self.revert_merge_popup.draw(f, size)?;
+        self.rebase_interactive_popup.draw(f, size)?;
+        self.reset_commit_popup.draw(f, size)?;
+        self.format_patch_popup.draw(f, size)?;
+        self.apply_patch_popup.draw(f, size)?;
+        self.author_stats_popup.draw(f, size)?;
         self.select_branch_popup.draw(f, size)?;
         self.create_branch_popup.draw(f, size)?;
         self.rename_branch_popup.draw(f, size)?;
@@ -0,0 +1,441 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    FileTreeComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, ApplyFailure, ApplyTarget, PatchEntry},
+    StatusItem, CWD,
+};
+use crossterm::event::Event;
+use std::{cell::Cell, convert::TryFrom, fmt, path::Path};
+use tui::{
+    backend::Backend,
+    layout::{Margin, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const PAGE_SIZE: usize = 10;
+
+/// asks for a patch or mbox file, previews the files it touches
+/// and applies it to the working tree, the index or as commits
+pub struct ApplyPatchComponent {
+    input: TextInputComponent,
+    patches: Vec<PatchEntry>,
+    files: Vec<Vec<StatusItem>>,
+    target: ApplyTarget,
+    /// patches already applied in an earlier attempt
+    applied: usize,
+    /// why the next patch to apply does not apply
+    failures: Vec<ApplyFailure>,
+    scroll_top: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ApplyPatchComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 60);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            f.render_widget(Clear, area);
+
+            f.render_widget(
+                Block::default()
+                    .title(Span::styled(
+                        strings::apply_patch_title(
+                            &self.key_config,
+                            self.patches.len(),
+                            self.target,
+                        ),
+                        self.theme.title(true),
+                    ))
+                    .border_type(BorderType::Thick)
+                    .borders(Borders::ALL),
+                area,
+            );
+
+            let area = area.inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+
+            let text = self.get_text();
+
+            let max_top =
+                text.len().saturating_sub(area.height as usize);
+            self.scroll_top.set(self.scroll_top.get().min(max_top));
+
+            f.render_widget(
+                Paragraph::new(text).scroll((
+                    u16::try_from(self.scroll_top.get())?,
+                    0,
+                )),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ApplyPatchComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.input.is_visible() {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::apply_patch_open(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::apply_patch_target(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::apply_patch_confirm(
+                    &self.key_config,
+                ),
+                self.failures.is_empty(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.input.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.load();
+                }
+
+                return Ok(true);
+            }
+        } else if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.scroll(true, 1);
+                } else if e == self.key_config.move_up {
+                    self.scroll(false, 1);
+                } else if e == self.key_config.page_down {
+                    self.scroll(true, PAGE_SIZE);
+                } else if e == self.key_config.page_up {
+                    self.scroll(false, PAGE_SIZE);
+                } else if e == self.key_config.tab_toggle {
+                    self.target = match self.target {
+                        ApplyTarget::WorkDir => ApplyTarget::Index,
+                        ApplyTarget::Index => ApplyTarget::Commits,
+                        ApplyTarget::Commits => ApplyTarget::WorkDir,
+                    };
+                    self.check();
+                } else if e == self.key_config.enter
+                    && self.failures.is_empty()
+                {
+                    self.apply();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible || self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl ApplyPatchComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::apply_patch_input_title(&key_config),
+                &strings::apply_patch_input_msg(&key_config),
+                true,
+            ),
+            patches: Vec::new(),
+            files: Vec::new(),
+            target: ApplyTarget::WorkDir,
+            applied: 0,
+            failures: Vec::new(),
+            scroll_top: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// asks for the file to apply
+    pub fn open(&mut self) -> Result<()> {
+        self.visible = false;
+        self.input.show()?;
+
+        Ok(())
+    }
+
+    fn load(&mut self) {
+        let path = self.input.get_text().clone();
+
+        let res = sync::read_patch_file(Path::new(&path)).and_then(
+            |patches| {
+                let files = patches
+                    .iter()
+                    .map(sync::get_patch_files)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((patches, files))
+            },
+        );
+
+        match res {
+            Ok((patches, files)) => {
+                // mails carry what is needed to commit them
+                self.target = if patches[0].author.is_some() {
+                    ApplyTarget::Commits
+                } else {
+                    ApplyTarget::WorkDir
+                };
+                self.patches = patches;
+                self.files = files;
+                self.applied = 0;
+                self.scroll_top.set(0);
+
+                self.input.hide();
+                self.visible = true;
+                self.check();
+            }
+            Err(e) => self.show_error(&e),
+        }
+    }
+
+    /// only the next patch can be checked, the later ones depend
+    /// on it
+    fn check(&mut self) {
+        self.failures.clear();
+
+        if let Some(patch) = self.patches.get(self.applied) {
+            match sync::check_patch(CWD, patch, self.target) {
+                Ok(failures) => self.failures = failures,
+                Err(e) => self.show_error(&e),
+            }
+        }
+    }
+
+    fn apply(&mut self) {
+        let res = sync::apply_patches(
+            CWD,
+            &self.patches[self.applied..],
+            self.target,
+        );
+
+        match res {
+            Ok(res) => {
+                self.applied += res.applied;
+                self.failures = res.failures;
+
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::Update(NeedsUpdate::ALL),
+                );
+
+                if self.failures.is_empty() {
+                    self.hide();
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowInfoMsg(
+                            strings::apply_patch_done_msg(
+                                &self.key_config,
+                                self.applied,
+                                self.target,
+                            ),
+                        ),
+                    );
+                }
+            }
+            Err(e) => self.show_error(&e),
+        }
+    }
+
+    fn show_error(&self, e: &impl fmt::Display) {
+        log::error!("apply patch: {}", e);
+        self.queue.borrow_mut().push_back(
+            InternalEvent::ShowErrorMsg(format!(
+                "apply patch error:\n{}",
+                e
+            )),
+        );
+    }
+
+    fn scroll(&self, down: bool, step: usize) {
+        let top = self.scroll_top.get();
+        self.scroll_top.set(if down {
+            top.saturating_add(step)
+        } else {
+            top.saturating_sub(step)
+        });
+    }
+
+    fn get_text(&self) -> Vec<Spans<'_>> {
+        let mut res = Vec::new();
+
+        let status = if self.applied >= self.patches.len() {
+            None
+        } else if self.failures.is_empty() {
+            Some(Span::styled(
+                strings::apply_patch_check_ok(
+                    &self.key_config,
+                    self.applied + 1,
+                ),
+                self.theme.text(true, false),
+            ))
+        } else {
+            Some(Span::styled(
+                strings::apply_patch_check_failed(
+                    &self.key_config,
+                    self.applied + 1,
+                ),
+                self.theme.text_danger(),
+            ))
+        };
+        if let Some(status) = status {
+            res.push(Spans::from(status));
+            res.push(Spans::default());
+        }
+
+        for (idx, (patch, files)) in
+            self.patches.iter().zip(self.files.iter()).enumerate()
+        {
+            let done = idx < self.applied;
+
+            let mut header = vec![Span::styled(
+                format!("[{}/{}] ", idx + 1, self.patches.len()),
+                self.theme.commit_hash(false),
+            )];
+            if let Some(subject) = patch.subject() {
+                header.push(Span::styled(
+                    subject,
+                    self.theme.text(!done, false),
+                ));
+            }
+            if let Some(author) = &patch.author {
+                header.push(Span::styled(
+                    format!(" <{}>", author.name),
+                    self.theme.commit_author(false),
+                ));
+            }
+            res.push(Spans::from(header));
+
+            for file in files {
+                res.push(Spans::from(Span::styled(
+                    format!(
+                        "  {} {}",
+                        FileTreeComponent::item_status_char(
+                            file.status
+                        ),
+                        file.path
+                    ),
+                    if done {
+                        self.theme.text(false, false)
+                    } else {
+                        self.theme.item(file.status, false)
+                    },
+                )));
+            }
+
+            if idx == self.applied {
+                for failure in &self.failures {
+                    res.push(Spans::from(Span::styled(
+                        format!(
+                            "  ! {}{}: {}",
+                            failure.path,
+                            failure
+                                .hunk
+                                .as_ref()
+                                .map(|hunk| format!(" {}", hunk))
+                                .unwrap_or_default(),
+                            failure.error
+                        ),
+                        self.theme.text_danger(),
+                    )));
+                }
+            }
+        }
+
+        res
+    }
+}
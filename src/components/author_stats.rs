@@ -0,0 +1,421 @@
+use super::{
+    utils::time_to_string, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    strings,
+    ui::{self, calc_scroll_top, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{AuthorStats, LogStart},
+    AsyncAuthorStats, AsyncNotification, AuthorStatsParams,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::{Margin, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const PAGE_SIZE: usize = 10;
+
+/// column the author list is sorted by
+#[derive(Copy, Clone, PartialEq)]
+enum AuthorSort {
+    Commits,
+    Lines,
+    Name,
+    FirstCommit,
+    LastCommit,
+}
+
+impl AuthorSort {
+    const ALL: [Self; 5] = [
+        Self::Commits,
+        Self::Lines,
+        Self::Name,
+        Self::FirstCommit,
+        Self::LastCommit,
+    ];
+
+    fn cycle(self, forward: bool) -> Self {
+        let count = Self::ALL.len();
+        let idx =
+            Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+
+        Self::ALL[if forward {
+            (idx + 1) % count
+        } else {
+            (idx + count - 1) % count
+        }]
+    }
+
+    /// numbers and dates descending, names ascending
+    fn sort(self, stats: &mut [AuthorStats]) {
+        let lines = |a: &AuthorStats| {
+            a.lines.map_or(0, |l| l.insertions + l.deletions)
+        };
+
+        stats.sort_by(|a, b| {
+            match self {
+                Self::Commits => b.commits.cmp(&a.commits),
+                Self::Lines => lines(b).cmp(&lines(a)),
+                Self::Name => {
+                    a.name.to_lowercase().cmp(&b.name.to_lowercase())
+                }
+                Self::FirstCommit => b.first_time.cmp(&a.first_time),
+                Self::LastCommit => b.last_time.cmp(&a.last_time),
+            }
+            .then_with(|| b.commits.cmp(&a.commits))
+        });
+    }
+}
+
+/// per author commit counts of the log (`git shortlog`)
+pub struct AuthorStatsComponent {
+    title: String,
+    params: Option<AuthorStatsParams>,
+    stats: Vec<AuthorStats>,
+    sort: AuthorSort,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    git_stats: AsyncAuthorStats,
+    visible: bool,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for AuthorStatsComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 60);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            f.render_widget(Clear, area);
+
+            f.render_widget(
+                Block::default()
+                    .title(Span::styled(
+                        strings::author_stats_title(
+                            &self.key_config,
+                            &self.title,
+                            self.stats.len(),
+                            self.sort_name(),
+                            self.git_stats.is_pending(),
+                        ),
+                        self.theme.title(true),
+                    ))
+                    .border_type(BorderType::Thick)
+                    .borders(Borders::ALL),
+                area,
+            );
+
+            let area = area.inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+
+            // header takes one line
+            let height = (area.height as usize).saturating_sub(1);
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(
+                Paragraph::new(self.get_text(height)),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for AuthorStatsComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::author_stats_sort(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::author_stats_lines(
+                    &self.key_config,
+                    self.with_lines(),
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.move_selection(true, 1);
+                } else if e == self.key_config.move_up {
+                    self.move_selection(false, 1);
+                } else if e == self.key_config.page_down {
+                    self.move_selection(true, PAGE_SIZE);
+                } else if e == self.key_config.page_up {
+                    self.move_selection(false, PAGE_SIZE);
+                } else if e == self.key_config.tab_toggle {
+                    self.set_sort(self.sort.cycle(true));
+                } else if e == self.key_config.tab_toggle_reverse {
+                    self.set_sort(self.sort.cycle(false));
+                } else if e == self.key_config.author_stats_lines {
+                    if let Some(params) = self.params.as_mut() {
+                        params.with_lines = !params.with_lines;
+                    }
+                    self.update()?;
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl AuthorStatsComponent {
+    ///
+    pub fn new(
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            title: String::new(),
+            params: None,
+            stats: Vec::new(),
+            sort: AuthorSort::Commits,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            git_stats: AsyncAuthorStats::new(sender),
+            visible: false,
+            theme,
+            key_config,
+        }
+    }
+
+    /// aggregates the log starting at `start`, `title` names it
+    pub fn open(
+        &mut self,
+        start: LogStart,
+        title: String,
+    ) -> Result<()> {
+        let with_lines = self.with_lines();
+
+        self.title = title;
+        self.params = Some(AuthorStatsParams { start, with_lines });
+        self.stats.clear();
+        self.selection = 0;
+        self.scroll_top.set(0);
+
+        self.update()?;
+        self.show()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_stats.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.visible && ev == AsyncNotification::AuthorStats {
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) -> Result<()> {
+        if let Some(params) = &self.params {
+            match self.git_stats.last()? {
+                Some((last, stats)) if &last == params => {
+                    self.stats = stats;
+                    self.sort.sort(&mut self.stats);
+                    self.move_selection(false, 0);
+                }
+                _ => self.git_stats.request(params.clone())?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn with_lines(&self) -> bool {
+        self.params.as_ref().map_or(false, |p| p.with_lines)
+    }
+
+    fn set_sort(&mut self, sort: AuthorSort) {
+        self.sort = sort;
+        self.sort.sort(&mut self.stats);
+        self.selection = 0;
+    }
+
+    const fn sort_name(&self) -> &'static str {
+        match self.sort {
+            AuthorSort::Commits => "commits",
+            AuthorSort::Lines => "lines",
+            AuthorSort::Name => "name",
+            AuthorSort::FirstCommit => "first commit",
+            AuthorSort::LastCommit => "last commit",
+        }
+    }
+
+    fn move_selection(&mut self, down: bool, step: usize) {
+        let max = self.stats.len().saturating_sub(1);
+
+        self.selection = if down {
+            self.selection.saturating_add(step).min(max)
+        } else {
+            self.selection.saturating_sub(step).min(max)
+        };
+    }
+
+    fn get_text(&self, height: usize) -> Vec<Spans<'_>> {
+        let with_lines = self.with_lines();
+
+        let header = format!(
+            "{:>7} {}{:<10} {:<10} {}",
+            "commits",
+            if with_lines {
+                format!("{:>9} {:>9} ", "added", "removed")
+            } else {
+                String::new()
+            },
+            "first",
+            "last",
+            "author"
+        );
+
+        let mut res = vec![Spans::from(Span::styled(
+            header,
+            self.theme.title(true),
+        ))];
+
+        res.extend(
+            self.stats
+                .iter()
+                .enumerate()
+                .skip(self.scroll_top.get())
+                .take(height)
+                .map(|(idx, author)| {
+                    let selected = idx == self.selection;
+
+                    let mut spans = vec![Span::styled(
+                        format!("{:>7} ", author.commits),
+                        self.theme.text(true, selected),
+                    )];
+
+                    if with_lines {
+                        let lines = author.lines.unwrap_or_default();
+                        spans.push(Span::styled(
+                            format!(
+                                "{:>9} ",
+                                format!("+{}", lines.insertions)
+                            ),
+                            self.theme.diff_line(
+                                asyncgit::DiffLineType::Add,
+                                selected,
+                            ),
+                        ));
+                        spans.push(Span::styled(
+                            format!(
+                                "{:>9} ",
+                                format!("-{}", lines.deletions)
+                            ),
+                            self.theme.diff_line(
+                                asyncgit::DiffLineType::Delete,
+                                selected,
+                            ),
+                        ));
+                    }
+
+                    spans.push(Span::styled(
+                        format!(
+                            "{} {} ",
+                            time_to_string(author.first_time, true),
+                            time_to_string(author.last_time, true),
+                        ),
+                        self.theme.commit_time(selected),
+                    ));
+                    spans.push(Span::styled(
+                        format!("{} <{}>", author.name, author.email),
+                        self.theme.commit_author(selected),
+                    ));
+
+                    Spans::from(spans)
+                }),
+        );
+
+        res
+    }
+}
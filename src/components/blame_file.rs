@@ -0,0 +1,414 @@
+use super::{
+    utils, visibility_blocking, CommandBlocking, CommandInfo,
+    Component, DrawableComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, calc_scroll_top, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{BlameHunk, CommitId, FileBlame},
+    AsyncBlame, AsyncNotification, BlameParams,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use std::{cell::Cell, convert::TryInto};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Margin, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_truncate::UnicodeTruncateStr;
+
+const AUTHOR_WIDTH: usize = 16;
+
+///
+pub struct BlameFileComponent {
+    params: Option<BlameParams>,
+    blame: Option<FileBlame>,
+    git_blame: AsyncBlame,
+    visible: bool,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    current_height: Cell<u16>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for BlameFileComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(90, 90);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            f.render_widget(Clear, area);
+
+            f.render_widget(
+                Block::default()
+                    .title(Span::styled(
+                        self.get_title(),
+                        self.theme.title(true),
+                    ))
+                    .border_type(BorderType::Thick)
+                    .borders(Borders::ALL),
+                area,
+            );
+
+            let area = area.inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+
+            self.draw_lines(f, area)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for BlameFileComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::blame_inspect_commit(
+                    &self.key_config,
+                ),
+                self.selected_hunk().is_some(),
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.move_selection(ScrollType::Down);
+                } else if e == self.key_config.move_up {
+                    self.move_selection(ScrollType::Up);
+                } else if e == self.key_config.page_down {
+                    self.move_selection(ScrollType::PageDown);
+                } else if e == self.key_config.page_up {
+                    self.move_selection(ScrollType::PageUp);
+                } else if e == self.key_config.home
+                    || e == self.key_config.shift_up
+                {
+                    self.move_selection(ScrollType::Home);
+                } else if e == self.key_config.end
+                    || e == self.key_config.shift_down
+                {
+                    self.move_selection(ScrollType::End);
+                } else if e == self.key_config.enter {
+                    if let Some(id) = self.selected_commit() {
+                        // inspect is drawn below us
+                        self.hide();
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::InspectCommit(id, None),
+                        );
+                    }
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl BlameFileComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            params: None,
+            blame: None,
+            git_blame: AsyncBlame::new(sender),
+            visible: false,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            current_height: Cell::new(0),
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(
+        &mut self,
+        file_path: String,
+        commit_id: Option<CommitId>,
+    ) -> Result<()> {
+        let params = BlameParams {
+            file_path,
+            commit_id,
+        };
+
+        self.blame = None;
+        self.selection = 0;
+        self.scroll_top.set(0);
+        self.git_blame.request(params.clone())?;
+        self.params = Some(params);
+        self.show()?;
+
+        self.update()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_blame.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.visible && ev == AsyncNotification::Blame {
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) -> Result<()> {
+        if let Some(params) = &self.params {
+            match self.git_blame.last()? {
+                Some((last_params, blame))
+                    if &last_params == params =>
+                {
+                    self.blame = blame;
+                }
+                _ => {
+                    if !self.git_blame.is_pending() {
+                        self.git_blame.request(params.clone())?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_title(&self) -> String {
+        let title = strings::blame_title(&self.key_config);
+
+        match (&self.params, &self.blame) {
+            (Some(params), Some(blame)) => format!(
+                "{} {} -- {}",
+                title,
+                blame.commit_id.get_short_string(),
+                params.file_path
+            ),
+            (Some(params), None) if self.git_blame.is_pending() => {
+                format!("{} {} (loading)", title, params.file_path)
+            }
+            (Some(params), None) => format!(
+                "{} {} (not available)",
+                title, params.file_path
+            ),
+            _ => title,
+        }
+    }
+
+    fn lines_count(&self) -> usize {
+        self.blame.as_ref().map_or(0, |blame| blame.lines.len())
+    }
+
+    fn selected_hunk(&self) -> Option<&BlameHunk> {
+        self.blame.as_ref().and_then(|blame| {
+            blame
+                .lines
+                .get(self.selection)
+                .and_then(|(hunk, _)| hunk.as_ref())
+        })
+    }
+
+    fn selected_commit(&self) -> Option<CommitId> {
+        self.selected_hunk().map(|hunk| hunk.commit_id)
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) {
+        let page = usize::from(self.current_height.get());
+        let max = self.lines_count().saturating_sub(1);
+
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_sub(1),
+            ScrollType::Down => self.selection.saturating_add(1),
+            ScrollType::PageUp => self.selection.saturating_sub(page),
+            ScrollType::PageDown => {
+                self.selection.saturating_add(page)
+            }
+            ScrollType::Home => 0,
+            ScrollType::End => max,
+        };
+
+        self.selection = new_selection.min(max);
+    }
+
+    fn get_text(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> Vec<Spans<'_>> {
+        let blame = match &self.blame {
+            Some(blame) => blame,
+            None => return Vec::new(),
+        };
+
+        let line_number_width = blame.lines.len().to_string().len();
+
+        blame
+            .lines
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(idx, (hunk, line))| {
+                let selected = idx == self.selection;
+
+                let (hash, author, time) = hunk.as_ref().map_or_else(
+                    || (String::new(), String::new(), String::new()),
+                    |hunk| {
+                        (
+                            hunk.commit_id.get_short_string(),
+                            hunk.author
+                                .unicode_truncate(AUTHOR_WIDTH)
+                                .0
+                                .to_string(),
+                            utils::time_to_string(hunk.time, true),
+                        )
+                    },
+                );
+
+                let content = line.replace('\t', "  ");
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:7} ", hash),
+                        self.theme.commit_hash(selected),
+                    ),
+                    Span::styled(
+                        format!("{:w$} ", author, w = AUTHOR_WIDTH),
+                        self.theme.commit_author(selected),
+                    ),
+                    Span::styled(
+                        format!("{:10} ", time),
+                        self.theme.commit_time(selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{:>w$} ",
+                            idx + 1,
+                            w = line_number_width
+                        ),
+                        self.theme.text(true, selected),
+                    ),
+                    Span::styled(
+                        format!("{:w$}", content, w = width),
+                        self.theme.text(true, selected),
+                    ),
+                ])
+            })
+            .collect()
+    }
+
+    fn draw_lines<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        r: Rect,
+    ) -> Result<()> {
+        let height_in_lines = r.height as usize;
+
+        self.scroll_top.set(calc_scroll_top(
+            self.scroll_top.get(),
+            height_in_lines,
+            self.selection,
+        ));
+
+        f.render_widget(
+            Paragraph::new(
+                self.get_text(r.width as usize, height_in_lines),
+            )
+            .alignment(Alignment::Left),
+            r,
+        );
+
+        let mut r = r;
+        r.width += 1;
+
+        ui::draw_scrollbar(
+            f,
+            r,
+            &self.theme,
+            self.lines_count(),
+            self.scroll_top.get(),
+        );
+
+        self.current_height.set(height_in_lines.try_into()?);
+
+        Ok(())
+    }
+}
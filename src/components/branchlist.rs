@@ -13,7 +13,7 @@ use anyhow::Result;
 use asyncgit::{
     sync::{
         branch::checkout_remote_branch, checkout_branch,
-        get_branches_info, BranchInfo,
+        get_branches_info, BranchInfo, LogStart,
     },
     CWD,
 };
@@ -133,6 +133,12 @@ impl Component for BranchListComponent {
                 true,
             ));
 
+            out.push(CommandInfo::new(
+                strings::commands::log_branch_popup(&self.key_config),
+                !self.branches.is_empty(),
+                true,
+            ));
+
             out.push(CommandInfo::new(
                 strings::commands::open_branch_create_popup(
                     &self.key_config,
@@ -179,6 +185,17 @@ impl Component for BranchListComponent {
                         "switch branch error:",
                         self.switch_to_selected_branch()
                     );
+                } else if e == self.key_config.log_branch {
+                    if let Some(branch) =
+                        self.branches.get(self.selection as usize)
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::SetLogStart(
+                                LogStart::Rev(branch.name.clone()),
+                            ),
+                        );
+                        self.hide();
+                    }
                 } else if e == self.key_config.create_branch {
                     self.queue
                         .borrow_mut()
@@ -4,7 +4,6 @@ use super::{
     ExternalEditorComponent,
 };
 use crate::{
-    get_app_config_path,
     keys::SharedKeyConfig,
     queue::{InternalEvent, NeedsUpdate, Queue},
     strings,
@@ -17,11 +16,6 @@ use asyncgit::{
     CWD,
 };
 use crossterm::event::Event;
-use std::{
-    fs::File,
-    io::{Read, Write},
-    path::PathBuf,
-};
 use tui::{
     backend::Backend,
     layout::{Alignment, Rect},
@@ -181,43 +175,12 @@ impl CommitComponent {
 
     pub fn show_editor(&mut self) -> Result<()> {
         const COMMIT_MSG_FILE_NAME: &str = "COMMITMSG_EDITOR";
-        //TODO: use a tmpfile here
-        let mut config_path: PathBuf = get_app_config_path()?;
-        config_path.push(COMMIT_MSG_FILE_NAME);
-
-        {
-            let mut file = File::create(&config_path)?;
-            file.write_fmt(format_args!(
-                "{}\n",
-                self.input.get_text()
-            ))?;
-            file.write_all(
-                strings::commit_editor_msg(&self.key_config)
-                    .as_bytes(),
-            )?;
-        }
-
-        ExternalEditorComponent::open_file_in_editor(&config_path)?;
-
-        let mut message = String::new();
-
-        let mut file = File::open(&config_path)?;
-        file.read_to_string(&mut message)?;
-        drop(file);
-        std::fs::remove_file(&config_path)?;
 
-        let message: String = message
-            .lines()
-            .flat_map(|l| {
-                if l.starts_with('#') {
-                    vec![]
-                } else {
-                    vec![l, "\n"]
-                }
-            })
-            .collect();
-
-        let message = message.trim().to_string();
+        let message = ExternalEditorComponent::edit_message(
+            COMMIT_MSG_FILE_NAME,
+            self.input.get_text(),
+            &strings::commit_editor_msg(&self.key_config),
+        )?;
 
         self.input.set_text(message);
         self.input.show()?;
@@ -225,6 +188,14 @@ impl CommitComponent {
         Ok(())
     }
 
+    /// opens the popup with `msg` (e.g. of a pending revert)
+    pub fn show_with_msg(&mut self, msg: String) -> Result<()> {
+        self.show()?;
+        self.input.set_text(msg);
+
+        Ok(())
+    }
+
     fn commit(&mut self) -> Result<()> {
         let msg = self.input.get_text().clone();
         self.input.clear();
@@ -1,21 +1,25 @@
 use crate::{
     components::{
-        dialog_paragraph, utils::time_to_string, CommandBlocking,
+        dialog_paragraph, utils::time_to_string_tz, CommandBlocking,
         CommandInfo, Component, DrawableComponent, ScrollType,
     },
     keys::SharedKeyConfig,
+    options::SharedOptions,
     strings::{self, order},
     ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{self, CommitDetails, CommitId, CommitMessage},
-    CWD,
+    sync::{
+        self, CommitDetails, CommitId, CommitMessage, CommitNote,
+        CommitStats, DiffParent, DiffStat,
+    },
+    DiffLineType, CWD,
 };
 use crossterm::event::Event;
 use itertools::Itertools;
 use std::clone::Clone;
-use std::{borrow::Cow, cell::Cell};
+use std::{borrow::Cow, cell::Cell, convert::TryFrom};
 use sync::CommitTags;
 use tui::{
     backend::Backend,
@@ -34,11 +38,16 @@ enum Detail {
 pub struct DetailsComponent {
     data: Option<CommitDetails>,
     tags: Vec<String>,
+    notes: Vec<CommitNote>,
+    parent: DiffParent,
+    /// number of changed files and their summed up line stats
+    stats: Option<(usize, DiffStat)>,
     theme: SharedTheme,
     focused: bool,
     current_size: Cell<(u16, u16)>,
     scroll_top: Cell<usize>,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
 }
 
 type WrappedCommitMessage<'a> =
@@ -49,16 +58,21 @@ impl DetailsComponent {
     pub const fn new(
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
         focused: bool,
     ) -> Self {
         Self {
             data: None,
             tags: Vec::new(),
+            notes: Vec::new(),
+            parent: DiffParent::Nth(0),
+            stats: None,
             theme,
             focused,
             current_size: Cell::new((0, 0)),
             scroll_top: Cell::new(0),
             key_config,
+            options,
         }
     }
 
@@ -68,9 +82,13 @@ impl DetailsComponent {
         tags: Option<CommitTags>,
     ) -> Result<()> {
         self.tags.clear();
+        self.stats = None;
 
         self.data =
             id.and_then(|id| sync::get_commit_details(CWD, id).ok());
+        self.notes = id
+            .and_then(|id| sync::get_commit_notes(CWD, id).ok())
+            .unwrap_or_default();
 
         self.scroll_top.set(0);
 
@@ -81,6 +99,121 @@ impl DetailsComponent {
         Ok(())
     }
 
+    /// highlights the parent(s) the commit is diffed against
+    pub fn set_parent(&mut self, parent: DiffParent) {
+        self.parent = parent;
+    }
+
+    /// line stats of the commit, `None` while they get fetched
+    pub fn set_stats(&mut self, stats: Option<&CommitStats>) {
+        self.stats =
+            stats.map(|stats| (stats.files.len(), stats.total));
+    }
+
+    ///
+    pub fn parents(&self) -> &[CommitId] {
+        self.data.as_ref().map_or(&[], |data| &data.parents)
+    }
+
+    #[allow(unstable_name_collisions)]
+    fn get_text_parents(&self) -> Option<Spans<'_>> {
+        let parents = self.parents();
+        if parents.is_empty() {
+            return None;
+        }
+
+        let mut spans = vec![Span::styled(
+            Cow::from(strings::commit::details_parents(
+                &self.key_config,
+            )),
+            self.theme.text(false, false),
+        )];
+
+        spans.extend(
+            parents
+                .iter()
+                .enumerate()
+                .map(|(idx, parent)| {
+                    let selected = match self.parent {
+                        DiffParent::Nth(n) => n == idx,
+                        DiffParent::Combined => true,
+                    };
+
+                    Span::styled(
+                        Cow::from(parent.get_short_string()),
+                        self.theme.commit_hash(selected),
+                    )
+                })
+                .intersperse(Span::raw(Cow::from(" "))),
+        );
+
+        if self.parent == DiffParent::Combined {
+            spans.push(Span::styled(
+                Cow::from(strings::commit::details_parents_combined(
+                    &self.key_config,
+                )),
+                self.theme.text(false, false),
+            ));
+        }
+
+        Some(Spans::from(spans))
+    }
+
+    fn get_text_trailers(&self) -> Vec<Spans<'_>> {
+        self.data
+            .as_ref()
+            .and_then(|data| data.message.as_ref())
+            .map_or_else(Vec::new, |message| {
+                message
+                    .trailers
+                    .iter()
+                    .map(|trailer| {
+                        Spans::from(vec![
+                            Span::styled(
+                                Cow::from(format!(
+                                    "{}: ",
+                                    trailer.key
+                                )),
+                                self.theme.text(false, false),
+                            ),
+                            Span::styled(
+                                Cow::from(trailer.value.as_str()),
+                                self.theme.text(true, false),
+                            ),
+                        ])
+                    })
+                    .collect()
+            })
+    }
+
+    fn get_text_stats(&self) -> Option<Spans<'_>> {
+        let (files, total) = self.stats?;
+
+        Some(Spans::from(vec![
+            Span::styled(
+                Cow::from(strings::commit::details_stats(
+                    &self.key_config,
+                )),
+                self.theme.text(false, false),
+            ),
+            Span::styled(
+                Cow::from(strings::commit::details_stats_files(
+                    &self.key_config,
+                    files,
+                )),
+                self.theme.text(true, false),
+            ),
+            Span::styled(
+                Cow::from(format!(", +{}", total.insertions)),
+                self.theme.diff_line(DiffLineType::Add, false),
+            ),
+            Span::styled(
+                Cow::from(format!(" -{}", total.deletions)),
+                self.theme.diff_line(DiffLineType::Delete, false),
+            ),
+        ]))
+    }
+
     fn wrap_commit_details(
         message: &CommitMessage,
         width: usize,
@@ -106,13 +239,38 @@ impl DetailsComponent {
     ) -> WrappedCommitMessage<'_> {
         if let Some(ref data) = self.data {
             if let Some(ref message) = data.message {
-                return Self::wrap_commit_details(message, width);
+                let (wrapped_title, mut wrapped_message) =
+                    Self::wrap_commit_details(message, width);
+                wrapped_message.extend(self.wrap_notes(width));
+
+                return (wrapped_title, wrapped_message);
             }
         }
 
         (vec![], vec![])
     }
 
+    /// notes below the message, indented like in `git log`
+    fn wrap_notes(&self, width: usize) -> Vec<Cow<'_, str>> {
+        let mut res = Vec::new();
+
+        for note in &self.notes {
+            res.push(Cow::from(""));
+            res.push(Cow::from(strings::commit::details_notes(
+                &self.key_config,
+                &note.notes_ref,
+            )));
+            res.extend(textwrap::wrap(
+                &note.message,
+                textwrap::Options::new(width)
+                    .initial_indent("    ")
+                    .subsequent_indent("    "),
+            ));
+        }
+
+        res
+    }
+
     fn get_number_of_lines(&self, width: usize) -> usize {
         let (wrapped_title, wrapped_message) =
             self.get_wrapped_lines(width);
@@ -183,6 +341,9 @@ impl DetailsComponent {
     #[allow(unstable_name_collisions)]
     fn get_text_info(&self) -> Vec<Spans> {
         if let Some(ref data) = self.data {
+            let date_format =
+                self.options.borrow().date_format.clone();
+
             let mut res = vec![
                 Spans::from(vec![
                     self.style_detail(&Detail::Author),
@@ -197,9 +358,10 @@ impl DetailsComponent {
                 Spans::from(vec![
                     self.style_detail(&Detail::Date),
                     Span::styled(
-                        Cow::from(time_to_string(
+                        Cow::from(time_to_string_tz(
                             data.author.time,
-                            false,
+                            data.author.time_offset,
+                            &date_format,
                         )),
                         self.theme.text(true, false),
                     ),
@@ -221,9 +383,10 @@ impl DetailsComponent {
                     Spans::from(vec![
                         self.style_detail(&Detail::Date),
                         Span::styled(
-                            Cow::from(time_to_string(
+                            Cow::from(time_to_string_tz(
                                 committer.time,
-                                false,
+                                committer.time_offset,
+                                &date_format,
                             )),
                             self.theme.text(true, false),
                         ),
@@ -231,6 +394,8 @@ impl DetailsComponent {
                 ]);
             }
 
+            res.extend(self.get_text_trailers());
+
             res.push(Spans::from(vec![
                 Span::styled(
                     Cow::from(strings::commit::details_sha(
@@ -244,6 +409,14 @@ impl DetailsComponent {
                 ),
             ]));
 
+            if let Some(parents) = self.get_text_parents() {
+                res.push(parents);
+            }
+
+            if let Some(stats) = self.get_text_stats() {
+                res.push(stats);
+            }
+
             if !self.tags.is_empty() {
                 res.push(Spans::from(
                     self.style_detail(&Detail::Sha),
@@ -308,10 +481,18 @@ impl DrawableComponent for DetailsComponent {
         f: &mut Frame<B>,
         rect: Rect,
     ) -> Result<()> {
+        let info = self.get_text_info();
+        let info_height =
+            u16::try_from(info.len())?.saturating_add(2).max(8);
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
-                [Constraint::Length(8), Constraint::Min(10)].as_ref(),
+                [
+                    Constraint::Length(info_height),
+                    Constraint::Min(10),
+                ]
+                .as_ref(),
             )
             .split(rect);
 
@@ -320,7 +501,7 @@ impl DrawableComponent for DetailsComponent {
                 &strings::commit::details_info_title(
                     &self.key_config,
                 ),
-                Text::from(self.get_text_info()),
+                Text::from(info),
                 &self.theme,
                 false,
             ),
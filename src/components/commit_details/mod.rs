@@ -5,12 +5,12 @@ use super::{
     Component, DrawableComponent, FileTreeComponent,
 };
 use crate::{
-    accessors, keys::SharedKeyConfig, queue::Queue, strings,
-    ui::style::SharedTheme,
+    accessors, keys::SharedKeyConfig, options::SharedOptions,
+    queue::Queue, strings, ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{CommitId, CommitTags},
+    sync::{CommitId, CommitTags, DiffParent},
     AsyncCommitFiles, AsyncNotification,
 };
 use crossbeam_channel::Sender;
@@ -26,6 +26,7 @@ pub struct CommitDetailsComponent {
     details: DetailsComponent,
     file_tree: FileTreeComponent,
     git_commit_files: AsyncCommitFiles,
+    parent: DiffParent,
     visible: bool,
     key_config: SharedKeyConfig,
 }
@@ -39,14 +40,17 @@ impl CommitDetailsComponent {
         sender: &Sender<AsyncNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             details: DetailsComponent::new(
                 theme.clone(),
                 key_config.clone(),
+                options,
                 false,
             ),
             git_commit_files: AsyncCommitFiles::new(sender),
+            parent: DiffParent::default(),
             file_tree: FileTreeComponent::new(
                 "",
                 false,
@@ -76,13 +80,16 @@ impl CommitDetailsComponent {
         tags: Option<CommitTags>,
     ) -> Result<()> {
         self.details.set_commit(id, tags)?;
+        self.file_tree.set_commit(id);
 
         if let Some(id) = id {
-            if let Some((fetched_id, res)) =
+            if let Some((fetched_id, (files, stats))) =
                 self.git_commit_files.current()?
             {
-                if fetched_id == id {
-                    self.file_tree.update(res.as_slice())?;
+                if fetched_id == (id, self.parent).into() {
+                    self.file_tree.update(files.as_slice())?;
+                    self.details.set_stats(Some(&stats));
+                    self.file_tree.set_stats(stats);
                     self.file_tree.set_title(self.get_files_title());
 
                     return Ok(());
@@ -90,7 +97,7 @@ impl CommitDetailsComponent {
             }
 
             self.file_tree.clear()?;
-            self.git_commit_files.fetch(id)?;
+            self.git_commit_files.fetch((id, self.parent).into())?;
         }
 
         self.file_tree.set_title(self.get_files_title());
@@ -98,6 +105,23 @@ impl CommitDetailsComponent {
         Ok(())
     }
 
+    /// parent(s) the files of the next `set_commit` are diffed
+    /// against
+    pub fn set_parent(&mut self, parent: DiffParent) {
+        self.parent = parent;
+        self.details.set_parent(parent);
+    }
+
+    ///
+    pub const fn parent(&self) -> DiffParent {
+        self.parent
+    }
+
+    ///
+    pub fn parents(&self) -> &[CommitId] {
+        self.details.parents()
+    }
+
     ///
     pub fn any_work_pending(&self) -> bool {
         self.git_commit_files.is_pending()
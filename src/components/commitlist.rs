@@ -1,16 +1,23 @@
-use super::utils::logitems::{ItemBatch, LogEntry};
+use super::utils::{
+    logitems::{ItemBatch, LogEntry},
+    time_to_string_fmt,
+};
 use crate::{
     components::{
         CommandBlocking, CommandInfo, Component, DrawableComponent,
         ScrollType,
     },
     keys::SharedKeyConfig,
+    options::SharedOptions,
     strings,
     ui::calc_scroll_top,
     ui::style::{SharedTheme, Theme},
 };
 use anyhow::Result;
-use asyncgit::sync::Tags;
+use asyncgit::sync::{
+    CommitId, CommitRef, CommitRefs, GraphCell, GraphRow, Notes,
+    Refs, Tags,
+};
 use crossterm::event::Event;
 use std::{
     borrow::Cow, cell::Cell, cmp, convert::TryFrom, time::Instant,
@@ -25,20 +32,35 @@ use tui::{
 use unicode_width::UnicodeWidthStr;
 
 const ELEMENTS_PER_LINE: usize = 10;
+const GRAPH_LANE_WIDTH: usize = 2;
+const MARKER_WIDTH: usize = 2;
+
+struct LogSearch {
+    query: String,
+    /// sorted indices of matching commits
+    matches: Vec<usize>,
+}
 
 ///
 pub struct CommitList {
     title: String,
     selection: usize,
     branch: Option<String>,
+    search: Option<LogSearch>,
     count_total: usize,
     items: ItemBatch,
     scroll_state: (Instant, f32),
     tags: Option<Tags>,
+    refs: Option<Refs>,
+    /// commits with a git note
+    notes: Option<Notes>,
+    /// commits marked in the order they got marked
+    marked: Vec<CommitId>,
     current_size: Cell<(u16, u16)>,
     scroll_top: Cell<usize>,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
 }
 
 impl CommitList {
@@ -47,18 +69,24 @@ impl CommitList {
         title: &str,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             items: ItemBatch::default(),
             selection: 0,
             branch: None,
+            search: None,
             count_total: 0,
             scroll_state: (Instant::now(), 0_f32),
             tags: None,
+            refs: None,
+            notes: None,
+            marked: Vec::new(),
             current_size: Cell::new((0, 0)),
             scroll_top: Cell::new(0),
             theme,
             key_config,
+            options,
             title: String::from(title),
         }
     }
@@ -68,11 +96,88 @@ impl CommitList {
         &mut self.items
     }
 
+    ///
+    pub fn set_title(&mut self, title: String) {
+        self.title = title;
+    }
+
     ///
     pub fn set_branch(&mut self, name: Option<String>) {
         self.branch = name;
     }
 
+    /// sets the active search and its matches (sorted indices)
+    pub fn set_search(
+        &mut self,
+        query: Option<String>,
+        matches: Vec<usize>,
+    ) {
+        self.search = query.map(|query| LogSearch { query, matches });
+    }
+
+    ///
+    pub fn has_search_matches(&self) -> bool {
+        self.search
+            .as_ref()
+            .map_or(false, |search| !search.matches.is_empty())
+    }
+
+    /// selects the first match after the selection (or at it if
+    /// `inclusive`), wrapping around at the end
+    pub fn select_next_match(&mut self, inclusive: bool) -> bool {
+        let selection = self.selection;
+        let next = self.search.as_ref().and_then(|search| {
+            search
+                .matches
+                .iter()
+                .find(|idx| {
+                    **idx > selection
+                        || (inclusive && **idx == selection)
+                })
+                .or_else(|| search.matches.first())
+                .copied()
+        });
+
+        self.select_match(next)
+    }
+
+    /// selects the last match before the selection, wrapping around
+    pub fn select_prev_match(&mut self) -> bool {
+        let selection = self.selection;
+        let prev = self.search.as_ref().and_then(|search| {
+            search
+                .matches
+                .iter()
+                .rev()
+                .find(|idx| **idx < selection)
+                .or_else(|| search.matches.last())
+                .copied()
+        });
+
+        self.select_match(prev)
+    }
+
+    /// selects the entry at `idx` if it exists
+    pub fn select(&mut self, idx: usize) -> bool {
+        self.select_match(Some(idx))
+    }
+
+    fn select_match(&mut self, idx: Option<usize>) -> bool {
+        match idx {
+            Some(idx) if idx <= self.selection_max() => {
+                self.selection = idx;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_search_match(&self, idx: usize) -> bool {
+        self.search.as_ref().map_or(false, |search| {
+            search.matches.binary_search(&idx).is_ok()
+        })
+    }
+
     ///
     pub const fn selection(&self) -> usize {
         self.selection
@@ -111,6 +216,42 @@ impl CommitList {
         self.tags = Some(tags);
     }
 
+    ///
+    pub fn set_notes(&mut self, notes: Notes) {
+        self.notes = Some(notes);
+    }
+
+    ///
+    pub fn set_refs(&mut self, refs: Refs) {
+        self.refs = Some(refs);
+    }
+
+    ///
+    pub fn marked(&self) -> &[CommitId] {
+        &self.marked
+    }
+
+    ///
+    pub fn clear_marked(&mut self) {
+        self.marked.clear();
+    }
+
+    /// marks the selected commit or unmarks it if already marked
+    pub fn toggle_marked(&mut self) -> bool {
+        if let Some(id) = self.selected_entry().map(|e| e.id) {
+            if let Some(idx) =
+                self.marked.iter().position(|m| m == &id)
+            {
+                self.marked.remove(idx);
+            } else {
+                self.marked.push(id);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
     ///
     pub fn selected_entry(&self) -> Option<&LogEntry> {
         self.items.iter().nth(
@@ -189,19 +330,61 @@ impl CommitList {
     }
 
     fn get_entry_to_add<'a>(
+        &self,
         e: &'a LogEntry,
+        time: String,
         selected: bool,
-        tags: Option<String>,
-        theme: &Theme,
         width: usize,
+        graph_lanes: usize,
+        search_match: bool,
     ) -> Spans<'a> {
-        let mut txt: Vec<Span> = Vec::new();
-        txt.reserve(ELEMENTS_PER_LINE);
+        let theme = &self.theme;
+        let refs: Option<&CommitRefs> =
+            self.refs.as_ref().and_then(|refs| refs.get(&e.id));
+        let tags = self
+            .tags
+            .as_ref()
+            .and_then(|t| t.get(&e.id))
+            .map(|tags| tags.join(" "));
+
+        let mut txt: Vec<Span> =
+            Vec::with_capacity(ELEMENTS_PER_LINE + graph_lanes * 2);
 
         let splitter_txt = Cow::from(" ");
         let splitter =
             Span::styled(splitter_txt, theme.text(true, selected));
 
+        // marked commits
+        if !self.marked.is_empty() {
+            txt.push(Span::styled(
+                Cow::from(if self.marked.contains(&e.id) {
+                    "\u{2713} " //✓
+                } else {
+                    "  "
+                }),
+                theme.text(true, selected),
+            ));
+        }
+
+        // commit graph
+        if graph_lanes > 0 {
+            Self::add_graph(
+                &mut txt,
+                e.graph.as_ref(),
+                graph_lanes,
+                selected,
+                theme,
+            );
+        }
+
+        let width = width
+            .saturating_sub(graph_lanes * GRAPH_LANE_WIDTH)
+            .saturating_sub(if self.marked.is_empty() {
+                0
+            } else {
+                MARKER_WIDTH
+            });
+
         // commit hash
         txt.push(Span::styled(
             Cow::from(e.hash_short.as_str()),
@@ -210,16 +393,20 @@ impl CommitList {
 
         txt.push(splitter.clone());
 
+        let time_width = time.width();
+
         // commit timestamp
         txt.push(Span::styled(
-            Cow::from(e.time.as_str()),
+            Cow::from(time),
             theme.commit_time(selected),
         ));
 
         txt.push(splitter.clone());
 
-        let author_width =
-            (width.saturating_sub(19) / 3).max(3).min(20);
+        let author_width = (width
+            .saturating_sub(e.hash_short.len() + time_width + 2)
+            / 3)
+        .clamp(3, 20);
         let author = string_width_align(&e.author, author_width);
 
         // commit author
@@ -230,6 +417,14 @@ impl CommitList {
 
         txt.push(splitter.clone());
 
+        // branches and HEAD
+        for commit_ref in refs.into_iter().flatten() {
+            txt.push(Span::styled(
+                Cow::from(format!(" {}", ref_name(commit_ref))),
+                theme.commit_ref(commit_ref, selected),
+            ));
+        }
+
         // commit tags
         txt.push(Span::styled(
             Cow::from(if let Some(tags) = tags {
@@ -240,45 +435,134 @@ impl CommitList {
             theme.tags(selected),
         ));
 
+        // commits with a note
+        if self
+            .notes
+            .as_ref()
+            .map_or(false, |notes| notes.contains(&e.id))
+        {
+            txt.push(Span::styled(
+                Cow::from(" \u{270e}"), //✎
+                theme.tags(selected),
+            ));
+        }
+
         txt.push(splitter);
 
         // commit msg
         txt.push(Span::styled(
             Cow::from(e.msg.as_str()),
-            theme.text(true, selected),
+            if search_match {
+                theme.search_match(selected)
+            } else {
+                theme.text(true, selected)
+            },
         ));
         Spans::from(txt)
     }
 
+    fn add_graph(
+        txt: &mut Vec<Span>,
+        row: Option<&GraphRow>,
+        lanes: usize,
+        selected: bool,
+        theme: &Theme,
+    ) {
+        let cells = row.map_or(&[][..], |row| row.cells.as_slice());
+        let commit_lane = row.and_then(GraphRow::commit_lane);
+
+        for lane in 0..lanes {
+            let cell = cells.get(lane).copied().unwrap_or_default();
+
+            txt.push(Span::styled(
+                Cow::from(graph_symbol(cell).to_string()),
+                theme.commit_graph(lane, selected),
+            ));
+
+            // horizontal lines take the color of the outer lane
+            let connector_lane = match commit_lane {
+                Some(commit_lane) if lane >= commit_lane => lane + 1,
+                _ => lane,
+            };
+
+            txt.push(Span::styled(
+                Cow::from(if cell.right { "\u{2500}" } else { " " }),
+                theme.commit_graph(connector_lane, selected),
+            ));
+        }
+    }
+
+    fn graph_lanes(&self, height: usize, width: usize) -> usize {
+        self.items
+            .iter()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .filter_map(|e| e.graph.as_ref())
+            .map(|row| row.cells.len())
+            .max()
+            .unwrap_or_default()
+            .min(width / 2 / GRAPH_LANE_WIDTH)
+    }
+
     fn get_text(&self, height: usize, width: usize) -> Vec<Spans> {
         let selection = self.relative_selection();
 
         let mut txt: Vec<Spans> = Vec::with_capacity(height);
 
-        for (idx, e) in self
+        let graph_lanes = self.graph_lanes(height, width);
+
+        let times = self.visible_times(height);
+        // formats like relative dates differ in width
+        let time_width = times
+            .iter()
+            .map(|time| time.width())
+            .max()
+            .unwrap_or_default();
+
+        for ((idx, e), time) in self
             .items
             .iter()
             .skip(self.scroll_top.get())
             .take(height)
             .enumerate()
+            .zip(times)
         {
-            let tags = self
-                .tags
-                .as_ref()
-                .and_then(|t| t.get(&e.id))
-                .map(|tags| tags.join(" "));
-            txt.push(Self::get_entry_to_add(
+            let idx = idx + self.scroll_top.get();
+            txt.push(self.get_entry_to_add(
                 e,
-                idx + self.scroll_top.get() == selection,
-                tags,
-                &self.theme,
+                string_width_align(&time, time_width),
+                idx == selection,
                 width,
+                graph_lanes,
+                self.is_search_match(idx + self.items.index_offset()),
             ));
         }
 
         txt
     }
 
+    /// dates of the visible commits in the configured format
+    fn visible_times(&self, height: usize) -> Vec<String> {
+        let options = self.options.borrow();
+
+        self.items
+            .iter()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|e| {
+                time_to_string_fmt(
+                    if options.log_author_date {
+                        e.author_time
+                    } else {
+                        e.time
+                    },
+                    true,
+                    &options.date_format,
+                )
+            })
+            .collect()
+    }
+
     #[allow(clippy::missing_const_for_fn)]
     fn relative_selection(&self) -> usize {
         self.selection.saturating_sub(self.items.index_offset())
@@ -309,12 +593,29 @@ impl DrawableComponent for CommitList {
         let branch_post_fix =
             self.branch.as_ref().map(|b| format!("- {{{}}}", b));
 
+        let search_post_fix = self.search.as_ref().map(|search| {
+            let current = search
+                .matches
+                .binary_search(&self.selection)
+                .map_or_else(
+                    |_| String::from("-"),
+                    |idx| (idx + 1).to_string(),
+                );
+            format!(
+                "- [search '{}': {}/{}]",
+                search.query,
+                current,
+                search.matches.len()
+            )
+        });
+
         let title = format!(
-            "{} {}/{} {}",
+            "{} {}/{} {} {}",
             self.title,
             self.count_total.saturating_sub(self.selection),
             self.count_total,
             branch_post_fix.as_deref().unwrap_or(""),
+            search_post_fix.as_deref().unwrap_or(""),
         );
 
         f.render_widget(
@@ -383,6 +684,40 @@ impl Component for CommitList {
     }
 }
 
+/// box drawing symbol for a single lane of the commit graph
+const fn graph_symbol(cell: GraphCell) -> char {
+    if cell.commit {
+        return '\u{25cf}'; //●
+    }
+
+    match (cell.up, cell.down, cell.left, cell.right) {
+        (false, false, false, false) => ' ',
+        (false, false, _, _) => '\u{2500}', //─
+        (_, _, false, false) => '\u{2502}', //│
+        (true, true, true, true) => '\u{253c}', //┼
+        (true, true, true, false) => '\u{2524}', //┤
+        (true, true, false, true) => '\u{251c}', //├
+        (true, false, true, true) => '\u{2534}', //┴
+        (false, true, true, true) => '\u{252c}', //┬
+        (true, false, true, false) => '\u{256f}', //╯
+        (true, false, false, true) => '\u{2570}', //╰
+        (false, true, true, false) => '\u{256e}', //╮
+        (false, true, false, true) => '\u{256d}', //╭
+    }
+}
+
+/// label of a ref like `git log --decorate` shows it
+fn ref_name(commit_ref: &CommitRef) -> Cow<'_, str> {
+    match commit_ref {
+        CommitRef::Head => Cow::from("HEAD"),
+        CommitRef::HeadBranch(name) => {
+            Cow::from(format!("HEAD -> {}", name))
+        }
+        CommitRef::LocalBranch(name)
+        | CommitRef::RemoteBranch(name) => Cow::from(name.as_str()),
+    }
+}
+
 #[inline]
 fn string_width_align(s: &str, width: usize) -> String {
     static POSTFIX: &str = "..";
@@ -410,6 +745,31 @@ fn find_truncate_point(s: &str, chars: usize) -> usize {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_graph_symbol() {
+        let merge_out = GraphCell {
+            down: true,
+            left: true,
+            ..GraphCell::default()
+        };
+        let pass_through = GraphCell {
+            up: true,
+            down: true,
+            ..GraphCell::default()
+        };
+
+        assert_eq!(graph_symbol(GraphCell::default()), ' ');
+        assert_eq!(graph_symbol(merge_out), '╮');
+        assert_eq!(graph_symbol(pass_through), '│');
+        assert_eq!(
+            graph_symbol(GraphCell {
+                commit: true,
+                ..pass_through
+            }),
+            '●'
+        );
+    }
+
     #[test]
     fn test_string_width_align() {
         assert_eq!(string_width_align("123", 3), "123");
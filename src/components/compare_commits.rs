@@ -0,0 +1,360 @@
+use super::{
+    command_pump, event_pump, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DiffComponent, DrawableComponent,
+    FileTreeComponent,
+};
+use crate::{
+    accessors, keys::SharedKeyConfig, options::SharedOptions,
+    queue::Queue, strings, ui::style::SharedTheme,
+    AsyncAppNotification,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, CommitInfo},
+    AsyncCommitFiles, AsyncDiff, AsyncNotification, DiffParams,
+    DiffType, CWD,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const MESSAGE_LENGTH: usize = 100;
+
+/// diff between the trees of two commits
+pub struct CompareCommitsComponent {
+    commit_ids: Option<(CommitId, CommitId)>,
+    commits: Vec<CommitInfo>,
+    file_tree: FileTreeComponent,
+    diff: DiffComponent,
+    git_commit_files: AsyncCommitFiles,
+    git_diff: AsyncDiff,
+    visible: bool,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CompareCommitsComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.is_visible() {
+            let percentages = if self.diff.focused() {
+                (30, 70)
+            } else {
+                (50, 50)
+            };
+
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Percentage(percentages.0),
+                        Constraint::Percentage(percentages.1),
+                    ]
+                    .as_ref(),
+                )
+                .split(rect);
+
+            let left = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [Constraint::Length(4), Constraint::Min(1)]
+                        .as_ref(),
+                )
+                .split(chunks[0]);
+
+            f.render_widget(Clear, rect);
+
+            self.draw_header(f, left[0]);
+            self.file_tree.draw(f, left[1])?;
+            self.diff.draw(f, chunks[1])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for CompareCommitsComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            command_pump(
+                out,
+                force_all,
+                self.components().as_slice(),
+            );
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::close_popup(&self.key_config),
+                    true,
+                    true,
+                )
+                .order(1),
+            );
+
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_right(&self.key_config),
+                self.can_focus_diff(),
+                !self.diff.focused() || force_all,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_left(&self.key_config),
+                true,
+                self.diff.focused() || force_all,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if event_pump(ev, self.components_mut().as_mut_slice())? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.focus_right
+                    && self.can_focus_diff()
+                {
+                    self.file_tree.focus(false);
+                    self.diff.focus(true);
+                } else if e == self.key_config.focus_left
+                    && self.diff.focused()
+                {
+                    self.file_tree.focus(true);
+                    self.diff.focus(false);
+                } else if e == self.key_config.focus_left {
+                    self.hide();
+                }
+
+                // stop key event propagation
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.file_tree.focus(true);
+        self.diff.focus(false);
+        self.update()?;
+        Ok(())
+    }
+}
+
+impl CompareCommitsComponent {
+    accessors!(self, [file_tree, diff]);
+
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        sender_app: &Sender<AsyncAppNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+        options: SharedOptions,
+    ) -> Self {
+        Self {
+            file_tree: FileTreeComponent::new(
+                "",
+                true,
+                Some(queue.clone()),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            diff: DiffComponent::new(
+                sender_app,
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+                options,
+                true,
+            ),
+            commit_ids: None,
+            commits: Vec::new(),
+            git_commit_files: AsyncCommitFiles::new(sender),
+            git_diff: AsyncDiff::new(sender),
+            visible: false,
+            theme,
+            key_config,
+        }
+    }
+
+    /// compares the tree of `old` to the tree of `new`
+    pub fn open(
+        &mut self,
+        old: CommitId,
+        new: CommitId,
+    ) -> Result<()> {
+        self.commits =
+            sync::get_commits_info(CWD, &[old, new], MESSAGE_LENGTH)?;
+        self.commit_ids = Some((old, new));
+        self.file_tree.set_commit(Some(new));
+        self.show()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_diff.is_pending()
+            || self.git_commit_files.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.is_visible() {
+            match ev {
+                AsyncNotification::CommitFiles => self.update()?,
+                AsyncNotification::Diff => self.update_diff()?,
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    pub fn update_app(&mut self, ev: AsyncAppNotification) {
+        if self.is_visible() {
+            match ev {
+                AsyncAppNotification::SyntaxHighlighting => {
+                    self.diff.update_syntax();
+                }
+            }
+        }
+    }
+
+    /// called when any tree component changed selection
+    pub fn update_diff(&mut self) -> Result<()> {
+        if self.is_visible() {
+            if let Some(ids) = self.commit_ids {
+                if let Some(f) = self.file_tree.selection_file() {
+                    let diff_params = DiffParams {
+                        path: f.path.clone(),
+                        diff_type: DiffType::Commits(ids),
+                    };
+
+                    if let Some((params, last)) =
+                        self.git_diff.last()?
+                    {
+                        if params == diff_params {
+                            self.diff.update(f.path, false, last)?;
+                            return Ok(());
+                        }
+                    }
+
+                    self.git_diff.request(diff_params)?;
+                    self.diff.clear(true)?;
+                    return Ok(());
+                }
+            }
+
+            self.diff.clear(false)?;
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) -> Result<()> {
+        if let Some(ids) = self.commit_ids {
+            match self.git_commit_files.current()? {
+                Some((params, (files, stats)))
+                    if params == ids.into() =>
+                {
+                    self.file_tree.update(files.as_slice())?;
+                    self.file_tree.set_stats(stats);
+                }
+                _ => {
+                    self.file_tree.clear()?;
+                    self.git_commit_files.fetch(ids.into())?;
+                }
+            }
+        }
+
+        self.file_tree.set_title(format!(
+            "{} {}",
+            strings::commit::details_files_title(&self.key_config),
+            self.file_tree.file_count()
+        ));
+
+        self.update_diff()?;
+
+        Ok(())
+    }
+
+    fn can_focus_diff(&self) -> bool {
+        self.file_tree.selection_file().is_some()
+    }
+
+    fn draw_header<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let labels = [
+            strings::compare_commits_old(&self.key_config),
+            strings::compare_commits_new(&self.key_config),
+        ];
+
+        let text = self
+            .commits
+            .iter()
+            .zip(labels.iter())
+            .map(|(commit, label)| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{} ", label),
+                        self.theme.text(false, false),
+                    ),
+                    Span::styled(
+                        commit.id.get_short_string(),
+                        self.theme.commit_hash(false),
+                    ),
+                    Span::styled(
+                        format!(" {}", commit.message),
+                        self.theme.text(true, false),
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        f.render_widget(
+            Paragraph::new(text).block(
+                Block::default()
+                    .title(Span::styled(
+                        strings::compare_commits_title(
+                            &self.key_config,
+                        ),
+                        self.theme.title(false),
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(self.theme.block(false)),
+            ),
+            rect,
+        );
+    }
+}
@@ -9,12 +9,16 @@ use crate::{
     ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::{sync, CWD};
+use asyncgit::{
+    sync::{self, CommitId},
+    CWD,
+};
 use crossterm::event::Event;
 use tui::{backend::Backend, layout::Rect, Frame};
 
 pub struct CreateBranchComponent {
     input: TextInputComponent,
+    commit: Option<CommitId>,
     queue: Queue,
     key_config: SharedKeyConfig,
 }
@@ -100,12 +104,23 @@ impl CreateBranchComponent {
                 &strings::create_branch_popup_msg(&key_config),
                 true,
             ),
+            commit: None,
             key_config,
         }
     }
 
     ///
     pub fn open(&mut self) -> Result<()> {
+        self.commit = None;
+        self.show()?;
+
+        Ok(())
+    }
+
+    /// creates the branch at `commit` instead of `HEAD` and stays
+    /// on the current branch
+    pub fn open_at(&mut self, commit: CommitId) -> Result<()> {
+        self.commit = Some(commit);
         self.show()?;
 
         Ok(())
@@ -113,8 +128,11 @@ impl CreateBranchComponent {
 
     ///
     pub fn create_branch(&mut self) {
-        let res =
-            sync::create_branch(CWD, self.input.get_text().as_str());
+        let name = self.input.get_text().as_str();
+        let res = self.commit.map_or_else(
+            || sync::create_branch(CWD, name),
+            |commit| sync::create_branch_at(CWD, name, commit),
+        );
 
         self.input.clear();
         self.hide();
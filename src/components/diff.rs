@@ -4,9 +4,14 @@ use super::{
 use crate::{
     components::{CommandInfo, Component},
     keys::SharedKeyConfig,
+    options::SharedOptions,
     queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
     strings, try_or_popup,
-    ui::{self, calc_scroll_top, style::SharedTheme},
+    ui::{
+        self, calc_scroll_top, style::SharedTheme, AsyncSyntaxJob,
+        SyntaxLine, SyntaxText,
+    },
+    AsyncAppNotification,
 };
 use anyhow::Result;
 use asyncgit::{
@@ -15,16 +20,21 @@ use asyncgit::{
     DiffLine, DiffLineType, FileDiff, CWD,
 };
 use bytesize::ByteSize;
+use crossbeam_channel::Sender;
 use crossterm::event::Event;
-use std::{borrow::Cow, cell::Cell, cmp, path::Path};
+use std::{
+    borrow::Cow, cell::Cell, cmp, ops::Range, path::Path, sync::Arc,
+};
 use tui::{
     backend::Backend,
     layout::Rect,
+    style::Style,
     symbols,
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use unicode_truncate::UnicodeTruncateStr;
 
 #[derive(Default)]
 struct Current {
@@ -33,6 +43,89 @@ struct Current {
     hash: u64,
 }
 
+/// row of the side-by-side view, the line indices of its old
+/// (left) and new (right) side, `None` leaves a side empty
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SplitRow {
+    old: Option<usize>,
+    new: Option<usize>,
+}
+
+/// context lines are shown on both sides, deleted lines are paired
+/// with the lines added after them, hunk headers span both sides
+fn split_rows(diff: &FileDiff) -> Vec<SplitRow> {
+    let mut res = Vec::with_capacity(diff.lines);
+    let mut offset = 0;
+
+    for hunk in &diff.hunks {
+        let lines = &hunk.lines;
+
+        let mut idx = 0;
+        while idx < lines.len() {
+            let deleted = lines[idx..]
+                .iter()
+                .take_while(|l| l.line_type == DiffLineType::Delete)
+                .count();
+            let added = lines[idx + deleted..]
+                .iter()
+                .take_while(|l| l.line_type == DiffLineType::Add)
+                .count();
+
+            if deleted + added == 0 {
+                res.push(SplitRow {
+                    old: Some(offset + idx),
+                    new: Some(offset + idx),
+                });
+                idx += 1;
+                continue;
+            }
+
+            for pair in 0..deleted.max(added) {
+                res.push(SplitRow {
+                    old: if pair < deleted {
+                        Some(offset + idx + pair)
+                    } else {
+                        None
+                    },
+                    new: if pair < added {
+                        Some(offset + idx + deleted + pair)
+                    } else {
+                        None
+                    },
+                });
+            }
+
+            idx += deleted + added;
+        }
+
+        offset += lines.len();
+    }
+
+    res
+}
+
+/// line indices in the order the side-by-side view shows them: row
+/// by row, the old before the new line of a row
+fn split_order(rows: &[SplitRow]) -> Vec<usize> {
+    rows.iter()
+        .flat_map(|row| {
+            let new = row.new.filter(|new| row.old != Some(*new));
+            row.old.into_iter().chain(new)
+        })
+        .collect()
+}
+
+/// position of each line index in `order` (see `split_order`)
+fn split_positions(order: &[usize]) -> Vec<usize> {
+    let mut res = vec![0; order.len()];
+    for (position, index) in order.iter().enumerate() {
+        if let Some(entry) = res.get_mut(*index) {
+            *entry = position;
+        }
+    }
+    res
+}
+
 ///
 #[derive(Clone, Copy)]
 enum Selection {
@@ -106,18 +199,26 @@ pub struct DiffComponent {
     focused: bool,
     current: Current,
     scroll_top: Cell<usize>,
+    split_rows: Vec<SplitRow>,
+    split_order: Vec<usize>,
+    split_positions: Vec<usize>,
+    syntax: Option<Arc<SyntaxText>>,
+    syntax_job: AsyncSyntaxJob,
     queue: Queue,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
     is_immutable: bool,
 }
 
 impl DiffComponent {
     ///
     pub fn new(
+        sender: &Sender<AsyncAppNotification>,
         queue: Queue,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
         is_immutable: bool,
     ) -> Self {
         Self {
@@ -130,8 +231,14 @@ impl DiffComponent {
             current_size: Cell::new((0, 0)),
             selection: Selection::Single(0),
             scroll_top: Cell::new(0),
+            split_rows: Vec::new(),
+            split_order: Vec::new(),
+            split_positions: Vec::new(),
+            syntax: None,
+            syntax_job: AsyncSyntaxJob::new(sender),
             theme,
             key_config,
+            options,
             is_immutable,
         }
     }
@@ -150,6 +257,10 @@ impl DiffComponent {
     pub fn clear(&mut self, pending: bool) -> Result<()> {
         self.current = Current::default();
         self.diff = None;
+        self.split_rows.clear();
+        self.split_order.clear();
+        self.split_positions.clear();
+        self.syntax = None;
         self.scroll_top.set(0);
         self.selection = Selection::Single(0);
         self.selected_hunk = None;
@@ -177,7 +288,11 @@ impl DiffComponent {
                 hash,
             };
 
+            self.split_rows = split_rows(&diff);
+            self.split_order = split_order(&self.split_rows);
+            self.split_positions = split_positions(&self.split_order);
             self.diff = Some(diff);
+            self.update_syntax();
 
             if reset_selection {
                 self.scroll_top.set(0);
@@ -195,8 +310,32 @@ impl DiffComponent {
         Ok(())
     }
 
-    fn move_selection(&mut self, move_type: ScrollType) {
+    /// picks up the syntax highlighting of the current diff once the
+    /// background job finished, requests it if not cached yet
+    pub fn update_syntax(&mut self) {
         if let Some(diff) = &self.diff {
+            let key = AsyncSyntaxJob::key(
+                &self.current.path,
+                self.current.hash,
+            );
+
+            self.syntax = self.syntax_job.get(key);
+
+            if self.syntax.is_none() {
+                self.syntax_job.request(
+                    key,
+                    self.current.path.clone(),
+                    diff.clone(),
+                    self.theme.syntax().to_string(),
+                );
+            }
+        }
+    }
+
+    fn move_selection(&mut self, move_type: ScrollType) {
+        if self.side_by_side() && !self.split_rows.is_empty() {
+            self.move_split_selection(move_type);
+        } else if let Some(diff) = &self.diff {
             let max = diff.lines.saturating_sub(1) as usize;
 
             let new_start = match move_type {
@@ -226,6 +365,38 @@ impl DiffComponent {
         }
     }
 
+    /// moves through the rows of the side-by-side view instead of
+    /// the line indices which jump between both sides
+    fn move_split_selection(&mut self, move_type: ScrollType) {
+        let order = &self.split_order;
+        let (top, bottom) = self.split_selection_range();
+        let page =
+            self.current_size.get().1.saturating_sub(1) as usize;
+        let first_of_row = |row: usize| {
+            let row =
+                self.split_rows[row.min(self.split_rows.len() - 1)];
+            row.old.or(row.new).unwrap_or_default()
+        };
+
+        let new_start = match move_type {
+            ScrollType::Down => {
+                order[(bottom + 1).min(order.len() - 1)]
+            }
+            ScrollType::Up => order[top.saturating_sub(1)],
+            ScrollType::Home => order[0],
+            ScrollType::End => order[order.len() - 1],
+            ScrollType::PageDown => first_of_row(
+                self.split_row_of(self.selection.get_bottom()) + page,
+            ),
+            ScrollType::PageUp => first_of_row(
+                self.split_row_of(self.selection.get_top())
+                    .saturating_sub(page),
+            ),
+        };
+
+        self.update_selection(new_start);
+    }
+
     fn update_selection(&mut self, new_start: usize) {
         if let Some(diff) = &self.diff {
             let max = diff.lines.saturating_sub(1) as usize;
@@ -242,8 +413,53 @@ impl DiffComponent {
             .map_or(0, |diff| diff.lines.saturating_sub(1))
     }
 
+    /// positions (see `split_order`) of the first and last selected
+    /// line of the side-by-side view
+    fn split_selection_range(&self) -> (usize, usize) {
+        let position = |index: usize| {
+            self.split_positions
+                .get(index)
+                .copied()
+                .unwrap_or_default()
+        };
+        let start = position(self.selection.get_start());
+        let end = position(self.selection.get_end());
+
+        (start.min(end), start.max(end))
+    }
+
+    /// the side-by-side view selects the lines it shows between the
+    /// ends of the selection, not the line indices between them
+    fn is_selected(&self, index: usize) -> bool {
+        if self.side_by_side() && !self.split_positions.is_empty() {
+            let (top, bottom) = self.split_selection_range();
+            self.split_positions
+                .get(index)
+                .map_or(false, |pos| top <= *pos && *pos <= bottom)
+        } else {
+            self.selection.contains(index)
+        }
+    }
+
     fn modify_selection(&mut self, direction: Direction) {
-        if let Some(diff) = &self.diff {
+        if self.side_by_side() && !self.split_order.is_empty() {
+            let order = &self.split_order;
+            let end = self
+                .split_positions
+                .get(self.selection.get_end())
+                .copied()
+                .unwrap_or_default();
+
+            let end = match direction {
+                Direction::Up => order[end.saturating_sub(1)],
+                Direction::Down => {
+                    order[(end + 1).min(order.len() - 1)]
+                }
+            };
+
+            self.selection =
+                Selection::Multiple(self.selection.get_start(), end);
+        } else if let Some(diff) = &self.diff {
             let max = diff.lines.saturating_sub(1);
 
             self.selection.modify(direction, max);
@@ -258,7 +474,7 @@ impl DiffComponent {
                 .flat_map(|hunk| hunk.lines.iter())
                 .enumerate()
                 .filter_map(|(i, line)| {
-                    if self.selection.contains(i) {
+                    if self.is_selected(i) {
                         Some(
                             line.content
                                 .trim_matches(|c| {
@@ -305,7 +521,7 @@ impl DiffComponent {
         None
     }
 
-    fn get_text(&self, width: u16, height: u16) -> Vec<Spans> {
+    fn get_text(&self, width: u16, height: u16) -> Vec<Spans<'_>> {
         let mut res: Vec<Spans> = Vec::new();
         if let Some(diff) = &self.diff {
             if diff.hunks.is_empty() {
@@ -385,6 +601,9 @@ impl DiffComponent {
                                             .contains(line_cursor),
                                     hunk_selected,
                                     i == hunk_len as usize - 1,
+                                    self.syntax.as_ref().and_then(
+                                        |s| s.line(line_cursor),
+                                    ),
                                     &self.theme,
                                 ));
                                 lines_added += 1;
@@ -407,6 +626,7 @@ impl DiffComponent {
         selected: bool,
         selected_hunk: bool,
         end_of_hunk: bool,
+        syntax: Option<&SyntaxLine>,
         theme: &SharedTheme,
     ) -> Spans<'a> {
         let style = theme.diff_hunk_marker(selected_hunk);
@@ -429,6 +649,18 @@ impl DiffComponent {
         let trimmed =
             line.content.trim_matches(|c| c == '\n' || c == '\r');
 
+        if syntax.is_some() || !line.changes.is_empty() {
+            return Self::get_styled_line_to_add(
+                width,
+                left_side_of_line,
+                trimmed,
+                line,
+                syntax,
+                selected,
+                theme,
+            );
+        }
+
         let filled = if selected {
             // selected line
             format!("{:w$}\n", trimmed, w = width as usize)
@@ -448,6 +680,279 @@ impl DiffComponent {
         ])
     }
 
+    /// line split into spans by its syntax highlighting and the
+    /// changed words (`DiffLine::changes`)
+    fn get_styled_line_to_add<'a>(
+        width: u16,
+        left_side_of_line: Span<'a>,
+        trimmed: &'a str,
+        line: &DiffLine,
+        syntax: Option<&SyntaxLine>,
+        selected: bool,
+        theme: &SharedTheme,
+    ) -> Spans<'a> {
+        let highlighted = syntax.is_some();
+
+        let mut spans = vec![left_side_of_line];
+        spans.extend(Self::get_content_spans(
+            trimmed, line, syntax, selected, theme,
+        ));
+
+        // the background of selected and (when highlighted) changed
+        // lines spans the whole width
+        let fill = if selected
+            || (highlighted && line.line_type != DiffLineType::None)
+        {
+            (width as usize).saturating_sub(trimmed.chars().count())
+        } else {
+            0
+        };
+        spans.push(Span::styled(
+            Cow::from(format!("{:w$}\n", "", w = fill)),
+            Self::line_style(
+                line.line_type,
+                highlighted,
+                selected,
+                false,
+                theme,
+            ),
+        ));
+
+        Spans::from(spans)
+    }
+
+    /// content of `line` split by its syntax highlighting and the
+    /// changed words (`DiffLine::changes`)
+    fn get_content_spans<'a>(
+        trimmed: &str,
+        line: &DiffLine,
+        syntax: Option<&SyntaxLine>,
+        selected: bool,
+        theme: &SharedTheme,
+    ) -> Vec<Span<'a>> {
+        let highlighted = syntax.is_some();
+
+        let plain = [(Style::default(), 0..trimmed.len())];
+        let syntax = syntax.map_or(&plain[..], |s| &s[..]);
+
+        let mut spans = Vec::new();
+        for (style, range) in syntax {
+            for (range, emphasized) in
+                split_changes(range.clone(), &line.changes)
+            {
+                if let Some(text) = trimmed.get(range) {
+                    spans.push(Span::styled(
+                        Cow::from(text.replace('\t', "  ")),
+                        Self::line_style(
+                            line.line_type,
+                            highlighted,
+                            selected,
+                            emphasized,
+                            theme,
+                        )
+                        .patch(*style),
+                    ));
+                }
+            }
+        }
+
+        spans
+    }
+
+    fn line_style(
+        line_type: DiffLineType,
+        highlighted: bool,
+        selected: bool,
+        emphasized: bool,
+        theme: &SharedTheme,
+    ) -> Style {
+        if highlighted {
+            theme.diff_line_bg(line_type, selected, emphasized)
+        } else if emphasized {
+            theme.diff_line_emphasis(line_type, selected)
+        } else {
+            theme.diff_line(line_type, selected)
+        }
+    }
+
+    fn side_by_side(&self) -> bool {
+        self.options.borrow().diff_side_by_side
+    }
+
+    /// row of the side-by-side view showing the line at `index`
+    fn split_row_of(&self, index: usize) -> usize {
+        self.split_rows
+            .iter()
+            .position(|row| {
+                row.old == Some(index) || row.new == Some(index)
+            })
+            .unwrap_or_default()
+    }
+
+    fn get_split_text(
+        &self,
+        width: u16,
+        height: u16,
+    ) -> Vec<Spans<'_>> {
+        let mut res: Vec<Spans> = Vec::new();
+        let diff = match &self.diff {
+            Some(diff) if !diff.hunks.is_empty() => diff,
+            _ => return self.get_text(width, height),
+        };
+
+        // hunk index and line of every line index
+        let lines: Vec<(usize, &DiffLine)> = diff
+            .hunks
+            .iter()
+            .enumerate()
+            .flat_map(|(i, hunk)| {
+                hunk.lines.iter().map(move |l| (i, l))
+            })
+            .collect();
+
+        let digits = lines
+            .iter()
+            .filter_map(|(_, l)| {
+                l.position.old_lineno.max(l.position.new_lineno)
+            })
+            .max()
+            .unwrap_or_default()
+            .to_string()
+            .len();
+
+        // one column separates both sides
+        let sides_width = width.saturating_sub(1) as usize;
+        let left_width = sides_width / 2;
+
+        for row in self
+            .split_rows
+            .iter()
+            .skip(self.scroll_top.get())
+            .take(height as usize)
+        {
+            let cell = |index: Option<usize>, old: bool, width| {
+                self.get_split_cell(
+                    index.map(|i| (i, lines[i].0, lines[i].1)),
+                    old,
+                    width,
+                    digits,
+                )
+            };
+
+            match row.old.map(|i| lines[i]) {
+                Some((hunk, line))
+                    if line.line_type == DiffLineType::Header =>
+                {
+                    let index = row.old.unwrap_or_default();
+                    res.push(Self::get_line_to_add(
+                        width,
+                        line,
+                        self.focused() && self.is_selected(index),
+                        self.focused()
+                            && self.selected_hunk == Some(hunk),
+                        false,
+                        None,
+                        &self.theme,
+                    ));
+                }
+                _ => {
+                    let mut spans = cell(row.old, true, left_width);
+                    spans.push(Span::styled(
+                        Cow::from(symbols::line::VERTICAL),
+                        self.theme.diff_hunk_marker(false),
+                    ));
+                    spans.extend(cell(
+                        row.new,
+                        false,
+                        sides_width - left_width,
+                    ));
+                    res.push(Spans::from(spans));
+                }
+            }
+        }
+
+        res
+    }
+
+    /// one side of a side-by-side row: line number and content
+    /// truncated or filled to `width`
+    fn get_split_cell<'a>(
+        &self,
+        line: Option<(usize, usize, &'a DiffLine)>,
+        old: bool,
+        width: usize,
+        digits: usize,
+    ) -> Vec<Span<'a>> {
+        let (index, hunk, line) = match line {
+            Some(line) => line,
+            None => {
+                return vec![Span::raw(Cow::from(" ".repeat(width)))]
+            }
+        };
+
+        let selected = self.focused() && self.is_selected(index);
+        let hunk_selected =
+            self.focused() && self.selected_hunk == Some(hunk);
+        let syntax = self.syntax.as_ref().and_then(|s| s.line(index));
+
+        let lineno = if old {
+            line.position.old_lineno
+        } else {
+            line.position.new_lineno
+        };
+        let gutter = format!(
+            "{:>w$} ",
+            lineno.map(|n| n.to_string()).unwrap_or_default(),
+            w = digits
+        );
+
+        let width = width.saturating_sub(gutter.len());
+        let mut spans = vec![Span::styled(
+            Cow::from(gutter),
+            self.theme.diff_hunk_marker(hunk_selected),
+        )];
+
+        let trimmed =
+            line.content.trim_matches(|c| c == '\n' || c == '\r');
+
+        let mut used = 0;
+        for span in Self::get_content_spans(
+            trimmed,
+            line,
+            syntax,
+            selected,
+            &self.theme,
+        ) {
+            let remaining = width - used;
+            if span.width() <= remaining {
+                used += span.width();
+                spans.push(span);
+            } else {
+                let (text, text_width) =
+                    span.content.unicode_truncate(remaining);
+                used += text_width;
+                spans.push(Span::styled(
+                    Cow::from(text.to_string()),
+                    span.style,
+                ));
+                break;
+            }
+        }
+
+        spans.push(Span::styled(
+            Cow::from(" ".repeat(width - used)),
+            Self::line_style(
+                line.line_type,
+                syntax.is_some(),
+                selected,
+                false,
+                &self.theme,
+            ),
+        ));
+
+        spans
+    }
+
     const fn hunk_visible(
         hunk_min: usize,
         hunk_max: usize,
@@ -568,9 +1073,7 @@ impl DiffComponent {
                         let is_add_or_delete = line.line_type
                             == DiffLineType::Add
                             || line.line_type == DiffLineType::Delete;
-                        if self.selection.contains(i)
-                            && is_add_or_delete
-                        {
+                        if self.is_selected(i) && is_add_or_delete {
                             Some(line.position)
                         } else {
                             None
@@ -605,6 +1108,39 @@ impl DiffComponent {
     }
 }
 
+/// splits `range` into the parts inside (`true`) and outside of the
+/// sorted `changes`
+fn split_changes(
+    range: Range<usize>,
+    changes: &[Range<usize>],
+) -> Vec<(Range<usize>, bool)> {
+    let mut res = Vec::new();
+    let mut start = range.start;
+
+    for change in changes {
+        if change.end <= start {
+            continue;
+        }
+        if change.start >= range.end {
+            break;
+        }
+
+        if change.start > start {
+            res.push((start..change.start, false));
+        }
+
+        let end = change.end.min(range.end);
+        res.push((start.max(change.start)..end, true));
+        start = end;
+    }
+
+    if start < range.end {
+        res.push((start..range.end, false));
+    }
+
+    res
+}
+
 impl DrawableComponent for DiffComponent {
     fn draw<B: Backend>(
         &self,
@@ -616,10 +1152,18 @@ impl DrawableComponent for DiffComponent {
             r.height.saturating_sub(2),
         ));
 
+        // side-by-side scrolls by rows instead of lines
+        let side_by_side = self.side_by_side();
+        let selected_row = if side_by_side {
+            self.split_row_of(self.selection.get_end())
+        } else {
+            self.selection.get_end()
+        };
+
         self.scroll_top.set(calc_scroll_top(
             self.scroll_top.get(),
             self.current_size.get().1 as usize,
-            self.selection.get_end(),
+            selected_row,
         ));
 
         let title = format!(
@@ -633,6 +1177,11 @@ impl DrawableComponent for DiffComponent {
                 Cow::from(strings::loading_text(&self.key_config)),
                 self.theme.text(false, false),
             )])]
+        } else if side_by_side {
+            self.get_split_text(
+                self.current_size.get().0,
+                self.current_size.get().1,
+            )
         } else {
             self.get_text(r.width, self.current_size.get().1)
         };
@@ -654,7 +1203,11 @@ impl DrawableComponent for DiffComponent {
                 f,
                 r,
                 &self.theme,
-                self.lines_count(),
+                if side_by_side {
+                    self.split_rows.len().saturating_sub(1)
+                } else {
+                    self.lines_count()
+                },
                 self.scroll_top.get(),
             );
         }
@@ -730,6 +1283,15 @@ impl Component for DiffComponent {
             self.focused,
         ));
 
+        out.push(CommandInfo::new(
+            strings::commands::diff_toggle_side_by_side(
+                &self.key_config,
+                self.side_by_side(),
+            ),
+            true,
+            self.focused,
+        ));
+
         CommandBlocking::PassingOn
     }
 
@@ -802,6 +1364,14 @@ impl Component for DiffComponent {
                 } else if e == self.key_config.copy {
                     self.copy_selection();
                     Ok(true)
+                } else if e
+                    == self.key_config.diff_toggle_side_by_side
+                {
+                    self.options
+                        .borrow_mut()
+                        .toggle_diff_side_by_side();
+                    self.scroll_top.set(0);
+                    Ok(true)
                 } else {
                     Ok(false)
                 };
@@ -818,3 +1388,89 @@ impl Component for DiffComponent {
         self.focused = focus
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_order() {
+        let row = |old, new| SplitRow { old, new };
+
+        assert_eq!(
+            split_order(&[
+                row(Some(0), Some(0)),
+                row(Some(1), Some(3)),
+                row(Some(2), None),
+                row(None, Some(4)),
+                row(Some(5), Some(5)),
+            ]),
+            vec![0, 1, 3, 2, 4, 5]
+        );
+    }
+
+    #[test]
+    fn test_split_positions() {
+        assert_eq!(
+            split_positions(&[0, 1, 3, 2, 4, 5]),
+            vec![0, 1, 3, 2, 4, 5]
+        );
+        assert_eq!(split_positions(&[2, 0, 1]), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_split_rows() {
+        let line = |line_type| DiffLine {
+            line_type,
+            ..DiffLine::default()
+        };
+        let diff = FileDiff {
+            hunks: vec![sync::diff::Hunk {
+                header_hash: 0,
+                lines: vec![
+                    line(DiffLineType::Header),
+                    line(DiffLineType::None),
+                    line(DiffLineType::Delete),
+                    line(DiffLineType::Delete),
+                    line(DiffLineType::Add),
+                    line(DiffLineType::None),
+                    line(DiffLineType::Add),
+                ],
+            }],
+            lines: 7,
+            ..FileDiff::default()
+        };
+
+        let row = |old, new| SplitRow { old, new };
+        assert_eq!(
+            split_rows(&diff),
+            vec![
+                row(Some(0), Some(0)),
+                row(Some(1), Some(1)),
+                row(Some(2), Some(4)),
+                row(Some(3), None),
+                row(Some(5), Some(5)),
+                row(None, Some(6)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_changes() {
+        assert_eq!(split_changes(0..10, &[]), vec![(0..10, false)]);
+        assert_eq!(
+            split_changes(2..10, &[0..3, 5..6, 9..12]),
+            vec![
+                (2..3, true),
+                (3..5, false),
+                (5..6, true),
+                (6..9, false),
+                (9..10, true)
+            ]
+        );
+        assert_eq!(
+            split_changes(4..5, &[0..2, 6..8]),
+            vec![(4..5, false)]
+        );
+    }
+}
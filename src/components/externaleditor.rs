@@ -3,6 +3,7 @@ use crate::{
         visibility_blocking, CommandBlocking, CommandInfo, Component,
         DrawableComponent,
     },
+    get_app_config_path,
     keys::SharedKeyConfig,
     strings,
     ui::{self, style::SharedTheme},
@@ -18,7 +19,13 @@ use crossterm::{
 };
 use scopeguard::defer;
 use std::ffi::OsStr;
-use std::{env, io, path::Path, process::Command};
+use std::{
+    env,
+    fs::File,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
 use tui::{
     backend::Backend,
     layout::Rect,
@@ -115,6 +122,47 @@ impl ExternalEditorComponent {
 
         Ok(())
     }
+
+    /// lets the user edit `msg` in a file named `file_name` in the
+    /// config dir, `help` is appended as comment lines which are
+    /// removed again from the returned message
+    pub fn edit_message(
+        file_name: &str,
+        msg: &str,
+        help: &str,
+    ) -> Result<String> {
+        //TODO: use a tmpfile here
+        let mut config_path: PathBuf = get_app_config_path()?;
+        config_path.push(file_name);
+
+        {
+            let mut file = File::create(&config_path)?;
+            file.write_fmt(format_args!("{}\n", msg))?;
+            file.write_all(help.as_bytes())?;
+        }
+
+        Self::open_file_in_editor(&config_path)?;
+
+        let mut message = String::new();
+
+        let mut file = File::open(&config_path)?;
+        file.read_to_string(&mut message)?;
+        drop(file);
+        std::fs::remove_file(&config_path)?;
+
+        let message: String = message
+            .lines()
+            .flat_map(|l| {
+                if l.starts_with('#') {
+                    vec![]
+                } else {
+                    vec![l, "\n"]
+                }
+            })
+            .collect();
+
+        Ok(message.trim().to_string())
+    }
 }
 
 impl DrawableComponent for ExternalEditorComponent {
@@ -0,0 +1,339 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, CommitList,
+    Component, DiffComponent, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig, options::SharedOptions, queue::Queue,
+    strings, ui::style::SharedTheme, AsyncAppNotification,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, FileHistoryEntry},
+    AsyncDiff, AsyncFileHistory, AsyncNotification, DiffParams,
+    DiffType, FileHistoryParams, CWD,
+};
+use crossbeam_channel::Sender;
+use crossterm::{event::Event, terminal};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::Clear,
+    Frame,
+};
+
+const SLICE_SIZE: usize = 1200;
+
+/// log of all commits changing a single file
+pub struct FileRevlogComponent {
+    path: Option<String>,
+    history: Vec<FileHistoryEntry>,
+    git_history: AsyncFileHistory,
+    git_diff: AsyncDiff,
+    list: CommitList,
+    diff: DiffComponent,
+    visible: bool,
+    key_config: SharedKeyConfig,
+}
+
+impl FileRevlogComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        sender_app: &Sender<AsyncAppNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+        options: SharedOptions,
+    ) -> Self {
+        Self {
+            path: None,
+            history: Vec::new(),
+            git_history: AsyncFileHistory::new(sender),
+            git_diff: AsyncDiff::new(sender),
+            list: CommitList::new(
+                "",
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+            ),
+            diff: DiffComponent::new(
+                sender_app,
+                queue.clone(),
+                theme,
+                key_config.clone(),
+                options,
+                true,
+            ),
+            visible: false,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, path: String) -> Result<()> {
+        self.list.set_title(strings::file_log_title(
+            &self.key_config,
+            &path,
+        ));
+        self.path = Some(path);
+        self.history.clear();
+        self.list.clear();
+        self.list.set_count_total(0);
+
+        if let Some(params) = self.params() {
+            self.git_history.fetch(params)?;
+        }
+
+        self.show()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_history.is_pending() || self.git_diff.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.is_visible() {
+            match ev {
+                AsyncNotification::FileHistory => self.update()?,
+                AsyncNotification::Diff => self.update_diff()?,
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    pub fn update_app(&mut self, ev: AsyncAppNotification) {
+        if self.is_visible() {
+            match ev {
+                AsyncAppNotification::SyntaxHighlighting => {
+                    self.diff.update_syntax();
+                }
+            }
+        }
+    }
+
+    fn params(&self) -> Option<FileHistoryParams> {
+        self.path.as_ref().map(|path| FileHistoryParams {
+            path: path.clone(),
+            follow_renames: true,
+        })
+    }
+
+    fn update(&mut self) -> Result<()> {
+        if let Some(params) = self.params() {
+            match self.git_history.current()? {
+                Some((current, history)) if current == params => {
+                    if history != self.history {
+                        self.history = history;
+                        self.list.clear();
+                    }
+                }
+                _ => {
+                    if !self.git_history.is_pending() {
+                        self.git_history.fetch(params)?;
+                    }
+                }
+            }
+
+            self.list.set_count_total(self.history.len());
+
+            let selection = self.list.selection();
+            let selection_max = self.list.selection_max();
+            if self.list.items().needs_data(selection, selection_max)
+            {
+                self.fetch_commits();
+            }
+
+            self.update_diff()?;
+        }
+
+        Ok(())
+    }
+
+    fn fetch_commits(&mut self) {
+        let want_min =
+            self.list.selection().saturating_sub(SLICE_SIZE / 2);
+
+        let ids: Vec<_> = self
+            .history
+            .iter()
+            .skip(want_min)
+            .take(SLICE_SIZE)
+            .map(|e| e.commit)
+            .collect();
+
+        // the history usually arrives before the list was drawn once
+        let width = match self.list.current_size().0 {
+            0 => terminal::size().map(|s| s.0).unwrap_or_default(),
+            width => width,
+        };
+
+        let commits = sync::get_commits_info(CWD, &ids, width.into());
+
+        if let Ok(commits) = commits {
+            self.list.items().set_items(want_min, commits);
+        }
+    }
+
+    fn selected_entry(&self) -> Option<&FileHistoryEntry> {
+        self.history.get(self.list.selection())
+    }
+
+    fn update_diff(&mut self) -> Result<()> {
+        if let Some(entry) = self.selected_entry() {
+            let path = entry.path.clone();
+            let diff_params = DiffParams {
+                path: path.clone(),
+                diff_type: DiffType::Commit(entry.commit),
+            };
+
+            if let Some((params, last)) = self.git_diff.last()? {
+                if params == diff_params {
+                    self.diff.update(path, false, last)?;
+                    return Ok(());
+                }
+            }
+
+            self.git_diff.request(diff_params)?;
+            self.diff.clear(true)?;
+            return Ok(());
+        }
+
+        self.diff.clear(self.git_history.is_pending())?;
+
+        Ok(())
+    }
+
+    fn can_focus_diff(&self) -> bool {
+        self.selected_entry().is_some()
+    }
+}
+
+impl DrawableComponent for FileRevlogComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.is_visible() {
+            let percentages = if self.diff.focused() {
+                (30, 70)
+            } else {
+                (50, 50)
+            };
+
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Percentage(percentages.0),
+                        Constraint::Percentage(percentages.1),
+                    ]
+                    .as_ref(),
+                )
+                .split(rect);
+
+            f.render_widget(Clear, rect);
+
+            self.list.draw(f, chunks[0])?;
+            self.diff.draw(f, chunks[1])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for FileRevlogComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            if self.diff.focused() {
+                self.diff.commands(out, force_all);
+            } else {
+                self.list.commands(out, force_all);
+            }
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::close_popup(&self.key_config),
+                    true,
+                    true,
+                )
+                .order(1),
+            );
+
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_right(&self.key_config),
+                self.can_focus_diff(),
+                !self.diff.focused() || force_all,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_left(&self.key_config),
+                true,
+                self.diff.focused() || force_all,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if self.diff.focused() {
+                if self.diff.event(ev)? {
+                    return Ok(true);
+                }
+            } else if self.list.event(ev)? {
+                self.update()?;
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.focus_right
+                    && self.can_focus_diff()
+                {
+                    self.diff.focus(true);
+                } else if e == self.key_config.focus_left
+                    && self.diff.focused()
+                {
+                    self.diff.focus(false);
+                } else if e == self.key_config.focus_left {
+                    self.hide();
+                }
+
+                // stop key event propagation
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.diff.focus(false);
+        self.update()?;
+        Ok(())
+    }
+}
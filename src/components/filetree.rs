@@ -14,10 +14,19 @@ use crate::{
     ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::{hash, StatusItem, StatusItemType};
+use asyncgit::{
+    hash,
+    sync::{CommitId, CommitStats},
+    DiffLineType, StatusItem, StatusItemType,
+};
 use crossterm::event::Event;
 use std::{borrow::Cow, cell::Cell, convert::From, path::Path};
-use tui::{backend::Backend, layout::Rect, text::Span, Frame};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    Frame,
+};
 
 ///
 pub struct FileTreeComponent {
@@ -28,6 +37,8 @@ pub struct FileTreeComponent {
     focused: bool,
     show_selection: bool,
     queue: Option<Queue>,
+    commit: Option<CommitId>,
+    stats: CommitStats,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
     scroll_top: Cell<usize>,
@@ -49,6 +60,8 @@ impl FileTreeComponent {
             focused: focus,
             show_selection: focus,
             queue,
+            commit: None,
+            stats: CommitStats::default(),
             theme,
             key_config,
             scroll_top: Cell::new(0),
@@ -89,6 +102,40 @@ impl FileTreeComponent {
         self.show_selection = show;
     }
 
+    /// commit the files belong to (`None` for workdir/stage)
+    pub fn set_commit(&mut self, commit: Option<CommitId>) {
+        self.commit = commit;
+    }
+
+    /// line stats to show next to the files, empty to hide them
+    pub fn set_stats(&mut self, stats: CommitStats) {
+        self.stats = stats;
+    }
+
+    fn open_blame(&self) -> bool {
+        match (&self.queue, self.selection_file()) {
+            (Some(queue), Some(item)) => {
+                queue.borrow_mut().push_back(
+                    InternalEvent::BlameFile(item.path, self.commit),
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn open_file_history(&self) -> bool {
+        match (&self.queue, self.selection_file()) {
+            (Some(queue), Some(item)) => {
+                queue.borrow_mut().push_back(
+                    InternalEvent::OpenFileRevlog(item.path),
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// returns true if list is empty
     pub fn is_empty(&self) -> bool {
         self.tree.is_empty()
@@ -107,6 +154,7 @@ impl FileTreeComponent {
     ///
     pub fn clear(&mut self) -> Result<()> {
         self.current_hash = 0;
+        self.stats = CommitStats::default();
         self.pending = true;
         self.tree.update(&[])
     }
@@ -135,7 +183,7 @@ impl FileTreeComponent {
         changed
     }
 
-    const fn item_status_char(item_type: StatusItemType) -> char {
+    pub const fn item_status_char(item_type: StatusItemType) -> char {
         match item_type {
             StatusItemType::Modified => 'M',
             StatusItemType::New => '+',
@@ -146,15 +194,15 @@ impl FileTreeComponent {
         }
     }
 
-    fn item_to_text<'b>(
+    fn item_to_text(
+        &self,
         string: &str,
         indent: usize,
         visible: bool,
         file_item_kind: &FileTreeItemKind,
         width: u16,
         selected: bool,
-        theme: &'b SharedTheme,
-    ) -> Option<Span<'b>> {
+    ) -> Option<Spans<'_>> {
         let indent_str = if indent == 0 {
             String::from("")
         } else {
@@ -173,23 +221,50 @@ impl FileTreeComponent {
                     .file_name()
                     .and_then(std::ffi::OsStr::to_str)
                     .expect("invalid path.");
-
-                let txt = if selected {
-                    format!(
-                        "{} {}{:w$}",
-                        status_char,
-                        indent_str,
-                        file,
-                        w = width as usize
+                let style =
+                    self.theme.item(status_item.status, selected);
+
+                let mut res = vec![Span::styled(
+                    Cow::from(format!(
+                        "{} {}{}",
+                        status_char, indent_str, file
+                    )),
+                    style,
+                )];
+
+                if let Some(stat) =
+                    self.stats.files.get(&status_item.path).filter(
+                        |stat| {
+                            stat.insertions > 0 || stat.deletions > 0
+                        },
                     )
-                } else {
-                    format!("{} {}{}", status_char, indent_str, file)
-                };
+                {
+                    res.push(Span::styled(
+                        Cow::from(format!(" +{}", stat.insertions)),
+                        self.theme
+                            .diff_line(DiffLineType::Add, selected),
+                    ));
+                    res.push(Span::styled(
+                        Cow::from(format!(" -{}", stat.deletions)),
+                        self.theme.diff_line(
+                            DiffLineType::Delete,
+                            selected,
+                        ),
+                    ));
+                }
 
-                Some(Span::styled(
-                    Cow::from(txt),
-                    theme.item(status_item.status, selected),
-                ))
+                if selected {
+                    res.push(Span::styled(
+                        Cow::from(format!(
+                            "{:w$}",
+                            "",
+                            w = width as usize
+                        )),
+                        style,
+                    ));
+                }
+
+                Some(Spans::from(res))
             }
 
             FileTreeItemKind::Path(path_collapsed) => {
@@ -211,10 +286,10 @@ impl FileTreeComponent {
                     )
                 };
 
-                Some(Span::styled(
+                Some(Spans::from(Span::styled(
                     Cow::from(txt),
-                    theme.text(true, selected),
-                ))
+                    self.theme.text(true, selected),
+                )))
             }
         }
     }
@@ -312,10 +387,10 @@ impl DrawableComponent for FileTreeComponent {
         r: Rect,
     ) -> Result<()> {
         if self.pending {
-            let items = vec![Span::styled(
+            let items = vec![Spans::from(Span::styled(
                 Cow::from(strings::loading_text(&self.key_config)),
                 self.theme.text(false, false),
-            )];
+            ))];
 
             ui::draw_list(
                 f,
@@ -349,14 +424,13 @@ impl DrawableComponent for FileTreeComponent {
                 .iter()
                 .enumerate()
                 .filter_map(|(index, draw_text_info)| {
-                    Self::item_to_text(
+                    self.item_to_text(
                         &draw_text_info.name,
                         draw_text_info.indent as usize,
                         draw_text_info.visible,
                         draw_text_info.item_kind,
                         r.width,
                         self.show_selection && select == index,
-                        &self.theme,
                     )
                 })
                 .skip(self.scroll_top.get());
@@ -389,6 +463,28 @@ impl Component for FileTreeComponent {
             .order(order::NAV),
         );
 
+        if self.queue.is_some() {
+            out.push(
+                CommandInfo::new(
+                    strings::commands::open_file_history(
+                        &self.key_config,
+                    ),
+                    self.selection_file().is_some(),
+                    self.focused || force_all,
+                )
+                .order(order::NAV),
+            );
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::open_blame(&self.key_config),
+                    self.selection_file().is_some(),
+                    self.focused || force_all,
+                )
+                .order(order::NAV),
+            );
+        }
+
         CommandBlocking::PassingOn
     }
 
@@ -411,6 +507,10 @@ impl Component for FileTreeComponent {
                     Ok(self.move_selection(MoveSelection::Left))
                 } else if e == self.key_config.move_right {
                     Ok(self.move_selection(MoveSelection::Right))
+                } else if e == self.key_config.file_history {
+                    Ok(self.open_file_history())
+                } else if e == self.key_config.blame {
+                    Ok(self.open_blame())
                 } else {
                     Ok(false)
                 };
@@ -0,0 +1,198 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, FormatPatchOptions},
+    CWD,
+};
+use crossterm::event::Event;
+use std::path::Path;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+const DEFAULT_DIR: &str = "patches";
+
+/// asks for the directory to export commits as mbox patches to
+pub struct FormatPatchComponent {
+    input: TextInputComponent,
+    commits: Vec<CommitId>,
+    options: FormatPatchOptions,
+    queue: Queue,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for FormatPatchComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for FormatPatchComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::format_patch_confirm(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::format_patch_numbered(
+                    &self.key_config,
+                    self.options.numbered,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::format_patch_cover_letter(
+                    &self.key_config,
+                    self.options.cover_letter,
+                ),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.export();
+                } else if e == self.key_config.format_patch_numbered {
+                    self.options.numbered = !self.options.numbered;
+                    self.update_title();
+                } else if e
+                    == self.key_config.format_patch_cover_letter
+                {
+                    self.options.cover_letter =
+                        !self.options.cover_letter;
+                    self.update_title();
+                }
+
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl FormatPatchComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            input: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                "",
+                &strings::format_patch_popup_msg(&key_config),
+                true,
+            ),
+            commits: Vec::new(),
+            options: FormatPatchOptions::default(),
+            queue,
+            key_config,
+        }
+    }
+
+    /// `commits` oldest first
+    pub fn open(&mut self, commits: Vec<CommitId>) -> Result<()> {
+        // like `git format-patch`: number a series, not single ones
+        self.options.numbered = commits.len() > 1;
+        self.commits = commits;
+
+        if self.input.get_text().is_empty() {
+            self.input.set_text(DEFAULT_DIR.to_string());
+        }
+        self.update_title();
+        self.show()?;
+
+        Ok(())
+    }
+
+    fn update_title(&mut self) {
+        self.input.set_title(strings::format_patch_popup_title(
+            &self.key_config,
+            self.commits.len(),
+            self.options,
+        ));
+    }
+
+    fn export(&mut self) {
+        let dir = self.input.get_text().clone();
+
+        self.hide();
+
+        let res = sync::format_patches(
+            CWD,
+            &self.commits,
+            Path::new(&dir),
+            self.options,
+        );
+
+        let event = match res {
+            Ok(files) => InternalEvent::ShowInfoMsg(
+                strings::format_patch_done_msg(
+                    &self.key_config,
+                    files.len(),
+                    &dir,
+                ),
+            ),
+            Err(e) => {
+                log::error!("format patch: {}", e);
+                InternalEvent::ShowErrorMsg(format!(
+                    "export patches error:\n{}",
+                    e,
+                ))
+            }
+        };
+
+        self.queue.borrow_mut().push_back(event);
+    }
+}
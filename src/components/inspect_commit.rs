@@ -4,12 +4,13 @@ use super::{
     DrawableComponent,
 };
 use crate::{
-    accessors, keys::SharedKeyConfig, queue::Queue, strings,
-    ui::style::SharedTheme,
+    accessors, keys::SharedKeyConfig, options::SharedOptions,
+    queue::Queue, strings, ui::style::SharedTheme,
+    AsyncAppNotification,
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{CommitId, CommitTags},
+    sync::{CommitId, CommitTags, DiffParent},
     AsyncDiff, AsyncNotification, DiffParams, DiffType,
 };
 use crossbeam_channel::Sender;
@@ -87,6 +88,14 @@ impl Component for InspectCommitComponent {
                 .order(1),
             );
 
+            out.push(CommandInfo::new(
+                strings::commands::inspect_cycle_parent(
+                    &self.key_config,
+                ),
+                true,
+                self.details.parents().len() > 1 || force_all,
+            ));
+
             out.push(CommandInfo::new(
                 strings::commands::diff_focus_right(&self.key_config),
                 self.can_focus_diff(),
@@ -112,6 +121,8 @@ impl Component for InspectCommitComponent {
             if let Event::Key(e) = ev {
                 if e == self.key_config.exit_popup {
                     self.hide();
+                } else if e == self.key_config.inspect_cycle_parent {
+                    self.cycle_parent()?;
                 } else if e == self.key_config.focus_right
                     && self.can_focus_diff()
                 {
@@ -157,8 +168,10 @@ impl InspectCommitComponent {
     pub fn new(
         queue: &Queue,
         sender: &Sender<AsyncNotification>,
+        sender_app: &Sender<AsyncAppNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             details: CommitDetailsComponent::new(
@@ -166,11 +179,14 @@ impl InspectCommitComponent {
                 sender,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             diff: DiffComponent::new(
+                sender_app,
                 queue.clone(),
                 theme,
                 key_config.clone(),
+                options,
                 true,
             ),
             commit_id: None,
@@ -189,6 +205,7 @@ impl InspectCommitComponent {
     ) -> Result<()> {
         self.commit_id = Some(id);
         self.tags = tags;
+        self.details.set_parent(DiffParent::default());
         self.show()?;
 
         Ok(())
@@ -215,6 +232,17 @@ impl InspectCommitComponent {
         Ok(())
     }
 
+    ///
+    pub fn update_app(&mut self, ev: AsyncAppNotification) {
+        if self.is_visible() {
+            match ev {
+                AsyncAppNotification::SyntaxHighlighting => {
+                    self.diff.update_syntax();
+                }
+            }
+        }
+    }
+
     /// called when any tree component changed selection
     pub fn update_diff(&mut self) -> Result<()> {
         if self.is_visible() {
@@ -223,7 +251,10 @@ impl InspectCommitComponent {
                 {
                     let diff_params = DiffParams {
                         path: f.path.clone(),
-                        diff_type: DiffType::Commit(id),
+                        diff_type: DiffType::CommitParent(
+                            id,
+                            self.details.parent(),
+                        ),
                     };
 
                     if let Some((params, last)) =
@@ -254,6 +285,26 @@ impl InspectCommitComponent {
         Ok(())
     }
 
+    /// diffs a merge against its next parent, all parents combined
+    /// after the last one
+    fn cycle_parent(&mut self) -> Result<()> {
+        let count = self.details.parents().len();
+        if count > 1 {
+            let next = match self.details.parent() {
+                DiffParent::Nth(n) if n + 1 < count => {
+                    DiffParent::Nth(n + 1)
+                }
+                DiffParent::Nth(_) => DiffParent::Combined,
+                DiffParent::Combined => DiffParent::Nth(0),
+            };
+
+            self.details.set_parent(next);
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
     fn can_focus_diff(&self) -> bool {
         self.details.files().selection_file().is_some()
     }
@@ -1,45 +1,73 @@
+mod apply_patch;
+mod author_stats;
+mod blame_file;
 mod branchlist;
 mod changes;
 mod command;
 mod commit;
 mod commit_details;
 mod commitlist;
+mod compare_commits;
 mod create_branch;
 mod cred;
 mod diff;
 mod externaleditor;
+mod file_revlog;
 mod filetree;
+mod format_patch;
+mod goto_commit;
 mod help;
 mod inspect_commit;
+mod log_revision;
+mod log_search;
 mod msg;
+mod note_commit;
 mod pull;
 mod push;
 mod push_tags;
+mod rebase_interactive;
+mod reflog;
 mod rename_branch;
 mod reset;
+mod reset_commit;
+mod revert_merge;
 mod stashmsg;
 mod tag_commit;
 mod textinput;
 mod utils;
 
+pub use apply_patch::ApplyPatchComponent;
+pub use author_stats::AuthorStatsComponent;
+pub use blame_file::BlameFileComponent;
 pub use branchlist::BranchListComponent;
 pub use changes::ChangesComponent;
 pub use command::{CommandInfo, CommandText};
 pub use commit::CommitComponent;
 pub use commit_details::CommitDetailsComponent;
 pub use commitlist::CommitList;
+pub use compare_commits::CompareCommitsComponent;
 pub use create_branch::CreateBranchComponent;
 pub use diff::DiffComponent;
 pub use externaleditor::ExternalEditorComponent;
+pub use file_revlog::FileRevlogComponent;
 pub use filetree::FileTreeComponent;
+pub use format_patch::FormatPatchComponent;
+pub use goto_commit::GotoCommitComponent;
 pub use help::HelpComponent;
 pub use inspect_commit::InspectCommitComponent;
+pub use log_revision::LogRevisionComponent;
+pub use log_search::LogSearchComponent;
 pub use msg::MsgComponent;
+pub use note_commit::NoteCommitComponent;
 pub use pull::PullComponent;
 pub use push::PushComponent;
 pub use push_tags::PushTagsComponent;
+pub use rebase_interactive::RebaseInteractiveComponent;
+pub use reflog::ReflogComponent;
 pub use rename_branch::RenameBranchComponent;
 pub use reset::ResetComponent;
+pub use reset_commit::ResetCommitComponent;
+pub use revert_merge::RevertMergeComponent;
 pub use stashmsg::StashMsgComponent;
 pub use tag_commit::TagCommitComponent;
 pub use textinput::{InputType, TextInputComponent};
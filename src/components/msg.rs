@@ -15,6 +15,7 @@ use tui::{
 use ui::style::SharedTheme;
 pub struct MsgComponent {
     title: String,
+    error: bool,
     msg: String,
     visible: bool,
     theme: SharedTheme,
@@ -60,7 +61,11 @@ impl DrawableComponent for MsgComponent {
                     Block::default()
                         .title(Span::styled(
                             self.title.as_str(),
-                            self.theme.text_danger(),
+                            if self.error {
+                                self.theme.text_danger()
+                            } else {
+                                self.theme.title(true)
+                            },
                         ))
                         .borders(Borders::ALL)
                         .border_type(BorderType::Thick),
@@ -124,6 +129,7 @@ impl MsgComponent {
     ) -> Self {
         Self {
             title: String::new(),
+            error: true,
             msg: String::new(),
             visible: false,
             theme,
@@ -134,6 +140,17 @@ impl MsgComponent {
     ///
     pub fn show_error(&mut self, msg: &str) -> Result<()> {
         self.title = strings::msg_title_error(&self.key_config);
+        self.error = true;
+        self.msg = msg.to_string();
+        self.show()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn show_info(&mut self, msg: &str) -> Result<()> {
+        self.title = strings::msg_title_info(&self.key_config);
+        self.error = false;
         self.msg = msg.to_string();
         self.show()?;
 
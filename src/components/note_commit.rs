@@ -0,0 +1,188 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    ExternalEditorComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId},
+    CWD,
+};
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// adds, edits or removes (when emptied) the note of a commit
+pub struct NoteCommitComponent {
+    input: TextInputComponent,
+    commit_id: Option<CommitId>,
+    queue: Queue,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for NoteCommitComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for NoteCommitComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::note_commit_confirm(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::note_commit_open_editor(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.save_note();
+                } else if e == self.key_config.open_commit_editor {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenNoteEditor);
+                    self.hide();
+                }
+
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl NoteCommitComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            queue,
+            input: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                "",
+                &strings::note_commit_popup_msg(&key_config),
+                true,
+            ),
+            commit_id: None,
+            key_config,
+        }
+    }
+
+    /// edits the note of `id` in the default notes ref
+    pub fn open(&mut self, id: CommitId) -> Result<()> {
+        let notes_ref = sync::get_notes_ref(CWD)?;
+        let note = sync::get_note(CWD, id)?;
+
+        self.commit_id = Some(id);
+        self.input.set_title(strings::note_commit_popup_title(
+            &self.key_config,
+            &id.get_short_string(),
+            &notes_ref,
+        ));
+        self.input.set_text(
+            note.unwrap_or_default().trim_end().to_string(),
+        );
+        self.show()?;
+
+        Ok(())
+    }
+
+    /// continues editing the note in the external editor
+    pub fn show_editor(&mut self) -> Result<()> {
+        const NOTE_MSG_FILE_NAME: &str = "NOTE_EDITOR";
+
+        let message = ExternalEditorComponent::edit_message(
+            NOTE_MSG_FILE_NAME,
+            self.input.get_text(),
+            &strings::note_commit_editor_msg(&self.key_config),
+        )?;
+
+        self.input.set_text(message);
+        self.input.show()?;
+
+        Ok(())
+    }
+
+    fn save_note(&mut self) {
+        if let Some(id) = self.commit_id {
+            match sync::set_note(CWD, id, self.input.get_text()) {
+                Ok(()) => {
+                    self.input.clear();
+                    self.hide();
+
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(
+                            NeedsUpdate::ALL | NeedsUpdate::NOTES,
+                        ),
+                    );
+                }
+                Err(e) => {
+                    self.hide();
+                    log::error!("note error: {}", e);
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "note error:\n{}",
+                            e
+                        )),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, calc_scroll_top, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{
+        self, CommitId, CommitInfo, CommitMessage, RebaseAction,
+        RebaseEntry,
+    },
+    CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::{Margin, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const MESSAGE_LENGTH: usize = 100;
+
+struct TodoItem {
+    info: CommitInfo,
+    action: RebaseAction,
+}
+
+/// todo list editor of an interactive rebase
+pub struct RebaseInteractiveComponent {
+    base: Option<CommitId>,
+    items: Vec<TodoItem>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    visible: bool,
+    reword_input: TextInputComponent,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for RebaseInteractiveComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 50);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            f.render_widget(Clear, area);
+
+            let base = self
+                .base
+                .map(|id| id.get_short_string())
+                .unwrap_or_default();
+
+            f.render_widget(
+                Block::default()
+                    .title(Span::styled(
+                        strings::rebase_interactive_title(
+                            &self.key_config,
+                            &base,
+                        ),
+                        self.theme.title(true),
+                    ))
+                    .border_type(BorderType::Thick)
+                    .borders(Borders::ALL),
+                area,
+            );
+
+            let area = area.inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+
+            let height = area.height as usize;
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(
+                Paragraph::new(self.get_text(height)),
+                area,
+            );
+
+            self.reword_input.draw(f, rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for RebaseInteractiveComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            if self.reword_input.is_visible() {
+                self.reword_input.commands(out, force_all);
+
+                out.push(CommandInfo::new(
+                    strings::commands::rebase_reword_confirm(
+                        &self.key_config,
+                    ),
+                    true,
+                    true,
+                ));
+
+                return visibility_blocking(self);
+            }
+
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_move(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_pick(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_reword(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_squash(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_fixup(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_drop(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_start(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if self.reword_input.is_visible() {
+                if self.reword_input.event(ev)? {
+                    return Ok(true);
+                }
+
+                if let Event::Key(e) = ev {
+                    if e == self.key_config.enter {
+                        self.confirm_reword();
+                    }
+                }
+
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = self
+                        .selection
+                        .saturating_add(1)
+                        .min(self.items.len().saturating_sub(1));
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.shift_down {
+                    self.move_selected(true);
+                } else if e == self.key_config.shift_up {
+                    self.move_selected(false);
+                } else if e == self.key_config.rebase_pick {
+                    self.set_action(RebaseAction::Pick);
+                } else if e == self.key_config.rebase_reword {
+                    self.open_reword()?;
+                } else if e == self.key_config.rebase_squash {
+                    self.set_action(RebaseAction::Squash);
+                } else if e == self.key_config.rebase_fixup {
+                    self.set_action(RebaseAction::Fixup);
+                } else if e == self.key_config.rebase_drop {
+                    self.set_action(RebaseAction::Drop);
+                } else if e == self.key_config.enter {
+                    self.confirm();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl RebaseInteractiveComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            base: None,
+            items: Vec::new(),
+            selection: 0,
+            scroll_top: Cell::new(0),
+            visible: false,
+            reword_input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::rebase_reword_title(&key_config),
+                &strings::rebase_reword_msg(&key_config),
+                true,
+            ),
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// opens the todo list with `commits` (oldest first) on top of
+    /// `base`, all picked
+    pub fn open(
+        &mut self,
+        base: CommitId,
+        commits: &[CommitId],
+    ) -> Result<()> {
+        self.items =
+            sync::get_commits_info(CWD, commits, MESSAGE_LENGTH)?
+                .into_iter()
+                .map(|info| TodoItem {
+                    info,
+                    action: RebaseAction::Pick,
+                })
+                .collect();
+        self.base = Some(base);
+        self.selection = 0;
+        self.scroll_top.set(0);
+        self.show()?;
+
+        Ok(())
+    }
+
+    fn set_action(&mut self, action: RebaseAction) {
+        if let Some(item) = self.items.get_mut(self.selection) {
+            item.action = action;
+        }
+    }
+
+    fn move_selected(&mut self, down: bool) {
+        let target = if down {
+            self.selection + 1
+        } else {
+            self.selection.wrapping_sub(1)
+        };
+
+        if target < self.items.len() {
+            self.items.swap(self.selection, target);
+            self.selection = target;
+        }
+    }
+
+    fn open_reword(&mut self) -> Result<()> {
+        if let Some(item) = self.items.get(self.selection) {
+            // the listed message is truncated
+            let msg = match &item.action {
+                RebaseAction::Reword(msg) => msg.clone(),
+                _ => sync::get_commit_details(CWD, item.info.id)?
+                    .message
+                    .map(CommitMessage::combine)
+                    .unwrap_or_default(),
+            };
+
+            self.reword_input.set_text(msg);
+            self.reword_input.show()?;
+        }
+
+        Ok(())
+    }
+
+    fn confirm_reword(&mut self) {
+        let msg = self.reword_input.get_text().clone();
+        self.reword_input.hide();
+
+        if !msg.trim().is_empty() {
+            self.set_action(RebaseAction::Reword(msg));
+        }
+    }
+
+    fn confirm(&mut self) {
+        if let Some(base) = self.base {
+            let todo: Vec<_> = self
+                .items
+                .iter()
+                .map(|item| RebaseEntry {
+                    id: item.info.id,
+                    action: item.action.clone(),
+                })
+                .collect();
+
+            // keep the list open to fix it
+            if let Err(e) = sync::validate_rebase_todo(&todo) {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "rebase error:\n{}",
+                        e
+                    )),
+                );
+                return;
+            }
+
+            self.hide();
+            self.queue.borrow_mut().push_back(
+                InternalEvent::RebaseInteractive(base, todo),
+            );
+        }
+    }
+
+    fn get_text(&self, height: usize) -> Vec<Spans<'_>> {
+        self.items
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(idx, item)| {
+                let selected = idx == self.selection;
+                let enabled = item.action != RebaseAction::Drop;
+
+                let message = match &item.action {
+                    RebaseAction::Reword(msg) => {
+                        msg.lines().next().unwrap_or_default()
+                    }
+                    _ => item.info.message.as_str(),
+                };
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:<7} ", item.action.name()),
+                        self.theme.text(enabled, selected),
+                    ),
+                    Span::styled(
+                        item.info.id.get_short_string(),
+                        self.theme.commit_hash(selected),
+                    ),
+                    Span::styled(
+                        format!(" {}", message),
+                        self.theme.text(enabled, selected),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,349 @@
+use super::{
+    utils::time_to_string, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, calc_scroll_top, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, ReflogEntry},
+    CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::{Margin, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const PAGE_SIZE: usize = 10;
+
+/// lists the reflog of `HEAD` and of the local branches
+pub struct ReflogComponent {
+    refs: Vec<String>,
+    current_ref: usize,
+    entries: Vec<ReflogEntry>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ReflogComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 60);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            f.render_widget(Clear, area);
+
+            f.render_widget(
+                Block::default()
+                    .title(Span::styled(
+                        strings::reflog_title(
+                            &self.key_config,
+                            self.ref_name(),
+                            self.current_ref + 1,
+                            self.refs.len(),
+                        ),
+                        self.theme.title(true),
+                    ))
+                    .border_type(BorderType::Thick)
+                    .borders(Borders::ALL),
+                area,
+            );
+
+            let area = area.inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+
+            let height = area.height as usize;
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(
+                Paragraph::new(self.get_text(height)),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ReflogComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            let selected = self.selected_commit().is_some();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::reflog_switch_ref(
+                    &self.key_config,
+                ),
+                self.refs.len() > 1,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::log_details_open(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::reflog_create_branch(
+                    &self.key_config,
+                ),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::log_reset_commit(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.move_selection(true, 1);
+                } else if e == self.key_config.move_up {
+                    self.move_selection(false, 1);
+                } else if e == self.key_config.page_down {
+                    self.move_selection(true, PAGE_SIZE);
+                } else if e == self.key_config.page_up {
+                    self.move_selection(false, PAGE_SIZE);
+                } else if e == self.key_config.tab_toggle {
+                    self.switch_ref(true)?;
+                } else if e == self.key_config.tab_toggle_reverse {
+                    self.switch_ref(false)?;
+                } else if e == self.key_config.focus_right
+                    || e == self.key_config.enter
+                {
+                    self.queue_for_selected(|id| {
+                        InternalEvent::InspectCommit(id, None)
+                    });
+                } else if e == self.key_config.create_branch {
+                    self.queue_for_selected(
+                        InternalEvent::CreateBranchAt,
+                    );
+                } else if e == self.key_config.log_reset_commit {
+                    self.queue_for_selected(
+                        InternalEvent::OpenResetCommit,
+                    );
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl ReflogComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            refs: Vec::new(),
+            current_ref: 0,
+            entries: Vec::new(),
+            selection: 0,
+            scroll_top: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// opens the reflog of `HEAD`
+    pub fn open(&mut self) -> Result<()> {
+        self.refs = sync::get_reflog_refs(CWD)?;
+        self.current_ref = 0;
+        self.fetch()?;
+        self.show()?;
+
+        Ok(())
+    }
+
+    /// re-reads refs and reflog, keeps the selection on the same
+    /// entry if it still exists
+    pub fn update(&mut self) -> Result<()> {
+        if self.visible {
+            let refs = sync::get_reflog_refs(CWD)?;
+            let current =
+                refs.iter().position(|name| name == self.ref_name());
+            self.refs = refs;
+
+            let current = if let Some(current) = current {
+                current
+            } else {
+                self.current_ref = 0;
+                return self.fetch();
+            };
+            self.current_ref = current;
+
+            let old_len = self.entries.len();
+            let selection = self.selection;
+
+            self.entries = sync::get_reflog(CWD, self.ref_name())?;
+
+            // new entries get prepended
+            self.selection = (selection + self.entries.len())
+                .saturating_sub(old_len)
+                .min(self.entries.len().saturating_sub(1));
+        }
+
+        Ok(())
+    }
+
+    fn fetch(&mut self) -> Result<()> {
+        self.entries = sync::get_reflog(CWD, self.ref_name())?;
+        self.selection = 0;
+        self.scroll_top.set(0);
+
+        Ok(())
+    }
+
+    fn ref_name(&self) -> &str {
+        self.refs
+            .get(self.current_ref)
+            .map_or("HEAD", String::as_str)
+    }
+
+    fn switch_ref(&mut self, forward: bool) -> Result<()> {
+        let count = self.refs.len();
+        if count > 1 {
+            self.current_ref = if forward {
+                (self.current_ref + 1) % count
+            } else {
+                (self.current_ref + count - 1) % count
+            };
+            self.fetch()?;
+        }
+
+        Ok(())
+    }
+
+    fn move_selection(&mut self, down: bool, step: usize) {
+        let max = self.entries.len().saturating_sub(1);
+
+        self.selection = if down {
+            self.selection.saturating_add(step).min(max)
+        } else {
+            self.selection.saturating_sub(step)
+        };
+    }
+
+    fn selected_commit(&self) -> Option<CommitId> {
+        self.entries.get(self.selection).map(|entry| entry.new_id)
+    }
+
+    fn queue_for_selected(
+        &self,
+        event: impl FnOnce(CommitId) -> InternalEvent,
+    ) {
+        if let Some(id) = self.selected_commit() {
+            self.queue.borrow_mut().push_back(event(id));
+        }
+    }
+
+    fn get_text(&self, height: usize) -> Vec<Spans<'_>> {
+        let name = self.ref_name().trim_start_matches("refs/heads/");
+
+        self.entries
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(idx, entry)| {
+                let selected = idx == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        entry.new_id.get_short_string(),
+                        self.theme.commit_hash(selected),
+                    ),
+                    Span::styled(
+                        format!(" {}@{{{}}} ", name, entry.index),
+                        self.theme.text(true, selected),
+                    ),
+                    Span::styled(
+                        time_to_string(entry.time, false),
+                        self.theme.commit_time(selected),
+                    ),
+                    Span::styled(
+                        format!(" {}", entry.message),
+                        self.theme.text(true, selected),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
@@ -181,6 +181,33 @@ impl ResetComponent {
                     strings::confirm_title_merge(&self.key_config,*rebase),
                     strings::confirm_msg_merge(&self.key_config,*incoming,*rebase),
                 ),
+                Action::CherryPick(commits) => (
+                    strings::confirm_title_cherry_pick(&self.key_config),
+                    strings::confirm_msg_cherry_pick(&self.key_config,commits.len()),
+                ),
+                Action::AbortOperation => (
+                    strings::confirm_title_abort(&self.key_config),
+                    strings::confirm_msg_abort_operation(&self.key_config),
+                ),
+                Action::ResetCommit { commit, mode, commits, changes } => (
+                    strings::confirm_title_reset_commit(&self.key_config),
+                    strings::confirm_msg_reset_commit(
+                        &self.key_config,
+                        &commit.get_short_string(),
+                        *mode,
+                        *commits,
+                        *changes,
+                    ),
+                ),
+                Action::CreateBranchHere(target) => (
+                    strings::confirm_title_create_branch_here(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_create_branch_here(
+                        &self.key_config,
+                        target,
+                    ),
+                ),
             };
         }
 
@@ -0,0 +1,225 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, ResetMode},
+    CWD,
+};
+use crossterm::event::Event;
+use std::convert::TryFrom;
+use tui::{
+    backend::Backend,
+    layout::{Margin, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const MODES: [ResetMode; 3] =
+    [ResetMode::Soft, ResetMode::Mixed, ResetMode::Hard];
+
+/// lets the user pick how to reset `HEAD` to a commit
+pub struct ResetCommitComponent {
+    commit: Option<CommitId>,
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ResetCommitComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let height =
+                u16::try_from(MODES.len())?.saturating_add(2);
+
+            let area =
+                ui::centered_rect_absolute(60, height, f.size());
+            let area = area.intersection(rect);
+
+            f.render_widget(Clear, area);
+
+            let commit = self
+                .commit
+                .map(|id| id.get_short_string())
+                .unwrap_or_default();
+
+            f.render_widget(
+                Block::default()
+                    .title(Span::styled(
+                        strings::reset_commit_title(
+                            &self.key_config,
+                            &commit,
+                        ),
+                        self.theme.title(true),
+                    ))
+                    .border_type(BorderType::Thick)
+                    .borders(Borders::ALL),
+                area,
+            );
+
+            let area = area.inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+
+            f.render_widget(Paragraph::new(self.get_text()), area);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ResetCommitComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::reset_commit_confirm(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = self
+                        .selection
+                        .saturating_add(1)
+                        .min(MODES.len() - 1);
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.enter {
+                    self.confirm()?;
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl ResetCommitComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            commit: None,
+            selection: 1,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, commit: CommitId) -> Result<()> {
+        self.commit = Some(commit);
+        // mixed is git's default
+        self.selection = 1;
+        self.show()?;
+
+        Ok(())
+    }
+
+    fn confirm(&mut self) -> Result<()> {
+        if let Some(commit) = self.commit {
+            let loss = sync::reset_loss(CWD, commit)?;
+
+            self.hide();
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::ResetCommit {
+                    commit,
+                    mode: MODES[self.selection],
+                    commits: loss.commits,
+                    changes: loss.changes,
+                }),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn get_text(&self) -> Vec<Spans<'_>> {
+        MODES
+            .iter()
+            .enumerate()
+            .map(|(idx, mode)| {
+                let selected = idx == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:<6} ", format!("{:?}", mode)),
+                        self.theme.text(true, selected),
+                    ),
+                    Span::styled(
+                        strings::reset_mode_desc(
+                            &self.key_config,
+                            *mode,
+                        ),
+                        self.theme.text(false, selected),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,221 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, CommitInfo},
+    CWD,
+};
+use crossterm::event::Event;
+use std::convert::TryFrom;
+use tui::{
+    backend::Backend,
+    layout::{Margin, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const MESSAGE_LENGTH: usize = 60;
+
+/// lets the user pick the mainline parent to revert a merge against
+pub struct RevertMergeComponent {
+    commit: Option<CommitId>,
+    parents: Vec<CommitInfo>,
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for RevertMergeComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let width = u16::try_from(MESSAGE_LENGTH + 16)?;
+            let height =
+                u16::try_from(self.parents.len())?.saturating_add(2);
+
+            let area =
+                ui::centered_rect_absolute(width, height, f.size());
+            let area = area.intersection(rect);
+
+            f.render_widget(Clear, area);
+
+            f.render_widget(
+                Block::default()
+                    .title(Span::styled(
+                        strings::revert_merge_title(&self.key_config),
+                        self.theme.title(true),
+                    ))
+                    .border_type(BorderType::Thick)
+                    .borders(Borders::ALL),
+                area,
+            );
+
+            let area = area.inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+
+            f.render_widget(Paragraph::new(self.get_text()), area);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for RevertMergeComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::revert_merge_confirm(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = self
+                        .selection
+                        .saturating_add(1)
+                        .min(self.parents.len().saturating_sub(1));
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.enter {
+                    self.confirm()?;
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl RevertMergeComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            commit: None,
+            parents: Vec::new(),
+            selection: 0,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(
+        &mut self,
+        commit: CommitId,
+        parents: &[CommitId],
+    ) -> Result<()> {
+        self.parents =
+            sync::get_commits_info(CWD, parents, MESSAGE_LENGTH)?;
+        self.commit = Some(commit);
+        self.selection = 0;
+        self.show()?;
+
+        Ok(())
+    }
+
+    fn confirm(&mut self) -> Result<()> {
+        if let Some(commit) = self.commit {
+            // git counts parents starting at 1
+            let mainline = u32::try_from(self.selection + 1)?;
+
+            self.hide();
+            self.queue.borrow_mut().push_back(
+                InternalEvent::RevertCommit(commit, Some(mainline)),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn get_text(&self) -> Vec<Spans<'_>> {
+        self.parents
+            .iter()
+            .enumerate()
+            .map(|(idx, parent)| {
+                let selected = idx == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{}: ", idx + 1),
+                        self.theme.text(true, selected),
+                    ),
+                    Span::styled(
+                        parent.id.get_short_string(),
+                        self.theme.commit_hash(selected),
+                    ),
+                    Span::styled(
+                        format!(" {}", parent.message),
+                        self.theme.text(true, selected),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
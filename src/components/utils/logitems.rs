@@ -1,15 +1,18 @@
-use super::time_to_string;
-use asyncgit::sync::{CommitId, CommitInfo};
+use asyncgit::sync::{CommitId, CommitInfo, GraphRow};
 use std::slice::Iter;
 
 static SLICE_OFFSET_RELOAD_THRESHOLD: usize = 100;
 
 pub struct LogEntry {
-    pub time: String,
+    /// committer time in secs since Unix epoch
+    pub time: i64,
+    /// author time in secs since Unix epoch
+    pub author_time: i64,
     pub author: String,
     pub msg: String,
     pub hash_short: String,
     pub id: CommitId,
+    pub graph: Option<GraphRow>,
 }
 
 impl From<CommitInfo> for LogEntry {
@@ -17,9 +20,11 @@ impl From<CommitInfo> for LogEntry {
         Self {
             author: c.author,
             msg: c.message,
-            time: time_to_string(c.time, true),
+            time: c.time,
+            author_time: c.author_time,
             hash_short: c.id.get_short_string(),
             id: c.id,
+            graph: None,
         }
     }
 }
@@ -62,6 +67,13 @@ impl ItemBatch {
         self.index_offset = start_index;
     }
 
+    /// attach graph rows to the current batch (same order as items)
+    pub fn set_graph(&mut self, rows: Vec<GraphRow>) {
+        for (item, row) in self.items.iter_mut().zip(rows) {
+            item.graph = Some(row);
+        }
+    }
+
     /// returns `true` if we should fetch updated list of items
     pub fn needs_data(&self, idx: usize, idx_max: usize) -> bool {
         let want_min =
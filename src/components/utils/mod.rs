@@ -1,4 +1,9 @@
-use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use crate::options::DateFormat;
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc,
+};
+use std::fmt::Display;
 
 pub mod filetree;
 pub mod logitems;
@@ -34,3 +39,177 @@ pub fn time_to_string(secs: i64, short: bool) -> String {
     })
     .to_string()
 }
+
+/// like `time_to_string` but in the configured `format`
+pub fn time_to_string_fmt(
+    secs: i64,
+    short: bool,
+    format: &DateFormat,
+) -> String {
+    format_time(
+        &Local.timestamp(secs, 0),
+        short,
+        format,
+        Utc::now().timestamp(),
+    )
+}
+
+/// formats the time of a commit signature in the timezone it was
+/// made in (`offset` in minutes east of UTC)
+pub fn time_to_string_tz(
+    secs: i64,
+    offset: i32,
+    format: &DateFormat,
+) -> String {
+    let tz = FixedOffset::east_opt(offset * 60)
+        .unwrap_or_else(|| FixedOffset::east(0));
+
+    format_time(
+        &tz.timestamp(secs, 0),
+        false,
+        format,
+        Utc::now().timestamp(),
+    )
+}
+
+fn format_time<Tz: TimeZone>(
+    time: &DateTime<Tz>,
+    short: bool,
+    format: &DateFormat,
+    now: i64,
+) -> String
+where
+    Tz::Offset: Display,
+{
+    let absolute = if short {
+        "%Y-%m-%d"
+    } else {
+        "%Y-%m-%d %H:%M:%S %z"
+    };
+
+    match format {
+        DateFormat::Absolute => time.format(absolute).to_string(),
+        DateFormat::Relative if short => {
+            relative_time(time.timestamp(), now)
+        }
+        DateFormat::Relative => format!(
+            "{} ({})",
+            relative_time(time.timestamp(), now),
+            time.format(absolute)
+        ),
+        DateFormat::Iso => {
+            time.format("%Y-%m-%dT%H:%M:%S%:z").to_string()
+        }
+        DateFormat::Custom(pattern) => {
+            // chrono panics when formatting an invalid pattern
+            if StrftimeItems::new(pattern)
+                .any(|item| matches!(item, Item::Error))
+            {
+                time.format(absolute).to_string()
+            } else {
+                time.format(pattern).to_string()
+            }
+        }
+    }
+}
+
+/// `3 hours ago`, roughly like git's `--date=relative`
+fn relative_time(secs: i64, now: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+
+    let diff = now - secs;
+    if diff < 0 {
+        return String::from("in the future");
+    }
+
+    let (value, unit) = if diff < 90 {
+        (diff, "second")
+    } else if diff < 90 * MINUTE {
+        ((diff + MINUTE / 2) / MINUTE, "minute")
+    } else if diff < 36 * HOUR {
+        ((diff + HOUR / 2) / HOUR, "hour")
+    } else if diff < 14 * DAY {
+        ((diff + DAY / 2) / DAY, "day")
+    } else if diff < 70 * DAY {
+        ((diff + 7 * DAY / 2) / (7 * DAY), "week")
+    } else if diff < 365 * DAY {
+        ((diff + 15 * DAY) / (30 * DAY), "month")
+    } else {
+        ((diff + 365 * DAY / 2) / (365 * DAY), "year")
+    };
+
+    format!(
+        "{} {}{} ago",
+        value,
+        unit,
+        if value == 1 { "" } else { "s" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_time() {
+        let now = 1_600_000_000;
+
+        assert_eq!(relative_time(now - 1, now), "1 second ago");
+        assert_eq!(relative_time(now - 120, now), "2 minutes ago");
+        assert_eq!(relative_time(now - 3 * 3600, now), "3 hours ago");
+        assert_eq!(
+            relative_time(now - 3 * 24 * 3600, now),
+            "3 days ago"
+        );
+        assert_eq!(
+            relative_time(now - 5 * 7 * 24 * 3600, now),
+            "5 weeks ago"
+        );
+        assert_eq!(
+            relative_time(now - 400 * 24 * 3600, now),
+            "1 year ago"
+        );
+        assert_eq!(relative_time(now + 10, now), "in the future");
+    }
+
+    #[test]
+    fn test_format_time() {
+        let tz = FixedOffset::east(2 * 3600);
+        let time = tz.ymd(2021, 3, 1).and_hms(14, 5, 0);
+        let now = time.timestamp() + 7200;
+
+        assert_eq!(
+            format_time(&time, false, &DateFormat::Absolute, now),
+            "2021-03-01 14:05:00 +0200"
+        );
+        assert_eq!(
+            format_time(&time, false, &DateFormat::Iso, now),
+            "2021-03-01T14:05:00+02:00"
+        );
+        assert_eq!(
+            format_time(&time, true, &DateFormat::Relative, now),
+            "2 hours ago"
+        );
+        assert_eq!(
+            format_time(
+                &time,
+                true,
+                &DateFormat::Custom(String::from("%d.%m.%Y")),
+                now
+            ),
+            "01.03.2021"
+        );
+        // invalid patterns fall back to absolute dates
+        assert_eq!(
+            format_time(
+                &time,
+                true,
+                &DateFormat::Custom(String::from("%Q")),
+                now
+            ),
+            "2021-03-01"
+        );
+    }
+}
@@ -53,6 +53,7 @@ pub struct KeyConfig {
     pub status_ignore_file: KeyEvent,
     pub diff_stage_lines: KeyEvent,
     pub diff_reset_lines: KeyEvent,
+    pub diff_toggle_side_by_side: KeyEvent,
     pub stashing_save: KeyEvent,
     pub stashing_toggle_untracked: KeyEvent,
     pub stashing_toggle_index: KeyEvent,
@@ -70,6 +71,40 @@ pub struct KeyConfig {
     pub push: KeyEvent,
     pub force_push: KeyEvent,
     pub pull: KeyEvent,
+    pub file_history: KeyEvent,
+    pub blame: KeyEvent,
+    pub log_search: KeyEvent,
+    pub log_search_next: KeyEvent,
+    pub log_search_prev: KeyEvent,
+    pub log_revision: KeyEvent,
+    pub log_goto_commit: KeyEvent,
+    pub log_all_branches: KeyEvent,
+    pub log_branch: KeyEvent,
+    pub log_mark_commit: KeyEvent,
+    pub log_cherry_pick: KeyEvent,
+    pub log_revert_commit: KeyEvent,
+    pub status_continue_operation: KeyEvent,
+    pub status_abort_operation: KeyEvent,
+    pub status_skip_operation: KeyEvent,
+    pub log_rebase_interactive: KeyEvent,
+    pub log_reset_commit: KeyEvent,
+    pub log_checkout_commit: KeyEvent,
+    pub log_compare_commits: KeyEvent,
+    pub inspect_cycle_parent: KeyEvent,
+    pub log_open_reflog: KeyEvent,
+    pub log_format_patch: KeyEvent,
+    pub format_patch_numbered: KeyEvent,
+    pub format_patch_cover_letter: KeyEvent,
+    pub log_apply_patch: KeyEvent,
+    pub log_author_stats: KeyEvent,
+    pub author_stats_lines: KeyEvent,
+    pub log_toggle_author_date: KeyEvent,
+    pub log_note_commit: KeyEvent,
+    pub rebase_pick: KeyEvent,
+    pub rebase_reword: KeyEvent,
+    pub rebase_squash: KeyEvent,
+    pub rebase_fixup: KeyEvent,
+    pub rebase_drop: KeyEvent,
 }
 
 #[rustfmt::skip]
@@ -109,6 +144,7 @@ impl Default for KeyConfig {
             diff_reset_lines: KeyEvent { code: KeyCode::Char('d'), modifiers: KeyModifiers::empty()},
 			status_ignore_file: KeyEvent { code: KeyCode::Char('i'), modifiers: KeyModifiers::empty()},
             diff_stage_lines: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::empty()},
+            diff_toggle_side_by_side: KeyEvent { code: KeyCode::Char('v'), modifiers: KeyModifiers::empty()},
 			stashing_save: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::empty()},
 			stashing_toggle_untracked: KeyEvent { code: KeyCode::Char('u'), modifiers: KeyModifiers::empty()},
 			stashing_toggle_index: KeyEvent { code: KeyCode::Char('i'), modifiers: KeyModifiers::empty()},
@@ -126,6 +162,40 @@ impl Default for KeyConfig {
             push: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
             force_push: KeyEvent { code: KeyCode::Char('P'), modifiers: KeyModifiers::SHIFT},
             pull: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty()},
+            file_history: KeyEvent { code: KeyCode::Char('l'), modifiers: KeyModifiers::empty()},
+            blame: KeyEvent { code: KeyCode::Char('B'), modifiers: KeyModifiers::SHIFT},
+            log_search: KeyEvent { code: KeyCode::Char('/'), modifiers: KeyModifiers::empty()},
+            log_search_next: KeyEvent { code: KeyCode::Char('n'), modifiers: KeyModifiers::empty()},
+            log_search_prev: KeyEvent { code: KeyCode::Char('N'), modifiers: KeyModifiers::SHIFT},
+            log_revision: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+            log_goto_commit: KeyEvent { code: KeyCode::Char('g'), modifiers: KeyModifiers::empty()},
+            log_all_branches: KeyEvent { code: KeyCode::Char('A'), modifiers: KeyModifiers::SHIFT},
+            log_branch: KeyEvent { code: KeyCode::Char('l'), modifiers: KeyModifiers::empty()},
+            log_mark_commit: KeyEvent { code: KeyCode::Char(' '), modifiers: KeyModifiers::empty()},
+            log_cherry_pick: KeyEvent { code: KeyCode::Char('C'), modifiers: KeyModifiers::SHIFT},
+            log_revert_commit: KeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT},
+            status_continue_operation: KeyEvent { code: KeyCode::Char('C'), modifiers: KeyModifiers::SHIFT},
+            status_abort_operation: KeyEvent { code: KeyCode::Char('A'), modifiers: KeyModifiers::SHIFT},
+            status_skip_operation: KeyEvent { code: KeyCode::Char('S'), modifiers: KeyModifiers::SHIFT},
+            log_rebase_interactive: KeyEvent { code: KeyCode::Char('I'), modifiers: KeyModifiers::SHIFT},
+            log_reset_commit: KeyEvent { code: KeyCode::Char('X'), modifiers: KeyModifiers::SHIFT},
+            log_checkout_commit: KeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::empty()},
+            log_compare_commits: KeyEvent { code: KeyCode::Char('d'), modifiers: KeyModifiers::empty()},
+            inspect_cycle_parent: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+            log_open_reflog: KeyEvent { code: KeyCode::Char('H'), modifiers: KeyModifiers::SHIFT},
+            log_format_patch: KeyEvent { code: KeyCode::Char('F'), modifiers: KeyModifiers::SHIFT},
+            format_patch_numbered: KeyEvent { code: KeyCode::Char('n'), modifiers: KeyModifiers::CONTROL},
+            format_patch_cover_letter: KeyEvent { code: KeyCode::Char('l'), modifiers: KeyModifiers::CONTROL},
+            log_apply_patch: KeyEvent { code: KeyCode::Char('M'), modifiers: KeyModifiers::SHIFT},
+            log_author_stats: KeyEvent { code: KeyCode::Char('W'), modifiers: KeyModifiers::SHIFT},
+            author_stats_lines: KeyEvent { code: KeyCode::Char('l'), modifiers: KeyModifiers::empty()},
+            log_toggle_author_date: KeyEvent { code: KeyCode::Char('T'), modifiers: KeyModifiers::SHIFT},
+            log_note_commit: KeyEvent { code: KeyCode::Char('E'), modifiers: KeyModifiers::SHIFT},
+            rebase_pick: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+            rebase_reword: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+            rebase_squash: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::empty()},
+            rebase_fixup: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty()},
+            rebase_drop: KeyEvent { code: KeyCode::Char('d'), modifiers: KeyModifiers::empty()},
         }
     }
 }
@@ -221,6 +291,12 @@ impl KeyConfig {
                     self.get_key_symbol(ev.code)
                 )
             }
+            KeyCode::Char(' ') => {
+                format!(
+                    "{}\u{2423}", //␣
+                    Self::get_modifier_hint(ev.modifiers),
+                )
+            }
             KeyCode::Char(c) => {
                 format!(
                     "{}{}",
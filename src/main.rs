@@ -18,6 +18,7 @@ mod components;
 mod input;
 mod keys;
 mod notify_mutex;
+mod options;
 mod profiler;
 mod queue;
 mod spinner;
@@ -44,6 +45,7 @@ use crossterm::{
 };
 use input::{Input, InputEvent, InputState};
 use keys::KeyConfig;
+use options::Options;
 use profiler::Profiler;
 use scopeguard::defer;
 use scopetime::scope_time;
@@ -67,12 +69,21 @@ use ui::style::Theme;
 static TICK_INTERVAL: Duration = Duration::from_secs(5);
 static SPINNER_INTERVAL: Duration = Duration::from_millis(80);
 
+/// notifications of the app's own background work, the git ones
+/// come from `asyncgit`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsyncAppNotification {
+    /// a diff was syntax highlighted
+    SyntaxHighlighting,
+}
+
 ///
 #[derive(Clone, Copy)]
 pub enum QueueEvent {
     Tick,
     SpinnerUpdate,
     GitEvent(AsyncNotification),
+    AppEvent(AsyncAppNotification),
     InputEvent(InputEvent),
 }
 
@@ -96,6 +107,9 @@ fn main() -> Result<()> {
     let theme = Theme::init(cliargs.theme)
         .map_err(|e| eprintln!("Theme loading error: {}", e))
         .unwrap_or_default();
+    let options = Options::init()
+        .map_err(|e| eprintln!("Options loading error: {}", e))
+        .unwrap_or_default();
 
     setup_terminal()?;
     defer! {
@@ -107,6 +121,7 @@ fn main() -> Result<()> {
     let mut terminal = start_terminal(io::stdout())?;
 
     let (tx_git, rx_git) = unbounded();
+    let (tx_app, rx_app) = unbounded();
 
     let input = Input::new();
 
@@ -114,7 +129,8 @@ fn main() -> Result<()> {
     let ticker = tick(TICK_INTERVAL);
     let spinner_ticker = tick(SPINNER_INTERVAL);
 
-    let mut app = App::new(&tx_git, input, theme, key_config);
+    let mut app =
+        App::new(&tx_git, &tx_app, input, theme, key_config, options);
 
     let mut spinner = Spinner::default();
     let mut first_update = true;
@@ -127,6 +143,7 @@ fn main() -> Result<()> {
             select_event(
                 &rx_input,
                 &rx_git,
+                &rx_app,
                 &ticker,
                 &spinner_ticker,
             )?
@@ -157,6 +174,7 @@ fn main() -> Result<()> {
                     app.update_git(ev)?
                 }
                 QueueEvent::GitEvent(..) => (),
+                QueueEvent::AppEvent(ev) => app.update_app(ev),
                 QueueEvent::SpinnerUpdate => unreachable!(),
             }
 
@@ -209,6 +227,7 @@ fn valid_path() -> Result<bool> {
 fn select_event(
     rx_input: &Receiver<InputEvent>,
     rx_git: &Receiver<AsyncNotification>,
+    rx_app: &Receiver<AsyncAppNotification>,
     rx_ticker: &Receiver<Instant>,
     rx_spinner: &Receiver<Instant>,
 ) -> Result<QueueEvent> {
@@ -216,6 +235,7 @@ fn select_event(
 
     sel.recv(rx_input);
     sel.recv(rx_git);
+    sel.recv(rx_app);
     sel.recv(rx_ticker);
     sel.recv(rx_spinner);
 
@@ -225,8 +245,9 @@ fn select_event(
     let ev = match index {
         0 => oper.recv(rx_input).map(QueueEvent::InputEvent),
         1 => oper.recv(rx_git).map(QueueEvent::GitEvent),
-        2 => oper.recv(rx_ticker).map(|_| QueueEvent::Tick),
-        3 => oper.recv(rx_spinner).map(|_| QueueEvent::SpinnerUpdate),
+        2 => oper.recv(rx_app).map(QueueEvent::AppEvent),
+        3 => oper.recv(rx_ticker).map(|_| QueueEvent::Tick),
+        4 => oper.recv(rx_spinner).map(|_| QueueEvent::SpinnerUpdate),
         _ => bail!("unknown select source"),
     }?;
 
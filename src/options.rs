@@ -0,0 +1,151 @@
+use crate::get_app_config_path;
+use anyhow::Result;
+use ron::{
+    self,
+    ser::{to_string_pretty, PrettyConfig},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    fs::{self, File},
+    io::{Read, Write},
+    path::PathBuf,
+    rc::Rc,
+};
+
+pub type SharedOptions = Rc<RefCell<Options>>;
+
+/// how commit dates are shown in the log and the commit details
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum DateFormat {
+    /// `2021-03-01` in the log, `2021-03-01 14:00:00 +0100` in details
+    Absolute,
+    /// `3 hours ago`
+    Relative,
+    /// `2021-03-01T14:00:00+01:00`
+    Iso,
+    /// any `strftime` pattern like `%d.%m.%Y %H:%M`
+    Custom(String),
+}
+
+/// user settings that can be changed from within gitui and are
+/// persisted in `options.ron`, missing fields get their default
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Options {
+    pub date_format: DateFormat,
+    /// show author instead of committer dates in the log
+    pub log_author_date: bool,
+    /// show old and new content of diffs next to each other
+    pub diff_side_by_side: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            date_format: DateFormat::Absolute,
+            log_author_date: false,
+            diff_side_by_side: false,
+        }
+    }
+}
+
+impl Options {
+    fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("options.ron"))
+    }
+
+    fn save(&self) -> Result<()> {
+        let mut file = File::create(Self::get_config_file()?)?;
+        let data = to_string_pretty(self, PrettyConfig::default())?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    fn read_file(file: PathBuf) -> Result<Self> {
+        let mut f = File::open(file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(ron::de::from_bytes(&buffer)?)
+    }
+
+    pub fn init() -> Result<Self> {
+        let file = Self::get_config_file()?;
+        if file.exists() {
+            match Self::read_file(file.clone()) {
+                Err(e) => {
+                    let config_path = file.clone();
+                    let config_path_old =
+                        format!("{}.old", file.to_string_lossy());
+                    fs::rename(
+                        config_path.clone(),
+                        config_path_old.clone(),
+                    )?;
+
+                    Self::default().save()?;
+
+                    Err(anyhow::anyhow!("{}\n Old file was renamed to {:?}.\n Defaults loaded and saved as {:?}",
+                        e,config_path_old,config_path.to_string_lossy()))
+                }
+                Ok(res) => Ok(res),
+            }
+        } else {
+            Self::default().save()?;
+            Ok(Self::default())
+        }
+    }
+
+    /// toggles `log_author_date` and persists it
+    pub fn toggle_log_author_date(&mut self) {
+        self.log_author_date = !self.log_author_date;
+        self.save_or_log();
+    }
+
+    /// toggles `diff_side_by_side` and persists it
+    pub fn toggle_diff_side_by_side(&mut self) {
+        self.diff_side_by_side = !self.diff_side_by_side;
+        self.save_or_log();
+    }
+
+    fn save_or_log(&self) {
+        if let Err(e) = self.save() {
+            log::error!("options save error: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_format_roundtrip() {
+        let options = Options {
+            date_format: DateFormat::Custom(String::from("%d.%m.%Y")),
+            log_author_date: true,
+            diff_side_by_side: true,
+        };
+
+        let data =
+            to_string_pretty(&options, PrettyConfig::default())
+                .unwrap();
+        let res: Options = ron::de::from_str(&data).unwrap();
+
+        assert_eq!(res, options);
+    }
+
+    #[test]
+    fn test_partial_file() {
+        let res: Options =
+            ron::de::from_str("(log_author_date: true)").unwrap();
+
+        assert_eq!(
+            res,
+            Options {
+                log_author_date: true,
+                ..Options::default()
+            }
+        );
+    }
+}
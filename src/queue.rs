@@ -1,5 +1,8 @@
 use crate::tabs::StashingOptions;
-use asyncgit::sync::{diff::DiffLinePosition, CommitId, CommitTags};
+use asyncgit::sync::{
+    diff::DiffLinePosition, CommitId, CommitTags, LogStart,
+    RebaseEntry, ResetMode,
+};
 use bitflags::bitflags;
 use std::{cell::RefCell, collections::VecDeque, rc::Rc};
 
@@ -12,6 +15,8 @@ bitflags! {
         const DIFF = 0b010;
         /// commands might need updating (app::update_commands)
         const COMMANDS = 0b100;
+        /// git notes changed (revlog::update_notes)
+        const NOTES = 0b1000;
     }
 }
 
@@ -32,7 +37,19 @@ pub enum Action {
     StashPop(CommitId),
     DeleteBranch(String),
     ForcePush(String, bool),
-    PullMerge { incoming: usize, rebase: bool },
+    PullMerge {
+        incoming: usize,
+        rebase: bool,
+    },
+    CherryPick(Vec<CommitId>),
+    AbortOperation,
+    ResetCommit {
+        commit: CommitId,
+        mode: ResetMode,
+        commits: usize,
+        changes: usize,
+    },
+    CreateBranchHere(String),
 }
 
 ///
@@ -44,6 +61,8 @@ pub enum InternalEvent {
     ///
     ShowErrorMsg(String),
     ///
+    ShowInfoMsg(String),
+    ///
     Update(NeedsUpdate),
     /// open commit msg input
     OpenCommit,
@@ -55,20 +74,67 @@ pub enum InternalEvent {
     InspectCommit(CommitId, Option<CommitTags>),
     ///
     TagCommit(CommitId),
+    /// add or edit the note of the commit
+    NoteCommit(CommitId),
     ///
     CreateBranch,
+    /// create branch at the commit without switching to it
+    CreateBranchAt(CommitId),
     ///
     RenameBranch(String, String),
     ///
     SelectBranch,
     ///
     OpenExternalEditor(Option<String>),
+    /// edit the note in the note popup with the external editor
+    OpenNoteEditor,
     ///
     Push(String, bool),
     ///
     Pull(String),
     ///
     PushTags,
+    /// open history of the file at the given path
+    OpenFileRevlog(String),
+    /// blame the file at the given path (at a commit or `HEAD`)
+    BlameFile(String, Option<CommitId>),
+    ///
+    OpenLogSearch,
+    /// search the log for the given text
+    SearchLog(String),
+    ///
+    OpenLogRevision,
+    ///
+    OpenGotoCommit,
+    /// select the commit in the log, inspect it if it is not part
+    /// of the log
+    GotoCommit(CommitId),
+    /// show the log starting from the given revision(s)
+    SetLogStart(LogStart),
+    /// revert commit (merges need the mainline parent)
+    RevertCommit(CommitId, Option<u32>),
+    /// open commit popup with the given message
+    OpenCommitWithMsg(String),
+    /// choose how to reset `HEAD` to the commit
+    OpenResetCommit(CommitId),
+    ///
+    OpenReflog,
+    /// export commits (oldest first) as patch files
+    OpenFormatPatch(Vec<CommitId>),
+    ///
+    OpenApplyPatch,
+    /// per author statistics of the log from `LogStart`, named by
+    /// the string
+    OpenAuthorStats(LogStart, String),
+    /// diff from the first to the second commit
+    CompareCommits(CommitId, CommitId),
+    /// checkout commit as detached `HEAD`, with an optional tag name
+    /// to refer to it by
+    CheckoutCommit(CommitId, Option<String>),
+    /// open the rebase todo list for the commits after this base
+    OpenRebaseInteractive(CommitId),
+    /// rebase the todo list onto the base commit
+    RebaseInteractive(CommitId, Vec<RebaseEntry>),
 }
 
 ///
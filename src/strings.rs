@@ -1,4 +1,5 @@
 use crate::keys::SharedKeyConfig;
+use asyncgit::sync::{ApplyTarget, FormatPatchOptions, ResetMode};
 
 pub mod order {
     pub static NAV: i8 = 1;
@@ -61,6 +62,9 @@ pub fn msg_opening_editor(_key_config: &SharedKeyConfig) -> String {
 pub fn msg_title_error(_key_config: &SharedKeyConfig) -> String {
     "Error".to_string()
 }
+pub fn msg_title_info(_key_config: &SharedKeyConfig) -> String {
+    "Info".to_string()
+}
 pub fn commit_title(_key_config: &SharedKeyConfig) -> String {
     "Commit".to_string()
 }
@@ -116,6 +120,89 @@ pub fn confirm_msg_merge(
         format!("Merge of {} incoming commits?", incoming)
     }
 }
+pub fn confirm_title_cherry_pick(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Cherry-pick".to_string()
+}
+pub fn confirm_msg_cherry_pick(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    if count == 1 {
+        "Cherry-pick the commit onto HEAD?".to_string()
+    } else {
+        format!("Cherry-pick {} commits onto HEAD?", count)
+    }
+}
+pub fn confirm_title_reset_commit(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Reset".to_string()
+}
+pub fn confirm_msg_reset_commit(
+    _key_config: &SharedKeyConfig,
+    commit: &str,
+    mode: ResetMode,
+    commits: usize,
+    changes: usize,
+) -> String {
+    let question = format!("{:?} reset HEAD to {}?", mode, commit);
+
+    match mode {
+        ResetMode::Hard => format!(
+            "{}\n{} commit(s) and {} uncommitted change(s) will be lost.",
+            question, commits, changes
+        ),
+        _ if commits == 0 => question,
+        _ => format!(
+            "{}\n{} commit(s) will be dropped, their changes are kept {}.",
+            question,
+            commits,
+            if mode == ResetMode::Soft {
+                "staged"
+            } else {
+                "unstaged"
+            }
+        ),
+    }
+}
+pub fn confirm_title_create_branch_here(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Detached HEAD".to_string()
+}
+pub fn confirm_msg_create_branch_here(
+    _key_config: &SharedKeyConfig,
+    target: &str,
+) -> String {
+    format!(
+        "HEAD is now detached at {}.\nCreate a branch here?",
+        target
+    )
+}
+pub fn confirm_title_abort(_key_config: &SharedKeyConfig) -> String {
+    "Abort".to_string()
+}
+pub fn confirm_msg_abort_operation(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Abort the operation in progress? Unresolved changes are lost."
+        .to_string()
+}
+pub fn operation_conflict_msg(
+    key_config: &SharedKeyConfig,
+    operation: &str,
+    commit: &str,
+) -> String {
+    format!(
+        "{} of {} stopped with conflicts.\nresolve and stage them, then continue [{}] or abort [{}] in the Status tab.",
+        operation,
+        commit,
+        key_config.get_hint(key_config.status_continue_operation),
+        key_config.get_hint(key_config.status_abort_operation),
+    )
+}
 pub fn confirm_msg_reset(_key_config: &SharedKeyConfig) -> String {
     "confirm file reset?".to_string()
 }
@@ -170,6 +257,211 @@ pub fn confirm_msg_force_push(
 pub fn log_title(_key_config: &SharedKeyConfig) -> String {
     "Commit".to_string()
 }
+pub fn file_log_title(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+) -> String {
+    format!("History: {}", path)
+}
+pub fn blame_title(_key_config: &SharedKeyConfig) -> String {
+    "Blame".to_string()
+}
+pub fn log_search_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Search log".to_string()
+}
+pub fn log_search_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "message, author or hash".to_string()
+}
+pub fn log_revision_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Log revision".to_string()
+}
+pub fn log_revision_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "branch, tag or revspec (empty for HEAD)".to_string()
+}
+pub fn goto_commit_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Go to commit".to_string()
+}
+pub fn goto_commit_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "hash, tag, branch or revspec (HEAD~5)".to_string()
+}
+pub fn log_all_branches_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "all branches".to_string()
+}
+pub fn compare_commits_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Compare".to_string()
+}
+pub fn compare_commits_old(_key_config: &SharedKeyConfig) -> String {
+    "from:".to_string()
+}
+pub fn compare_commits_new(_key_config: &SharedKeyConfig) -> String {
+    "to:  ".to_string()
+}
+pub fn revert_merge_title(_key_config: &SharedKeyConfig) -> String {
+    "Revert merge against parent".to_string()
+}
+pub fn reflog_title(
+    _key_config: &SharedKeyConfig,
+    refname: &str,
+    current: usize,
+    total: usize,
+) -> String {
+    format!("Reflog: {} ({}/{})", refname, current, total)
+}
+pub fn format_patch_popup_title(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+    options: FormatPatchOptions,
+) -> String {
+    format!(
+        "Export {} patch{} ({}{})",
+        count,
+        if count == 1 { "" } else { "es" },
+        if options.numbered {
+            "numbered"
+        } else {
+            "unnumbered"
+        },
+        if options.cover_letter {
+            ", cover letter"
+        } else {
+            ""
+        },
+    )
+}
+pub fn format_patch_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "output directory".to_string()
+}
+pub fn format_patch_done_msg(
+    _key_config: &SharedKeyConfig,
+    files: usize,
+    dir: &str,
+) -> String {
+    format!("wrote {} patch file(s) to '{}'", files, dir)
+}
+pub fn apply_patch_input_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Apply patches".to_string()
+}
+pub fn apply_patch_input_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "patch or mbox file".to_string()
+}
+const fn apply_target_name(target: ApplyTarget) -> &'static str {
+    match target {
+        ApplyTarget::WorkDir => "working tree",
+        ApplyTarget::Index => "index",
+        ApplyTarget::Commits => "new commits",
+    }
+}
+pub fn apply_patch_title(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+    target: ApplyTarget,
+) -> String {
+    format!(
+        "Apply {} patch{} to {}",
+        count,
+        if count == 1 { "" } else { "es" },
+        apply_target_name(target),
+    )
+}
+pub fn apply_patch_check_ok(
+    _key_config: &SharedKeyConfig,
+    patch: usize,
+) -> String {
+    format!("patch {} applies cleanly", patch)
+}
+pub fn apply_patch_check_failed(
+    _key_config: &SharedKeyConfig,
+    patch: usize,
+) -> String {
+    format!("patch {} does not apply:", patch)
+}
+pub fn apply_patch_done_msg(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+    target: ApplyTarget,
+) -> String {
+    format!(
+        "applied {} patch(es) to {}",
+        count,
+        apply_target_name(target)
+    )
+}
+pub fn author_stats_title(
+    _key_config: &SharedKeyConfig,
+    log: &str,
+    authors: usize,
+    sort: &str,
+    pending: bool,
+) -> String {
+    format!(
+        "Authors of {}: {} (by {}){}",
+        log,
+        authors,
+        sort,
+        if pending { " ..." } else { "" }
+    )
+}
+pub fn reset_commit_title(
+    _key_config: &SharedKeyConfig,
+    commit: &str,
+) -> String {
+    format!("Reset HEAD to {}", commit)
+}
+pub fn reset_mode_desc(
+    _key_config: &SharedKeyConfig,
+    mode: ResetMode,
+) -> String {
+    match mode {
+        ResetMode::Soft => "keep all changes staged",
+        ResetMode::Mixed => "keep changes, unstage them",
+        ResetMode::Hard => "discard all changes",
+    }
+    .to_string()
+}
+pub fn rebase_interactive_title(
+    _key_config: &SharedKeyConfig,
+    base: &str,
+) -> String {
+    format!("Rebase onto {}", base)
+}
+pub fn rebase_reword_title(_key_config: &SharedKeyConfig) -> String {
+    "Reword".to_string()
+}
+pub fn rebase_reword_msg(_key_config: &SharedKeyConfig) -> String {
+    "type new commit message".to_string()
+}
+pub fn rebase_conflict_msg(
+    key_config: &SharedKeyConfig,
+    commit: &str,
+) -> String {
+    format!(
+        "rebase stopped with conflicts at {}.\nresolve and stage them, then continue [{}], skip the commit [{}] or abort [{}] in the Status tab.",
+        commit,
+        key_config.get_hint(key_config.status_continue_operation),
+        key_config.get_hint(key_config.status_skip_operation),
+        key_config.get_hint(key_config.status_abort_operation),
+    )
+}
 pub fn tag_commit_popup_title(
     _key_config: &SharedKeyConfig,
 ) -> String {
@@ -178,6 +470,26 @@ pub fn tag_commit_popup_title(
 pub fn tag_commit_popup_msg(_key_config: &SharedKeyConfig) -> String {
     "type tag".to_string()
 }
+pub fn note_commit_popup_title(
+    _key_config: &SharedKeyConfig,
+    commit: &str,
+    notes_ref: &str,
+) -> String {
+    format!("Note {} ({})", commit, notes_ref)
+}
+pub fn note_commit_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "type note (empty to remove it)".to_string()
+}
+pub fn note_commit_editor_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    r"
+# Edit the note of the commit, an empty note removes it
+# Lines starting with '#' will be ignored"
+        .to_string()
+}
 pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
     "Stashes".to_string()
 }
@@ -245,9 +557,44 @@ pub mod commit {
     pub fn details_date(_key_config: &SharedKeyConfig) -> String {
         "Date: ".to_string()
     }
+    pub fn details_parents(_key_config: &SharedKeyConfig) -> String {
+        "Parents: ".to_string()
+    }
+    pub fn details_parents_combined(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        " (combined)".to_string()
+    }
+    pub fn details_stats(_key_config: &SharedKeyConfig) -> String {
+        "Stats: ".to_string()
+    }
+    pub fn details_stats_files(
+        _key_config: &SharedKeyConfig,
+        files: usize,
+    ) -> String {
+        format!(
+            "{} file{} changed",
+            files,
+            if files == 1 { "" } else { "s" }
+        )
+    }
     pub fn details_tags(_key_config: &SharedKeyConfig) -> String {
         "Tags: ".to_string()
     }
+    pub fn details_notes(
+        _key_config: &SharedKeyConfig,
+        notes_ref: &str,
+    ) -> String {
+        // like `git log`
+        if notes_ref == "refs/notes/commits" {
+            "Notes:".to_string()
+        } else {
+            format!(
+                "Notes ({}):",
+                notes_ref.trim_start_matches("refs/notes/")
+            )
+        }
+    }
     pub fn details_info_title(
         _key_config: &SharedKeyConfig,
     ) -> String {
@@ -444,6 +791,25 @@ pub mod commands {
             CMD_GROUP_DIFF,
         )
     }
+    pub fn diff_toggle_side_by_side(
+        key_config: &SharedKeyConfig,
+        side_by_side: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} [{}]",
+                if side_by_side {
+                    "Unified"
+                } else {
+                    "Side-by-side"
+                },
+                key_config
+                    .get_hint(key_config.diff_toggle_side_by_side),
+            ),
+            "toggle showing old and new content next to each other",
+            CMD_GROUP_DIFF,
+        )
+    }
     pub fn diff_lines_unstage(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
@@ -817,148 +1183,802 @@ pub mod commands {
             CMD_GROUP_LOG,
         )
     }
-    pub fn log_tag_commit(
+    pub fn log_search(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Search [{}]",
+                key_config.get_hint(key_config.log_search),
+            ),
+            "search commits by message, author or hash",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_next_prev(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Tag [{}]",
-                key_config.get_hint(key_config.log_tag_commit),
+                "Next/Prev Match [{}{}]",
+                key_config.get_hint(key_config.log_search_next),
+                key_config.get_hint(key_config.log_search_prev),
             ),
-            "tag commit",
+            "select next/previous search match",
             CMD_GROUP_LOG,
         )
     }
-    pub fn tag_commit_confirm_msg(
+    pub fn log_search_clear(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Tag [{}]",
+                "Clear Search [{}]",
+                key_config.get_hint(key_config.exit_popup),
+            ),
+            "stop searching the log",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Search [{}]",
                 key_config.get_hint(key_config.enter),
             ),
-            "tag commit",
+            "search log",
             CMD_GROUP_LOG,
         )
     }
-    pub fn create_branch_confirm_msg(
+    pub fn log_revision(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Log Revision [{}]",
+                key_config.get_hint(key_config.log_revision),
+            ),
+            "show the log of a branch, tag or revspec",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_revision_confirm(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Create Branch [{}]",
+                "Show [{}]",
                 key_config.get_hint(key_config.enter),
             ),
-            "create branch",
-            CMD_GROUP_GENERAL,
+            "show log of revision",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn open_branch_create_popup(
+    pub fn log_goto_commit(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Create [{}]",
-                key_config.get_hint(key_config.create_branch),
+                "Go to [{}]",
+                key_config.get_hint(key_config.log_goto_commit),
             ),
-            "open create branch popup",
-            CMD_GROUP_GENERAL,
+            "select a commit by hash or revspec",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn rename_branch_confirm_msg(
+    pub fn goto_commit_confirm(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Rename Branch [{}]",
+                "Go to [{}]",
                 key_config.get_hint(key_config.enter),
             ),
-            "rename branch",
-            CMD_GROUP_GENERAL,
+            "select the commit",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn rename_branch_popup(
+    pub fn log_all_branches(
         key_config: &SharedKeyConfig,
+        enabled: bool,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Rename Branch [{}]",
-                key_config.get_hint(key_config.rename_branch),
+                "{} [{}]",
+                if enabled { "HEAD Only" } else { "All Branches" },
+                key_config.get_hint(key_config.log_all_branches),
             ),
-            "rename branch",
-            CMD_GROUP_GENERAL,
+            "toggle showing commits of all branches",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn delete_branch_popup(
+    pub fn log_toggle_author_date(
         key_config: &SharedKeyConfig,
+        author_date: bool,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Delete [{}]",
-                key_config.get_hint(key_config.delete_branch),
+                "{} [{}]",
+                if author_date {
+                    "Committer Dates"
+                } else {
+                    "Author Dates"
+                },
+                key_config
+                    .get_hint(key_config.log_toggle_author_date),
             ),
-            "delete a branch",
-            CMD_GROUP_GENERAL,
+            "toggle showing author or committer dates",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn select_branch_popup(
+    pub fn log_head(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Log HEAD [{}]",
+                key_config.get_hint(key_config.exit_popup),
+            ),
+            "go back to the log of HEAD",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_branch_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Checkout [{}]",
-                key_config.get_hint(key_config.enter),
+                "Log [{}]",
+                key_config.get_hint(key_config.log_branch),
             ),
-            "checkout branch",
+            "show log of branch",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn toggle_branch_popup(
+    pub fn log_mark_commit(
         key_config: &SharedKeyConfig,
-        local: bool,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "{} [{}]",
-                if local { "Remote" } else { "Local" },
-                key_config.get_hint(key_config.tab_toggle),
+                "Mark [{}]",
+                key_config.get_hint(key_config.log_mark_commit),
             ),
-            "toggle branch type (remote/local)",
-            CMD_GROUP_GENERAL,
+            "mark/unmark commit to cherry-pick several at once",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn open_branch_select_popup(
+    pub fn log_clear_marked(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Branches [{}]",
-                key_config.get_hint(key_config.select_branch),
+                "Unmark All [{}]",
+                key_config.get_hint(key_config.exit_popup),
             ),
-            "open select branch popup",
-            CMD_GROUP_GENERAL,
+            "unmark all marked commits",
+            CMD_GROUP_LOG,
         )
     }
-
-    pub fn status_push(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_cherry_pick(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Push [{}]",
-                key_config.get_hint(key_config.push),
+                "Cherry-pick [{}]",
+                key_config.get_hint(key_config.log_cherry_pick),
             ),
-            "push to origin",
-            CMD_GROUP_GENERAL,
+            "cherry-pick selected or marked commits onto HEAD",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn status_force_push(
+    pub fn log_revert_commit(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Force Push [{}]",
-                key_config.get_hint(key_config.force_push),
+                "Revert [{}]",
+                key_config.get_hint(key_config.log_revert_commit),
             ),
-            "force push to origin",
+            "revert selected commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn revert_merge_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Revert [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "revert the changes the merge brought in relative to the selected parent",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_reset_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset [{}]",
+                key_config.get_hint(key_config.log_reset_commit),
+            ),
+            "reset HEAD to the selected commit (soft, mixed or hard)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn inspect_cycle_parent(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Parent [{}]",
+                key_config.get_hint(key_config.inspect_cycle_parent),
+            ),
+            "diff the merge against the next parent or all of them",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn log_open_reflog(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reflog [{}]",
+                key_config.get_hint(key_config.log_open_reflog),
+            ),
+            "show where HEAD and the branches pointed to before",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_format_patch(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Export patches [{}]",
+                key_config.get_hint(key_config.log_format_patch),
+            ),
+            "export selected or marked commits as mbox patch files",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn format_patch_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Export [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "write the patch files",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn format_patch_numbered(
+        key_config: &SharedKeyConfig,
+        on: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Numbered {} [{}]",
+                if on { "off" } else { "on" },
+                key_config.get_hint(key_config.format_patch_numbered),
+            ),
+            "toggle [PATCH n/m] subjects",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn format_patch_cover_letter(
+        key_config: &SharedKeyConfig,
+        on: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Cover letter {} [{}]",
+                if on { "off" } else { "on" },
+                key_config
+                    .get_hint(key_config.format_patch_cover_letter),
+            ),
+            "toggle writing a cover letter",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn log_author_stats(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Authors [{}]",
+                key_config.get_hint(key_config.log_author_stats),
+            ),
+            "commits per author of the shown log (shortlog)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn author_stats_sort(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Sort [{}]",
+                key_config.get_hint(key_config.tab_toggle),
+            ),
+            "sort by commits, lines, name or dates",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn author_stats_lines(
+        key_config: &SharedKeyConfig,
+        on: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Lines {} [{}]",
+                if on { "off" } else { "on" },
+                key_config.get_hint(key_config.author_stats_lines),
+            ),
+            "toggle counting lines added and removed (slow)",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn log_apply_patch(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Apply patches [{}]",
+                key_config.get_hint(key_config.log_apply_patch),
+            ),
+            "apply a patch or mbox file",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn apply_patch_open(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Preview [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "read the file and show what it changes",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn apply_patch_target(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Target [{}]",
+                key_config.get_hint(key_config.tab_toggle),
+            ),
+            "apply to working tree, index or as new commits",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn apply_patch_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Apply [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "apply the patches",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn reflog_switch_ref(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Ref [{}]",
+                key_config.get_hint(key_config.tab_toggle),
+            ),
+            "switch between the reflogs of HEAD and the branches",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn reflog_create_branch(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Branch [{}]",
+                key_config.get_hint(key_config.create_branch),
+            ),
+            "create a branch at the selected entry",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_compare_commits(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Compare [{}]",
+                key_config.get_hint(key_config.log_compare_commits),
+            ),
+            "compare the marked commit with the selected one",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_checkout_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Checkout [{}]",
+                key_config.get_hint(key_config.log_checkout_commit),
+            ),
+            "checkout the selected commit or tag as detached HEAD",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn reset_commit_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "reset using the selected mode",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_rebase_interactive(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Rebase [{}]",
+                key_config
+                    .get_hint(key_config.log_rebase_interactive),
+            ),
+            "interactively rebase the commits after the selected one",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_pick(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Pick [{}]",
+                key_config.get_hint(key_config.rebase_pick),
+            ),
+            "use the commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_reword(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reword [{}]",
+                key_config.get_hint(key_config.rebase_reword),
+            ),
+            "use the commit with a new message",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_squash(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Squash [{}]",
+                key_config.get_hint(key_config.rebase_squash),
+            ),
+            "meld the commit into the previous one",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_fixup(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Fixup [{}]",
+                key_config.get_hint(key_config.rebase_fixup),
+            ),
+            "meld the commit into the previous one, discarding its message",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_drop(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Drop [{}]",
+                key_config.get_hint(key_config.rebase_drop),
+            ),
+            "remove the commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_move(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Move [{}{}]",
+                key_config.get_hint(key_config.shift_up),
+                key_config.get_hint(key_config.shift_down),
+            ),
+            "move the commit up or down in the todo list",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_start(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Rebase [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "run the rebase",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_reword_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reword [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "use the new message",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_tag_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Tag [{}]",
+                key_config.get_hint(key_config.log_tag_commit),
+            ),
+            "tag commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_note_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Note [{}]",
+                key_config.get_hint(key_config.log_note_commit),
+            ),
+            "add or edit the note of the selected commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn note_commit_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Save [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "save the note, an empty note removes it",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn note_commit_open_editor(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Open editor [{}]",
+                key_config.get_hint(key_config.open_commit_editor),
+            ),
+            "edit the note in the external editor",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn tag_commit_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Tag [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "tag commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn create_branch_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Create Branch [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "create branch",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn open_branch_create_popup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Create [{}]",
+                key_config.get_hint(key_config.create_branch),
+            ),
+            "open create branch popup",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn rename_branch_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Rename Branch [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "rename branch",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn rename_branch_popup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Rename Branch [{}]",
+                key_config.get_hint(key_config.rename_branch),
+            ),
+            "rename branch",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn delete_branch_popup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Delete [{}]",
+                key_config.get_hint(key_config.delete_branch),
+            ),
+            "delete a branch",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn select_branch_popup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Checkout [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "checkout branch",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn toggle_branch_popup(
+        key_config: &SharedKeyConfig,
+        local: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} [{}]",
+                if local { "Remote" } else { "Local" },
+                key_config.get_hint(key_config.tab_toggle),
+            ),
+            "toggle branch type (remote/local)",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn open_branch_select_popup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Branches [{}]",
+                key_config.get_hint(key_config.select_branch),
+            ),
+            "open select branch popup",
+            CMD_GROUP_GENERAL,
+        )
+    }
+
+    pub fn open_file_history(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "History [{}]",
+                key_config.get_hint(key_config.file_history),
+            ),
+            "open history of selected file",
+            CMD_GROUP_GENERAL,
+        )
+    }
+
+    pub fn open_blame(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Blame [{}]",
+                key_config.get_hint(key_config.blame),
+            ),
+            "open blame view of selected file",
+            CMD_GROUP_GENERAL,
+        )
+    }
+
+    pub fn blame_inspect_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Inspect [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "inspect commit that last changed the selected line",
+            CMD_GROUP_GENERAL,
+        )
+    }
+
+    pub fn status_push(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Push [{}]",
+                key_config.get_hint(key_config.push),
+            ),
+            "push to origin",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn status_force_push(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Force Push [{}]",
+                key_config.get_hint(key_config.force_push),
+            ),
+            "force push to origin",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn status_continue_operation(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Continue [{}]",
+                key_config
+                    .get_hint(key_config.status_continue_operation),
+            ),
+            "commit the resolved conflicts and continue",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn status_abort_operation(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Abort [{}]",
+                key_config
+                    .get_hint(key_config.status_abort_operation),
+            ),
+            "abort the operation in progress",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn status_skip_operation(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Skip [{}]",
+                key_config.get_hint(key_config.status_skip_operation),
+            ),
+            "drop the conflicting commit and continue",
             CMD_GROUP_GENERAL,
         )
     }
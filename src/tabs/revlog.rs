@@ -5,19 +5,21 @@ use crate::{
         DrawableComponent,
     },
     keys::SharedKeyConfig,
-    queue::{InternalEvent, Queue},
+    options::SharedOptions,
+    queue::{Action, InternalEvent, Queue},
     strings,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
     cached,
-    sync::{self, CommitId},
-    AsyncLog, AsyncNotification, AsyncTags, FetchStatus, CWD,
+    sync::{self, CommitId, LogStart},
+    AsyncLog, AsyncLogSearch, AsyncNotes, AsyncNotification,
+    AsyncRefs, AsyncTags, FetchStatus, CWD,
 };
 use crossbeam_channel::Sender;
-use crossterm::event::Event;
-use std::time::Duration;
+use crossterm::event::{Event, KeyEvent};
+use std::{cmp::Reverse, time::Duration};
 use sync::CommitTags;
 use tui::{
     backend::Backend,
@@ -33,10 +35,19 @@ pub struct Revlog {
     list: CommitList,
     git_log: AsyncLog,
     git_tags: AsyncTags,
+    git_refs: AsyncRefs,
+    git_notes: AsyncNotes,
+    git_search: AsyncLogSearch,
+    search_query: Option<String>,
+    /// select the first match once results come in
+    search_jump: bool,
+    /// select once the walk reached it
+    goto_target: Option<CommitId>,
     queue: Queue,
     visible: bool,
     branch_name: cached::BranchName,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
 }
 
 impl Revlog {
@@ -46,6 +57,7 @@ impl Revlog {
         sender: &Sender<AsyncNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             queue: queue.clone(),
@@ -54,35 +66,68 @@ impl Revlog {
                 sender,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             list: CommitList::new(
                 &strings::log_title(&key_config),
                 theme,
                 key_config.clone(),
+                options.clone(),
             ),
             git_log: AsyncLog::new(sender),
             git_tags: AsyncTags::new(sender),
+            git_refs: AsyncRefs::new(sender),
+            git_notes: AsyncNotes::new(sender),
+            git_search: AsyncLogSearch::new(sender),
+            search_query: None,
+            search_jump: false,
+            goto_target: None,
             visible: false,
             branch_name: cached::BranchName::new(CWD),
             key_config,
+            options,
         }
     }
 
     ///
     pub fn any_work_pending(&self) -> bool {
         self.git_log.is_pending()
+            || self.git_search.is_pending()
             || self.git_tags.is_pending()
+            || self.git_refs.is_pending()
+            || self.git_notes.is_pending()
             || self.commit_details.any_work_pending()
     }
 
     ///
     pub fn update(&mut self) -> Result<()> {
         if self.visible {
-            let log_changed =
-                self.git_log.fetch()? == FetchStatus::Started;
+            let log_changed = match self.git_log.fetch() {
+                Ok(status) => status == FetchStatus::Started,
+                Err(e) if self.git_log.start() != &LogStart::Head => {
+                    // e.g. the branch was deleted in the meantime
+                    self.git_log.set_start(LogStart::Head);
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "log revision error:\n{}",
+                            e
+                        )),
+                    );
+                    self.git_log.fetch()? == FetchStatus::Started
+                }
+                Err(e) => return Err(e.into()),
+            };
 
             self.list.set_count_total(self.git_log.count()?);
 
+            if log_changed {
+                // indices of the old log are useless now
+                self.git_search.clear()?;
+            }
+
+            self.update_search()?;
+            self.update_goto()?;
+
             let selection = self.list.selection();
             let selection_max = self.list.selection_max();
             if self.list.items().needs_data(selection, selection_max)
@@ -92,10 +137,13 @@ impl Revlog {
             }
 
             self.git_tags.request(Duration::from_secs(3), false)?;
+            // a moved HEAD or branch tip changes the log too
+            self.git_refs
+                .request(Duration::from_secs(3), log_changed)?;
+            self.git_notes.request(Duration::from_secs(3), false)?;
 
-            self.list.set_branch(
-                self.branch_name.lookup().map(Some).unwrap_or(None),
-            );
+            let title = self.start_title();
+            self.list.set_branch(title);
 
             if self.commit_details.is_visible() {
                 let commit = self.selected_commit();
@@ -108,6 +156,12 @@ impl Revlog {
         Ok(())
     }
 
+    /// refetches the notes right away, e.g. after one was edited
+    pub fn update_notes(&mut self) -> Result<()> {
+        self.git_notes.request(Duration::from_secs(3), true)?;
+        Ok(())
+    }
+
     ///
     pub fn update_git(
         &mut self,
@@ -116,13 +170,26 @@ impl Revlog {
         if self.visible {
             match ev {
                 AsyncNotification::CommitFiles
-                | AsyncNotification::Log => self.update()?,
+                | AsyncNotification::Log
+                | AsyncNotification::LogSearch => self.update()?,
+                AsyncNotification::Refs => {
+                    if let Some(refs) = self.git_refs.last()? {
+                        self.list.set_refs(refs);
+                        self.update()?;
+                    }
+                }
                 AsyncNotification::Tags => {
                     if let Some(tags) = self.git_tags.last()? {
                         self.list.set_tags(tags);
                         self.update()?;
                     }
                 }
+                AsyncNotification::Notes => {
+                    if let Some(notes) = self.git_notes.last()? {
+                        self.list.set_notes(notes);
+                        self.update()?;
+                    }
+                }
                 _ => (),
             }
         }
@@ -130,6 +197,342 @@ impl Revlog {
         Ok(())
     }
 
+    /// searches the log for `query` and selects the first match
+    pub fn search(&mut self, query: String) -> Result<()> {
+        self.git_search.clear()?;
+        self.list.set_search(Some(query.clone()), Vec::new());
+        self.search_query = Some(query);
+        self.search_jump = true;
+        self.update()?;
+
+        Ok(())
+    }
+
+    /// selects `id` as soon as the log walk got to it, inspects it
+    /// if the log does not contain it
+    pub fn goto_commit(&mut self, id: CommitId) -> Result<()> {
+        self.goto_target = Some(id);
+        self.update()?;
+
+        Ok(())
+    }
+
+    /// shows the log starting from `start` instead of `HEAD`
+    pub fn set_start(&mut self, start: LogStart) -> Result<()> {
+        self.git_log.set_start(start);
+        self.list.clear();
+        self.list.clear_marked();
+        self.update()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn clear_marked(&mut self) {
+        self.list.clear_marked();
+    }
+
+    /// queues `event`, always `true` to mark the key as handled
+    fn queue_event(&self, event: InternalEvent) -> bool {
+        self.queue.borrow_mut().push_back(event);
+        true
+    }
+
+    /// popups that do not depend on the selection
+    fn popup_event(&mut self, k: KeyEvent) -> Option<InternalEvent> {
+        if k == self.key_config.log_search {
+            Some(InternalEvent::OpenLogSearch)
+        } else if k == self.key_config.log_revision {
+            Some(InternalEvent::OpenLogRevision)
+        } else if k == self.key_config.select_branch {
+            Some(InternalEvent::SelectBranch)
+        } else if k == self.key_config.log_open_reflog {
+            Some(InternalEvent::OpenReflog)
+        } else if k == self.key_config.log_apply_patch {
+            Some(InternalEvent::OpenApplyPatch)
+        } else if k == self.key_config.log_goto_commit {
+            Some(InternalEvent::OpenGotoCommit)
+        } else if k == self.key_config.log_author_stats {
+            Some(InternalEvent::OpenAuthorStats(
+                self.git_log.start().clone(),
+                self.start_title().unwrap_or_else(|| "HEAD".into()),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// queues the event for the selected commit, `false` if there
+    /// is none
+    fn queue_for_selected(
+        &self,
+        event: impl FnOnce(CommitId) -> InternalEvent,
+    ) -> bool {
+        self.selected_commit().map_or(false, |id| {
+            self.queue.borrow_mut().push_back(event(id));
+            true
+        })
+    }
+
+    fn commands_commit_ops(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        out.push(CommandInfo::new(
+            strings::commands::log_mark_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_clear_marked(&self.key_config),
+            true,
+            (self.visible
+                && self.search_query.is_none()
+                && !self.list.marked().is_empty())
+                || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_cherry_pick(&self.key_config),
+            self.selected_commit().is_some()
+                || !self.list.marked().is_empty(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_revert_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_open_reflog(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_format_patch(&self.key_config),
+            self.selected_commit().is_some()
+                || !self.list.marked().is_empty(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_author_stats(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_apply_patch(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_compare_commits(&self.key_config),
+            self.compare_target().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_checkout_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_reset_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_rebase_interactive(
+                &self.key_config,
+            ),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+    }
+
+    /// the single marked commit and the selected one, if they differ
+    fn compare_target(&self) -> Option<(CommitId, CommitId)> {
+        match (self.list.marked(), self.selected_commit()) {
+            ([marked], Some(selected)) if *marked != selected => {
+                Some((*marked, selected))
+            }
+            _ => None,
+        }
+    }
+
+    fn compare_commits(&self) -> bool {
+        self.compare_target().map_or(false, |(old, new)| {
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::CompareCommits(old, new));
+            true
+        })
+    }
+
+    /// checks out the selected commit, referring to it by its tag
+    /// if it has one
+    fn checkout_commit(&self) -> bool {
+        self.queue_for_selected(|id| {
+            InternalEvent::CheckoutCommit(
+                id,
+                self.selected_commit_tags(&Some(id))
+                    .and_then(|tags| tags.into_iter().next()),
+            )
+        })
+    }
+
+    fn format_patch(&self) -> Result<bool> {
+        let commits = self.commits_to_pick()?;
+
+        Ok(!commits.is_empty()
+            && self
+                .queue_event(InternalEvent::OpenFormatPatch(commits)))
+    }
+
+    fn confirm_cherry_pick(&self) -> Result<()> {
+        let commits = self.commits_to_pick()?;
+        if !commits.is_empty() {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::CherryPick(
+                    commits,
+                )),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn toggle_all_branches(&mut self) -> Result<()> {
+        let start = if self.git_log.start() == &LogStart::AllBranches
+        {
+            LogStart::Head
+        } else {
+            LogStart::AllBranches
+        };
+
+        self.set_start(start)
+    }
+
+    /// marked commits (oldest first) or else the selected one
+    fn commits_to_pick(&self) -> Result<Vec<CommitId>> {
+        let marked = self.list.marked();
+
+        if marked.is_empty() {
+            return Ok(self.selected_commit().into_iter().collect());
+        }
+
+        let mut marked = marked
+            .iter()
+            .map(|id| Ok((self.git_log.position(*id)?, *id)))
+            .collect::<Result<Vec<_>>>()?;
+        marked.sort_by_key(|(position, _)| Reverse(*position));
+
+        Ok(marked.into_iter().map(|(_, id)| id).collect())
+    }
+
+    /// leaves search, marking or a non `HEAD` log (in that order)
+    fn escape(&mut self) -> Result<bool> {
+        if self.search_query.is_some() {
+            self.clear_search()?;
+        } else if !self.list.marked().is_empty() {
+            self.list.clear_marked();
+        } else if self.git_log.start() != &LogStart::Head {
+            self.set_start(LogStart::Head)?;
+        } else {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    fn start_title(&mut self) -> Option<String> {
+        match self.git_log.start() {
+            LogStart::Head => self.branch_name.lookup().ok(),
+            LogStart::Rev(rev) => Some(rev.clone()),
+            LogStart::AllBranches => Some(
+                strings::log_all_branches_title(&self.key_config),
+            ),
+        }
+    }
+
+    fn clear_search(&mut self) -> Result<()> {
+        self.git_search.clear()?;
+        self.list.set_search(None, Vec::new());
+        self.search_query = None;
+        self.search_jump = false;
+
+        Ok(())
+    }
+
+    /// keeps searching while the log is still growing
+    fn update_search(&mut self) -> Result<()> {
+        if let Some(query) = self.search_query.clone() {
+            let count = self.git_log.count()?;
+
+            let last = self
+                .git_search
+                .last()?
+                .filter(|(params, _)| params.query == query);
+
+            let searched =
+                last.as_ref().map(|(params, _)| params.searched);
+
+            if let Some((_, matches)) = last {
+                self.list.set_search(Some(query.clone()), matches);
+
+                let complete = searched == Some(count)
+                    && !self.git_log.is_pending();
+
+                if self.search_jump
+                    && (complete || self.list.has_search_matches())
+                {
+                    self.search_jump = false;
+                    self.list.select_next_match(true);
+                }
+            }
+
+            if searched != Some(count)
+                && !self.git_search.is_pending()
+            {
+                // only the commits fetched since the last search
+                let offset = searched.unwrap_or_default().min(count);
+                self.git_search.request(
+                    query,
+                    offset,
+                    self.git_log.get_slice(offset, count - offset)?,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn update_goto(&mut self) -> Result<()> {
+        if let Some(id) = self.goto_target {
+            if let Some(idx) = self.git_log.position(id)? {
+                self.goto_target = None;
+                self.list.select(idx);
+            } else if !self.git_log.is_pending() {
+                // not reachable from the start of the log
+                self.goto_target = None;
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::InspectCommit(id, None),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     fn fetch_commits(&mut self) -> Result<()> {
         let want_min =
             self.list.selection().saturating_sub(SLICE_SIZE / 2);
@@ -142,6 +545,9 @@ impl Revlog {
 
         if let Ok(commits) = commits {
             self.list.items().set_items(want_min, commits);
+            self.list.items().set_graph(
+                self.git_log.get_graph_slice(want_min, SLICE_SIZE)?,
+            );
         }
 
         Ok(())
@@ -218,15 +624,13 @@ impl Component for Revlog {
                         .push_back(InternalEvent::PushTags);
                     return Ok(true);
                 } else if k == self.key_config.log_tag_commit {
-                    return self.selected_commit().map_or(
-                        Ok(false),
-                        |id| {
-                            self.queue.borrow_mut().push_back(
-                                InternalEvent::TagCommit(id),
-                            );
-                            Ok(true)
-                        },
-                    );
+                    return Ok(self.queue_for_selected(
+                        InternalEvent::TagCommit,
+                    ));
+                } else if k == self.key_config.log_note_commit {
+                    return Ok(self.queue_for_selected(
+                        InternalEvent::NoteCommit,
+                    ));
                 } else if k == self.key_config.focus_right
                     && self.commit_details.is_visible()
                 {
@@ -244,10 +648,51 @@ impl Component for Revlog {
                             Ok(true)
                         },
                     );
-                } else if k == self.key_config.select_branch {
-                    self.queue
+                } else if (k == self.key_config.log_search_next
+                    && self.list.select_next_match(false))
+                    || (k == self.key_config.log_search_prev
+                        && self.list.select_prev_match())
+                {
+                    self.update()?;
+                    return Ok(true);
+                } else if k == self.key_config.exit_popup
+                    && self.escape()?
+                {
+                    return Ok(true);
+                } else if k == self.key_config.log_mark_commit {
+                    return Ok(self.list.toggle_marked());
+                } else if k == self.key_config.log_cherry_pick {
+                    self.confirm_cherry_pick()?;
+                    return Ok(true);
+                } else if k == self.key_config.log_revert_commit {
+                    return Ok(self.queue_for_selected(|id| {
+                        InternalEvent::RevertCommit(id, None)
+                    }));
+                } else if let Some(event) = self.popup_event(k) {
+                    return Ok(self.queue_event(event));
+                } else if k == self.key_config.log_format_patch {
+                    return self.format_patch();
+                } else if k == self.key_config.log_compare_commits {
+                    return Ok(self.compare_commits());
+                } else if k == self.key_config.log_checkout_commit {
+                    return Ok(self.checkout_commit());
+                } else if k == self.key_config.log_reset_commit {
+                    return Ok(self.queue_for_selected(
+                        InternalEvent::OpenResetCommit,
+                    ));
+                } else if k == self.key_config.log_rebase_interactive
+                {
+                    return Ok(self.queue_for_selected(
+                        InternalEvent::OpenRebaseInteractive,
+                    ));
+                } else if k == self.key_config.log_all_branches {
+                    self.toggle_all_branches()?;
+                    return Ok(true);
+                } else if k == self.key_config.log_toggle_author_date
+                {
+                    self.options
                         .borrow_mut()
-                        .push_back(InternalEvent::SelectBranch);
+                        .toggle_log_author_date();
                     return Ok(true);
                 }
             }
@@ -284,6 +729,12 @@ impl Component for Revlog {
             self.visible || force_all,
         ));
 
+        out.push(CommandInfo::new(
+            strings::commands::log_note_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
         out.push(CommandInfo::new(
             strings::commands::open_branch_select_popup(
                 &self.key_config,
@@ -292,6 +743,68 @@ impl Component for Revlog {
             self.visible || force_all,
         ));
 
+        out.push(CommandInfo::new(
+            strings::commands::log_search(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_next_prev(&self.key_config),
+            self.list.has_search_matches(),
+            (self.visible && self.search_query.is_some())
+                || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_clear(&self.key_config),
+            true,
+            (self.visible && self.search_query.is_some())
+                || force_all,
+        ));
+
+        self.commands_commit_ops(out, force_all);
+
+        out.push(CommandInfo::new(
+            strings::commands::log_revision(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_goto_commit(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_all_branches(
+                &self.key_config,
+                self.git_log.start() == &LogStart::AllBranches,
+            ),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_author_date(
+                &self.key_config,
+                self.options.borrow().log_author_date,
+            ),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_head(&self.key_config),
+            true,
+            (self.visible
+                && self.search_query.is_none()
+                && self.list.marked().is_empty()
+                && self.git_log.start() != &LogStart::Head)
+                || force_all,
+        ));
+
         out.push(CommandInfo::new(
             strings::commands::copy_hash(&self.key_config),
             true,
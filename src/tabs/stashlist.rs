@@ -4,6 +4,7 @@ use crate::{
         CommitList, Component, DrawableComponent,
     },
     keys::SharedKeyConfig,
+    options::SharedOptions,
     queue::{Action, InternalEvent, Queue},
     strings,
     ui::style::SharedTheme,
@@ -28,6 +29,7 @@ impl StashList {
         queue: &Queue,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             visible: false,
@@ -35,6 +37,7 @@ impl StashList {
                 &strings::stashlist_title(&key_config),
                 theme,
                 key_config.clone(),
+                options,
             ),
             queue: queue.clone(),
             key_config,
@@ -6,15 +6,20 @@ use crate::{
         DiffComponent, DrawableComponent, FileTreeItemKind,
     },
     keys::SharedKeyConfig,
-    queue::{Action, InternalEvent, Queue, ResetItem},
+    options::SharedOptions,
+    queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
     strings,
     ui::style::SharedTheme,
+    AsyncAppNotification,
 };
 use anyhow::Result;
 use asyncgit::{
     cached,
     sync::BranchCompare,
-    sync::{self, status::StatusType, RepoState},
+    sync::{
+        self, status::StatusType, CherryPickResult, CommitId,
+        RebaseEntry, RepoState,
+    },
     AsyncDiff, AsyncNotification, AsyncStatus, DiffParams, DiffType,
     StatusParams, CWD,
 };
@@ -65,6 +70,11 @@ pub struct Status {
     git_status_stage: AsyncStatus,
     git_branch_state: Option<BranchCompare>,
     git_branch_name: cached::BranchName,
+    git_repo_state: RepoState,
+    /// commits left to cherry-pick after the one in progress
+    cherry_pick_todo_len: usize,
+    /// entries left in a paused interactive rebase
+    rebase_todo_len: usize,
     queue: Queue,
     git_action_executed: bool,
     key_config: SharedKeyConfig,
@@ -116,7 +126,7 @@ impl DrawableComponent for Status {
         self.index.draw(f, left_chunks[1])?;
         self.diff.draw(f, chunks[1])?;
         self.draw_branch_state(f, &left_chunks);
-        Self::draw_repo_state(f, left_chunks[0]);
+        self.draw_repo_state(f, left_chunks[0]);
 
         Ok(())
     }
@@ -129,8 +139,10 @@ impl Status {
     pub fn new(
         queue: &Queue,
         sender: &Sender<AsyncNotification>,
+        sender_app: &Sender<AsyncAppNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             queue: queue.clone(),
@@ -154,9 +166,11 @@ impl Status {
                 key_config.clone(),
             ),
             diff: DiffComponent::new(
+                sender_app,
                 queue.clone(),
                 theme,
                 key_config.clone(),
+                options,
                 false,
             ),
             git_diff: AsyncDiff::new(sender),
@@ -165,6 +179,9 @@ impl Status {
             git_action_executed: false,
             git_branch_state: None,
             git_branch_name: cached::BranchName::new(CWD),
+            git_repo_state: RepoState::Clean,
+            cherry_pick_todo_len: 0,
+            rebase_todo_len: 0,
             key_config,
         }
     }
@@ -209,12 +226,26 @@ impl Status {
     }
 
     fn draw_repo_state<B: tui::backend::Backend>(
+        &self,
         f: &mut tui::Frame<B>,
         r: tui::layout::Rect,
     ) {
         if let Ok(state) = asyncgit::sync::repo_state(CWD) {
             if state != RepoState::Clean {
-                let txt = format!("{:?}", state);
+                let more = match state {
+                    RepoState::CherryPick => {
+                        self.cherry_pick_todo_len
+                    }
+                    RepoState::Rebase => {
+                        self.rebase_todo_len.saturating_sub(1)
+                    }
+                    _ => 0,
+                };
+                let txt = if more > 0 {
+                    format!("{:?} ({} more)", state, more)
+                } else {
+                    format!("{:?}", state)
+                };
                 let txt_len = u16::try_from(txt.len())
                     .expect("state name too long");
                 let w = Paragraph::new(txt)
@@ -301,6 +332,24 @@ impl Status {
     ///
     pub fn update(&mut self) -> Result<()> {
         self.git_branch_name.lookup().map(Some).unwrap_or(None);
+        self.git_repo_state =
+            sync::repo_state(CWD).unwrap_or(RepoState::Clean);
+        self.cherry_pick_todo_len =
+            if self.git_repo_state == RepoState::CherryPick {
+                sync::cherry_pick_todo(CWD)
+                    .map(|todo| todo.len())
+                    .unwrap_or_default()
+            } else {
+                0
+            };
+        self.rebase_todo_len =
+            if self.git_repo_state == RepoState::Rebase {
+                sync::rebase_interactive_todo(CWD)
+                    .map(|todo| todo.len())
+                    .unwrap_or_default()
+            } else {
+                0
+            };
 
         if self.is_visible() {
             self.git_diff.refresh()?;
@@ -341,6 +390,15 @@ impl Status {
         Ok(())
     }
 
+    ///
+    pub fn update_app(&mut self, ev: AsyncAppNotification) {
+        match ev {
+            AsyncAppNotification::SyntaxHighlighting => {
+                self.diff.update_syntax();
+            }
+        }
+    }
+
     fn update_status(&mut self) -> Result<()> {
         let stage_status = self.git_status_stage.last()?;
         self.index.set_items(&stage_status.items)?;
@@ -408,7 +466,7 @@ impl Status {
     }
 
     /// called after confirmation
-    pub fn reset(&mut self, item: &ResetItem) -> bool {
+    pub fn reset(&self, item: &ResetItem) -> bool {
         if let Err(e) = sync::reset_workdir(CWD, item.path.as_str()) {
             self.queue.borrow_mut().push_back(
                 InternalEvent::ShowErrorMsg(format!(
@@ -425,7 +483,7 @@ impl Status {
 
     fn push(&self, force: bool) {
         if self.can_push() {
-            if let Some(branch) = self.git_branch_name.last() {
+            if let Some(branch) = self.git_branch_name.last_branch() {
                 if force {
                     self.queue.borrow_mut().push_back(
                         InternalEvent::ConfirmAction(
@@ -442,16 +500,175 @@ impl Status {
     }
 
     fn pull(&self) {
-        if let Some(branch) = self.git_branch_name.last() {
+        if let Some(branch) = self.git_branch_name.last_branch() {
             self.queue
                 .borrow_mut()
                 .push_back(InternalEvent::Pull(branch));
         }
     }
 
+    /// picks `commits` onto `HEAD`, returns `true` if it stopped
+    /// with conflicts
+    pub fn cherry_pick(&self, commits: &[CommitId]) -> bool {
+        self.cherry_pick_result(sync::cherry_pick(CWD, commits))
+    }
+
+    fn cherry_pick_result<E: std::fmt::Display>(
+        &self,
+        res: std::result::Result<CherryPickResult, E>,
+    ) -> bool {
+        match res {
+            Ok(res) => {
+                if let Some(conflict) = res.remaining.first() {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(
+                            strings::operation_conflict_msg(
+                                &self.key_config,
+                                "cherry-pick",
+                                &conflict.get_short_string(),
+                            ),
+                        ),
+                    );
+                    return true;
+                }
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "cherry-pick error:\n{}",
+                        e
+                    )),
+                );
+            }
+        }
+
+        false
+    }
+
+    /// replays `todo` onto `base`, returns `true` if it stopped
+    /// with conflicts
+    pub fn rebase_interactive(
+        &self,
+        base: CommitId,
+        todo: &[RebaseEntry],
+    ) -> bool {
+        self.rebase_result(sync::rebase_interactive(CWD, base, todo))
+    }
+
+    fn rebase_result<E: std::fmt::Display>(
+        &self,
+        res: std::result::Result<Option<CommitId>, E>,
+    ) -> bool {
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+
+        match res {
+            Ok(Some(conflict)) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(
+                        strings::rebase_conflict_msg(
+                            &self.key_config,
+                            &conflict.get_short_string(),
+                        ),
+                    ),
+                );
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "rebase error:\n{}",
+                        e
+                    )),
+                );
+                false
+            }
+        }
+    }
+
+    /// aborts the cherry-pick, revert or rebase in progress
+    pub fn abort_operation(&self) -> Result<()> {
+        match self.git_repo_state {
+            RepoState::Rebase => sync::rebase_interactive_abort(CWD)?,
+            RepoState::CherryPick => sync::cherry_pick_abort(CWD)?,
+            _ => sync::abort_pending_state(CWD)?,
+        }
+
+        Ok(())
+    }
+
+    const fn operation_in_progress(&self) -> bool {
+        matches!(
+            self.git_repo_state,
+            RepoState::CherryPick
+                | RepoState::Revert
+                | RepoState::Rebase
+        )
+    }
+
+    fn continue_operation(&self) -> Result<()> {
+        match self.git_repo_state {
+            RepoState::CherryPick => self.continue_cherry_pick(),
+            RepoState::Revert => {
+                let msg = sync::merge_msg(CWD)?;
+                self.queue
+                    .borrow_mut()
+                    .push_back(InternalEvent::OpenCommitWithMsg(msg));
+            }
+            RepoState::Rebase => {
+                self.rebase_result(
+                    sync::rebase_interactive_continue(CWD),
+                );
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn continue_cherry_pick(&self) {
+        self.cherry_pick_result(sync::cherry_pick_continue(CWD));
+
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+    }
+
+    fn commands_operation(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        let in_progress = self.operation_in_progress();
+
+        out.push(CommandInfo::new(
+            strings::commands::status_continue_operation(
+                &self.key_config,
+            ),
+            true,
+            in_progress || force_all,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::status_abort_operation(
+                &self.key_config,
+            ),
+            true,
+            in_progress || force_all,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::status_skip_operation(
+                &self.key_config,
+            ),
+            true,
+            self.git_repo_state == RepoState::Rebase || force_all,
+        ));
+    }
+
     fn branch_compare(&mut self) {
         self.git_branch_state =
-            self.git_branch_name.last().and_then(|branch| {
+            self.git_branch_name.last_branch().and_then(|branch| {
                 sync::branch_compare_upstream(CWD, branch.as_str())
                     .ok()
             });
@@ -504,6 +721,8 @@ impl Component for Status {
                 true,
                 !focus_on_diff,
             ));
+
+            self.commands_operation(out, force_all);
         }
 
         {
@@ -634,6 +853,28 @@ impl Component for Status {
                 {
                     self.pull();
                     Ok(true)
+                } else if k
+                    == self.key_config.status_continue_operation
+                    && self.operation_in_progress()
+                {
+                    self.continue_operation()?;
+                    Ok(true)
+                } else if k == self.key_config.status_skip_operation
+                    && self.git_repo_state == RepoState::Rebase
+                {
+                    self.rebase_result(
+                        sync::rebase_interactive_skip(CWD),
+                    );
+                    Ok(true)
+                } else if k == self.key_config.status_abort_operation
+                    && self.operation_in_progress()
+                {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ConfirmAction(
+                            Action::AbortOperation,
+                        ),
+                    );
+                    Ok(true)
                 } else {
                     Ok(false)
                 };
@@ -5,7 +5,7 @@ use tui::{
     buffer::Buffer,
     layout::Rect,
     style::Style,
-    text::Span,
+    text::{Span, Spans},
     widgets::{Block, Borders, List, ListItem, Widget},
     Frame,
 };
@@ -13,7 +13,7 @@ use tui::{
 ///
 struct ScrollableList<'b, L>
 where
-    L: Iterator<Item = Span<'b>>,
+    L: Iterator<Item = Spans<'b>>,
 {
     block: Option<Block<'b>>,
     /// Items to be displayed
@@ -24,7 +24,7 @@ where
 
 impl<'b, L> ScrollableList<'b, L>
 where
-    L: Iterator<Item = Span<'b>>,
+    L: Iterator<Item = Spans<'b>>,
 {
     fn new(items: L) -> Self {
         Self {
@@ -42,7 +42,7 @@ where
 
 impl<'b, L> Widget for ScrollableList<'b, L>
 where
-    L: Iterator<Item = Span<'b>>,
+    L: Iterator<Item = Spans<'b>>,
 {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // Render items
@@ -63,7 +63,7 @@ pub fn draw_list<'b, B: Backend, L>(
     selected: bool,
     theme: &SharedTheme,
 ) where
-    L: Iterator<Item = Span<'b>>,
+    L: Iterator<Item = Spans<'b>>,
 {
     let list = ScrollableList::new(items).block(
         Block::default()
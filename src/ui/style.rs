@@ -2,7 +2,7 @@
 #![allow(clippy::use_self)]
 
 use anyhow::Result;
-use asyncgit::{DiffLineType, StatusItemType};
+use asyncgit::{sync::CommitRef, DiffLineType, StatusItemType};
 use ron::{
     de::from_bytes,
     ser::{to_string_pretty, PrettyConfig},
@@ -33,6 +33,23 @@ pub struct Theme {
     diff_line_add: Color,
     #[serde(with = "Color")]
     diff_line_delete: Color,
+    #[serde(with = "Color", default = "default_diff_line_add_bg")]
+    diff_line_add_bg: Color,
+    #[serde(with = "Color", default = "default_diff_line_delete_bg")]
+    diff_line_delete_bg: Color,
+    #[serde(
+        with = "Color",
+        default = "default_diff_line_add_emphasis_bg"
+    )]
+    diff_line_add_emphasis_bg: Color,
+    #[serde(
+        with = "Color",
+        default = "default_diff_line_delete_emphasis_bg"
+    )]
+    diff_line_delete_emphasis_bg: Color,
+    /// syntect theme used to highlight diffs
+    #[serde(default = "default_syntax")]
+    syntax: String,
     #[serde(with = "Color")]
     diff_file_added: Color,
     #[serde(with = "Color")]
@@ -48,6 +65,12 @@ pub struct Theme {
     #[serde(with = "Color")]
     commit_author: Color,
     #[serde(with = "Color")]
+    commit_head: Color,
+    #[serde(with = "Color")]
+    commit_local_branch: Color,
+    #[serde(with = "Color")]
+    commit_remote_branch: Color,
+    #[serde(with = "Color")]
     danger_fg: Color,
     #[serde(with = "Color")]
     push_gauge_bg: Color,
@@ -193,6 +216,49 @@ impl Theme {
         self.apply_select(style, selected)
     }
 
+    /// changed words within a changed line
+    pub fn diff_line_emphasis(
+        &self,
+        typ: DiffLineType,
+        selected: bool,
+    ) -> Style {
+        self.diff_line(typ, selected)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+    }
+
+    /// background of a syntax highlighted diff line, the syntax
+    /// colors are patched on top
+    pub fn diff_line_bg(
+        &self,
+        typ: DiffLineType,
+        selected: bool,
+        emphasized: bool,
+    ) -> Style {
+        let style = match typ {
+            DiffLineType::Add => Style::default().bg(if emphasized {
+                self.diff_line_add_emphasis_bg
+            } else {
+                self.diff_line_add_bg
+            }),
+            DiffLineType::Delete => {
+                Style::default().bg(if emphasized {
+                    self.diff_line_delete_emphasis_bg
+                } else {
+                    self.diff_line_delete_bg
+                })
+            }
+            DiffLineType::Header | DiffLineType::None => {
+                Style::default()
+            }
+        };
+
+        self.apply_select(style, selected)
+    }
+
+    pub fn syntax(&self) -> &str {
+        &self.syntax
+    }
+
     pub fn text_danger(&self) -> Style {
         Style::default().fg(self.danger_fg)
     }
@@ -229,6 +295,47 @@ impl Theme {
         )
     }
 
+    pub fn commit_ref(
+        &self,
+        commit_ref: &CommitRef,
+        selected: bool,
+    ) -> Style {
+        let color = match commit_ref {
+            CommitRef::Head | CommitRef::HeadBranch(_) => {
+                self.commit_head
+            }
+            CommitRef::LocalBranch(_) => self.commit_local_branch,
+            CommitRef::RemoteBranch(_) => self.commit_remote_branch,
+        };
+
+        self.apply_select(
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+            selected,
+        )
+    }
+
+    pub fn search_match(&self, selected: bool) -> Style {
+        self.text(true, selected)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    }
+
+    pub fn commit_graph(&self, lane: usize, selected: bool) -> Style {
+        const LANE_COLORS: [Color; 6] = [
+            Color::Red,
+            Color::Green,
+            Color::Yellow,
+            Color::Blue,
+            Color::Magenta,
+            Color::Cyan,
+        ];
+
+        self.apply_select(
+            Style::default()
+                .fg(LANE_COLORS[lane % LANE_COLORS.len()]),
+            selected,
+        )
+    }
+
     pub fn push_gauge(&self) -> Style {
         Style::default()
             .fg(self.push_gauge_fg)
@@ -276,6 +383,28 @@ impl Theme {
     }
 }
 
+// the following were added later, older theme files lack them
+
+const fn default_diff_line_add_bg() -> Color {
+    Color::Indexed(22)
+}
+
+const fn default_diff_line_delete_bg() -> Color {
+    Color::Indexed(52)
+}
+
+const fn default_diff_line_add_emphasis_bg() -> Color {
+    Color::Indexed(28)
+}
+
+const fn default_diff_line_delete_emphasis_bg() -> Color {
+    Color::Indexed(88)
+}
+
+fn default_syntax() -> String {
+    String::from("base16-eighties.dark")
+}
+
 impl Default for Theme {
     fn default() -> Self {
         Self {
@@ -286,6 +415,13 @@ impl Default for Theme {
             disabled_fg: Color::DarkGray,
             diff_line_add: Color::Green,
             diff_line_delete: Color::Red,
+            diff_line_add_bg: default_diff_line_add_bg(),
+            diff_line_delete_bg: default_diff_line_delete_bg(),
+            diff_line_add_emphasis_bg:
+                default_diff_line_add_emphasis_bg(),
+            diff_line_delete_emphasis_bg:
+                default_diff_line_delete_emphasis_bg(),
+            syntax: default_syntax(),
             diff_file_added: Color::LightGreen,
             diff_file_removed: Color::LightRed,
             diff_file_moved: Color::LightMagenta,
@@ -293,6 +429,9 @@ impl Default for Theme {
             commit_hash: Color::Magenta,
             commit_time: Color::LightCyan,
             commit_author: Color::Green,
+            commit_head: Color::Cyan,
+            commit_local_branch: Color::LightGreen,
+            commit_remote_branch: Color::LightRed,
             danger_fg: Color::Red,
             push_gauge_bg: Color::Blue,
             push_gauge_fg: Color::Reset,
@@ -0,0 +1,282 @@
+use crate::AsyncAppNotification;
+use asyncgit::{hash, DiffLine, DiffLineType, FileDiff};
+use crossbeam_channel::Sender;
+use once_cell::sync::Lazy;
+use scopetime::scope_time;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ops::Range,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{FontStyle, Style as SyntectStyle, ThemeSet},
+    parsing::{SyntaxReference, SyntaxSet},
+};
+use tui::style::{Color, Modifier, Style};
+
+/// number of highlighted diffs kept around
+const CACHE_SIZE: usize = 32;
+
+static SYNTAX_SET: Lazy<SyntaxSet> =
+    Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+/// syntax styles of a single diff line (byte ranges of its content)
+pub type SyntaxLine = Vec<(Style, Range<usize>)>;
+
+/// syntax highlighting of all lines of a `FileDiff`
+#[derive(Default)]
+pub struct SyntaxText {
+    /// indexed like the lines of all hunks one after the other,
+    /// empty for headers and if the language is unknown
+    lines: Vec<SyntaxLine>,
+}
+
+impl SyntaxText {
+    /// highlights `diff` of the file at `path` using the syntect
+    /// theme `theme`, the language is chosen by the file extension
+    pub fn new(diff: &FileDiff, path: &str, theme: &str) -> Self {
+        scope_time!("syntax_highlighting");
+
+        let syntax = match find_syntax(path) {
+            Some(syntax) => syntax,
+            None => return Self::default(),
+        };
+        let theme = if let Some(res) = THEME_SET.themes.get(theme) {
+            res
+        } else {
+            log::error!("unknown syntax theme: {}", theme);
+            return Self::default();
+        };
+
+        let mut lines = Vec::with_capacity(diff.lines);
+
+        for hunk in &diff.hunks {
+            // old and new side are highlighted separately so that
+            // state like open block comments is tracked correctly
+            let mut old = HighlightLines::new(syntax, theme);
+            let mut new = HighlightLines::new(syntax, theme);
+
+            for line in &hunk.lines {
+                let res = match line.line_type {
+                    DiffLineType::Header => Vec::new(),
+                    DiffLineType::Delete => highlight(&mut old, line),
+                    DiffLineType::Add => highlight(&mut new, line),
+                    DiffLineType::None => {
+                        highlight(&mut old, line);
+                        highlight(&mut new, line)
+                    }
+                };
+
+                lines.push(res);
+            }
+        }
+
+        Self { lines }
+    }
+
+    /// styles of the line at `index` (counting all hunk lines)
+    pub fn line(&self, index: usize) -> Option<&SyntaxLine> {
+        self.lines.get(index).filter(|line| !line.is_empty())
+    }
+}
+
+fn find_syntax(path: &str) -> Option<&'static SyntaxReference> {
+    let path = Path::new(path);
+
+    // files like `Makefile` are matched by their name
+    path.extension()
+        .or_else(|| path.file_name())
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| SYNTAX_SET.find_syntax_by_extension(ext))
+}
+
+fn highlight(h: &mut HighlightLines, line: &DiffLine) -> SyntaxLine {
+    let content =
+        line.content.trim_matches(|c| c == '\n' || c == '\r');
+    // the newline syntaxes expect every line to be terminated
+    let content = format!("{}\n", content);
+
+    let ranges = match h.highlight_line(&content, &SYNTAX_SET) {
+        Ok(ranges) => ranges,
+        Err(e) => {
+            log::error!("syntax highlighting error: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut start = 0;
+    ranges
+        .into_iter()
+        .filter_map(|(style, text)| {
+            let range = start..start + text.len();
+            start = range.end;
+
+            let range = range.start..range.end.min(content.len() - 1);
+            if range.is_empty() {
+                None
+            } else {
+                Some((to_tui_style(style), range))
+            }
+        })
+        .collect()
+}
+
+fn to_tui_style(style: SyntectStyle) -> Style {
+    let fg = style.foreground;
+    let mut res = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
+
+    if style.font_style.contains(FontStyle::BOLD) {
+        res = res.add_modifier(Modifier::BOLD);
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        res = res.add_modifier(Modifier::ITALIC);
+    }
+
+    res
+}
+
+#[derive(Default)]
+struct SyntaxCache {
+    entries: HashMap<u64, Arc<SyntaxText>>,
+    /// keys from oldest to newest
+    order: VecDeque<u64>,
+    pending: HashSet<u64>,
+}
+
+impl SyntaxCache {
+    fn insert(&mut self, key: u64, text: SyntaxText) {
+        self.pending.remove(&key);
+
+        if self.order.len() >= CACHE_SIZE {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.order.push_back(key);
+        self.entries.insert(key, Arc::new(text));
+    }
+}
+
+/// highlights diffs off the ui thread and caches the results by the
+/// hash of the diff (and its path)
+pub struct AsyncSyntaxJob {
+    cache: Arc<Mutex<SyntaxCache>>,
+    sender: Sender<AsyncAppNotification>,
+}
+
+impl AsyncSyntaxJob {
+    ///
+    pub fn new(sender: &Sender<AsyncAppNotification>) -> Self {
+        Self {
+            cache: Arc::new(Mutex::new(SyntaxCache::default())),
+            sender: sender.clone(),
+        }
+    }
+
+    /// key a highlighting of `diff_hash` at `path` is cached under
+    pub fn key(path: &str, diff_hash: u64) -> u64 {
+        hash(&(path, diff_hash))
+    }
+
+    /// cached highlighting for `key` if it was computed already
+    pub fn get(&self, key: u64) -> Option<Arc<SyntaxText>> {
+        self.cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.entries.get(&key).cloned())
+    }
+
+    /// starts highlighting `diff` unless cached or already pending,
+    /// sends `AsyncAppNotification::SyntaxHighlighting` when done
+    pub fn request(
+        &self,
+        key: u64,
+        path: String,
+        diff: FileDiff,
+        theme: String,
+    ) {
+        {
+            let mut cache = match self.cache.lock() {
+                Ok(cache) => cache,
+                Err(_) => return,
+            };
+
+            if cache.entries.contains_key(&key)
+                || !cache.pending.insert(key)
+            {
+                return;
+            }
+        }
+
+        let arc_cache = Arc::clone(&self.cache);
+        let sender = self.sender.clone();
+
+        rayon_core::spawn(move || {
+            let text = SyntaxText::new(&diff, &path, &theme);
+
+            if let Ok(mut cache) = arc_cache.lock() {
+                cache.insert(key, text);
+            }
+
+            sender
+                .send(AsyncAppNotification::SyntaxHighlighting)
+                .expect("error sending notify");
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asyncgit::sync::diff::Hunk;
+
+    fn line(content: &str, line_type: DiffLineType) -> DiffLine {
+        DiffLine {
+            content: content.to_string(),
+            line_type,
+            ..DiffLine::default()
+        }
+    }
+
+    #[test]
+    fn test_highlight_diff() {
+        let diff = FileDiff {
+            hunks: vec![Hunk {
+                header_hash: 0,
+                lines: vec![
+                    line("@@ -1,2 +1,2 @@\n", DiffLineType::Header),
+                    line("/* a\n", DiffLineType::Delete),
+                    line("fn a() {}\n", DiffLineType::Add),
+                    line("*/\n", DiffLineType::None),
+                ],
+            }],
+            lines: 4,
+            ..FileDiff::default()
+        };
+
+        let text =
+            SyntaxText::new(&diff, "src/lib.rs", "base16-ocean.dark");
+
+        assert!(text.line(0).is_none());
+        assert!(text.line(1).is_some());
+
+        // `fn` is a keyword on the new side
+        let add = text.line(2).unwrap();
+        assert_eq!(add[0].1, 0..2);
+        assert_ne!(add[0].0, add[1].0);
+
+        // styles never cover the appended newline
+        assert_eq!(text.line(3).unwrap().last().unwrap().1.end, 2);
+
+        let unknown = SyntaxText::new(
+            &diff,
+            "file.unknown",
+            "base16-ocean.dark",
+        );
+        assert!(unknown.line(2).is_none());
+    }
+}